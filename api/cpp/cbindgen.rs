@@ -63,7 +63,9 @@ fn default_config() -> cbindgen::Config {
                 ("VoidArg".into(), "void".into()),
                 ("KeyEventArg".into(), "KeyEvent".into()),
                 ("PointerEventArg".into(), "PointerEvent".into()),
+                ("PinchEventArg".into(), "PinchEvent".into()),
                 ("PointArg".into(), "Point".into()),
+                ("StringArg".into(), "SharedString".into()),
                 ("FloatArg".into(), "float".into()),
                 ("Coord".into(), "float".into()),
             ]
@@ -141,16 +143,21 @@ fn gen_corelib(
         "TextHorizontalAlignment",
         "TextVerticalAlignment",
         "TextOverflow",
+        "TextClipAxis",
         "TextWrap",
         "ImageFit",
         "FillRule",
         "MouseCursor",
         "InputType",
+        "CaretStyle",
+        "SuggestionAcceptKey",
+        "ColorScheme",
         "StandardButtonKind",
         "DialogButtonRole",
         "PointerEventKind",
         "PointerEventButton",
         "PointerEvent",
+        "PinchEvent",
     ]
     .iter()
     .chain(items.iter())
@@ -193,7 +200,9 @@ fn gen_corelib(
         "VoidArg",
         "KeyEventArg",
         "PointerEventArg",
+        "PinchEventArg",
         "PointArg",
+        "StringArg",
         "Point",
         "slint_color_brighter",
         "slint_color_darker",
@@ -307,6 +316,7 @@ fn gen_corelib(
             "slint_windowrc_get_scale_factor",
             "slint_windowrc_set_scale_factor",
             "slint_windowrc_set_focus_item",
+            "slint_windowrc_set_input_mask",
             "slint_windowrc_set_component",
             "slint_windowrc_show_popup",
             "slint_windowrc_set_rendering_notifier",
@@ -433,6 +443,13 @@ fn gen_corelib(
         .body
         .insert("Flickable".to_owned(), "    inline Flickable(); inline ~Flickable();".into());
     config.export.pre_body.insert("FlickableDataBox".to_owned(), "struct FlickableData;".into());
+    config.export.body.insert("Text".to_owned(), "    inline Text(); inline ~Text();".into());
+    config.export.pre_body.insert("TextDataBox".to_owned(), "struct TextData;".into());
+    config
+        .export
+        .body
+        .insert("TextInput".to_owned(), "    inline TextInput(); inline ~TextInput();".into());
+    config.export.pre_body.insert("TextInputDataBox".to_owned(), "struct TextInputData;".into());
     config.export.include.push("StandardListViewItem".into());
     cbindgen::Builder::new()
         .with_config(config)
@@ -457,7 +474,7 @@ fn gen_corelib(
     namespace cbindgen_private {
         using slint::private_api::PlatformWindowRc;
         using namespace vtable;
-        struct KeyEvent; struct PointerEvent;
+        struct KeyEvent; struct PointerEvent; struct PinchEvent;
         using private_api::Property;
         using private_api::PathData;
         using private_api::Point;
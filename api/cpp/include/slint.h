@@ -60,6 +60,7 @@ using ItemArray = slint::cbindgen_private::Slice<ItemArrayEntry>;
 using cbindgen_private::KeyboardModifiers;
 using cbindgen_private::KeyEvent;
 using cbindgen_private::PointerEvent;
+using cbindgen_private::PinchEvent;
 using cbindgen_private::StandardListViewItem;
 
 /// Internal function that checks that the API that must be called from the main
@@ -128,6 +129,17 @@ public:
         cbindgen_private::slint_windowrc_set_focus_item(&inner, &item_rc);
     }
 
+    void set_input_mask(const ComponentRc &component_rc, uintptr_t item_index)
+    {
+        cbindgen_private::ItemRc item_rc { component_rc, item_index };
+        cbindgen_private::slint_windowrc_set_input_mask(&inner, &item_rc);
+    }
+
+    void clear_input_mask()
+    {
+        cbindgen_private::slint_windowrc_set_input_mask(&inner, nullptr);
+    }
+
     template<typename Component, typename ItemArray>
     void register_component(Component *c, ItemArray items) const
     {
@@ -909,6 +921,19 @@ public:
         viewport_height->set(h);
     }
 
+    void ensure_updated_listview_horizontal(
+            const Parent *parent, const private_api::Property<float> *viewport_width,
+            const private_api::Property<float> *viewport_height,
+            [[maybe_unused]] const private_api::Property<float> *viewport_x,
+            [[maybe_unused]] const private_api::Property<float> *listview_width,
+            float listview_height) const
+    {
+        ensure_updated(parent);
+
+        float w = compute_layout_listview_horizontal(viewport_height, listview_height);
+        viewport_width->set(w);
+    }
+
     uintptr_t visit(TraversalOrder order, private_api::ItemVisitorRefMut visitor) const
     {
         for (std::size_t i = 0; i < inner->data.size(); ++i) {
@@ -952,6 +977,19 @@ public:
         return offset;
     }
 
+    float compute_layout_listview_horizontal(const private_api::Property<float> *viewport_height,
+                                             float listview_height) const
+    {
+        float offset = 0;
+        viewport_height->set(listview_height);
+        if (!inner)
+            return offset;
+        for (auto &x : inner->data) {
+            (*x.ptr)->listview_layout_horizontal(&offset, viewport_height);
+        }
+        return offset;
+    }
+
     void model_set_row_data(int row, const ModelData &data) const
     {
         if (model.is_dirty()) {
@@ -982,6 +1020,22 @@ cbindgen_private::Flickable::~Flickable()
 {
     slint_flickable_data_free(&data);
 }
+cbindgen_private::Text::Text()
+{
+    slint_text_data_init(&data);
+}
+cbindgen_private::Text::~Text()
+{
+    slint_text_data_free(&data);
+}
+cbindgen_private::TextInput::TextInput()
+{
+    slint_textinput_data_init(&data);
+}
+cbindgen_private::TextInput::~TextInput()
+{
+    slint_textinput_data_free(&data);
+}
 
 cbindgen_private::NativeStyleMetrics::NativeStyleMetrics(void *)
 {
@@ -246,8 +246,8 @@ struct MyStruct {
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
 pub use i_slint_core::model::{
-    FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
-    StandardListViewItem, VecModel,
+    FilterModel, LazyMapModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc,
+    ModelTracker, SortModel, StandardListViewItem, VecModel,
 };
 pub use i_slint_core::sharedvector::SharedVector;
 pub use i_slint_core::string::SharedString;
@@ -273,7 +273,8 @@ pub mod re_exports {
     };
     pub use i_slint_core::graphics::*;
     pub use i_slint_core::input::{
-        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers, MouseEvent,
+        FocusEvent, InputEventResult, InputMethodEvent, KeyEvent, KeyEventResult, KeyEventType,
+        KeyboardModifiers, MouseEvent, StandardShortcut, TouchPhase,
     };
     pub use i_slint_core::item_tree::{
         visit_item_tree, ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, ItemWeak,
@@ -447,6 +448,13 @@ pub fn run_event_loop() {
         b.run_event_loop(i_slint_core::platform::EventLoopQuitBehavior::QuitOnLastWindowClosed)
     })
 }
+
+/// Returns the screens connected to the system, with their position, size and scale factor. The list
+/// is empty if the backend doesn't know about the screens.
+pub fn screens() -> alloc::vec::Vec<platform::ScreenInfo> {
+    i_slint_backend_selector::with_platform_abstraction(|b| b.screens())
+}
+
 /// This module contains functions useful for unit tests
 #[cfg(feature = "std")]
 pub mod testing {
@@ -484,10 +492,12 @@ pub fn set_current_keyboard_modifiers<
             + crate::re_exports::WindowHandleAccess,
         Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
     >(
-        _component: &Component,
+        component: &Component,
         modifiers: crate::re_exports::KeyboardModifiers,
     ) {
-        KEYBOARD_MODIFIERS.with(|x| x.set(modifiers))
+        KEYBOARD_MODIFIERS.with(|x| x.set(modifiers));
+        // So that the mouse events sent afterwards are interpreted with the modifiers
+        component.clone_strong().into().window_handle().set_keyboard_modifiers(modifiers);
     }
 
     /// Simulate entering a sequence of ascii characters key by key.
@@ -544,6 +554,25 @@ pub mod platform {
     pub use i_slint_core::platform::*;
 }
 
+/// Module to find the bindings that are slow to evaluate, for performance debugging.
+///
+/// Call [`binding_timing::set_slow_binding_handler()`] with a threshold to get called with the
+/// bindings that take longer than that to evaluate.
+#[cfg(feature = "binding-timing")]
+pub mod binding_timing {
+    pub use i_slint_core::properties::binding_timing::*;
+}
+
+/// Module to find the properties that cause the layout to be invalidated too often, for
+/// performance debugging.
+///
+/// Call [`layout_invalidation::start_recording()`], interact with the application, and then
+/// [`layout_invalidation::report()`] lists the properties that caused the most layout passes.
+#[cfg(feature = "layout-invalidation")]
+pub mod layout_invalidation {
+    pub use i_slint_core::properties::layout_invalidation::*;
+}
+
 /// Helper type that helps checking that the generated code is generated for the right version
 #[doc(hidden)]
 #[allow(non_camel_case_types)]
@@ -0,0 +1,31 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+#![cfg(feature = "renderer-software")]
+
+use ::slint::slint;
+
+#[test]
+fn take_snapshot_of_headless_window() {
+    slint::platform::headless::init().unwrap();
+
+    slint!(Snapshot := Window {
+        background: #0000ff;
+        Rectangle {
+            x: 1phx;
+            y: 0phx;
+            width: 1phx;
+            height: 2phx;
+            background: #ff0000;
+        }
+    });
+
+    let instance = Snapshot::new();
+    instance.window().set_size(slint::euclid::size2(2, 2));
+
+    let snapshot = instance.window().take_snapshot().unwrap();
+    assert_eq!((snapshot.width(), snapshot.height()), (2, 2));
+    let blue = slint::Rgba8Pixel { r: 0, g: 0, b: 255, a: 255 };
+    let red = slint::Rgba8Pixel { r: 255, g: 0, b: 0, a: 255 };
+    assert_eq!(snapshot.as_slice(), &[blue, red, blue, red]);
+}
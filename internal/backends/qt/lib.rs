@@ -9,6 +9,8 @@
 
 extern crate alloc;
 
+#[cfg(not(no_qt))]
+use i_slint_core::platform::ColorScheme;
 use std::rc::Rc;
 
 #[cfg(not(no_qt))]
@@ -220,30 +222,126 @@ struct EventHolder {
     }
 
     #[cfg(not(no_qt))]
-    fn set_clipboard_text(&self, _text: &str) {
+    fn set_clipboard_text(&self, text: &str) {
+        set_qt_clipboard_text(text, QT_CLIPBOARD_MODE_CLIPBOARD)
+    }
+
+    #[cfg(not(no_qt))]
+    fn set_primary_selection_text(&self, text: &str) {
+        set_qt_clipboard_text(text, QT_CLIPBOARD_MODE_SELECTION)
+    }
+
+    #[cfg(not(no_qt))]
+    fn double_click_interval(&self) -> core::time::Duration {
         use cpp::cpp;
-        let text: qttypes::QString = _text.into();
-        cpp! {unsafe [text as "QString"] {
+        let interval = cpp! {unsafe [] -> i32 as "int" {
             ensure_initialized();
-            QGuiApplication::clipboard()->setText(text);
-        } }
+            return QApplication::doubleClickInterval();
+        } };
+        core::time::Duration::from_millis(interval.max(0) as u64)
+    }
+
+    #[cfg(not(no_qt))]
+    fn color_scheme(&self) -> ColorScheme {
+        use cpp::cpp;
+        let window_background = cpp! {unsafe [] -> u32 as "QRgb" {
+            ensure_initialized();
+            return qApp->palette().color(QPalette::Window).rgba();
+        } };
+        // Same as for the dark-style of the native style
+        qt_widgets::color_scheme_for_window_background(
+            i_slint_core::graphics::Color::from_argb_encoded(window_background),
+        )
     }
 
     #[cfg(not(no_qt))]
     fn clipboard_text(&self) -> Option<String> {
+        qt_clipboard_text(QT_CLIPBOARD_MODE_CLIPBOARD)
+    }
+
+    #[cfg(not(no_qt))]
+    fn primary_selection_text(&self) -> Option<String> {
+        qt_clipboard_text(QT_CLIPBOARD_MODE_SELECTION)
+    }
+
+    #[cfg(not(no_qt))]
+    fn set_clipboard_image(&self, image: &i_slint_core::graphics::Image) {
         use cpp::cpp;
-        let has_text = cpp! {unsafe [] -> bool as "bool" {
+        let pixmap = match qt_window::image_to_pixmap(image.into(), None) {
+            Some(pixmap) => pixmap,
+            None => return,
+        };
+        cpp! {unsafe [pixmap as "QPixmap"] {
             ensure_initialized();
-            return QGuiApplication::clipboard()->mimeData()->hasText();
+            QGuiApplication::clipboard()->setPixmap(pixmap);
+        } }
+    }
+
+    #[cfg(not(no_qt))]
+    fn clipboard_image(&self) -> Option<i_slint_core::graphics::Image> {
+        use cpp::cpp;
+        use i_slint_core::graphics::{Image, Rgba8Pixel, SharedPixelBuffer};
+        let image = cpp! {unsafe [] -> qttypes::QImage as "QImage" {
+            ensure_initialized();
+            return QGuiApplication::clipboard()->image().convertToFormat(QImage::Format_RGBA8888);
         } };
-        if has_text {
-            return Some(
-                cpp! { unsafe [] -> qttypes::QString as "QString" {
-                    return QGuiApplication::clipboard()->text();
-                }}
-                .into(),
-            );
+        let size = cpp! {unsafe [image as "QImage"] -> qttypes::QSize as "QSize" {
+            return image.size();
+        } };
+        let (width, height) = (size.width, size.height);
+        if width == 0 || height == 0 {
+            return None;
         }
-        None
+        let mut buffer = SharedPixelBuffer::<Rgba8Pixel>::new(width, height);
+        let buffer_ptr = buffer.make_mut_bytes().as_mut_ptr();
+        cpp! {unsafe [image as "QImage", buffer_ptr as "uchar*", width as "uint32_t", height as "uint32_t"] {
+            for (uint32_t y = 0; y < height; ++y) {
+                memcpy(buffer_ptr + y * width * 4, image.constScanLine(y), width * 4);
+            }
+        } };
+        Some(Image::from_rgba8(buffer))
+    }
+}
+
+/// The values of `QClipboard::Mode`
+#[cfg(not(no_qt))]
+const QT_CLIPBOARD_MODE_CLIPBOARD: i32 = 0;
+#[cfg(not(no_qt))]
+const QT_CLIPBOARD_MODE_SELECTION: i32 = 1;
+
+/// Sets the text of the clipboard of the given `QClipboard::Mode`
+#[cfg(not(no_qt))]
+fn set_qt_clipboard_text(text: &str, mode: i32) {
+    use cpp::cpp;
+    let text: qttypes::QString = text.into();
+    cpp! {unsafe [text as "QString", mode as "int"] {
+        ensure_initialized();
+        QClipboard *clipboard = QGuiApplication::clipboard();
+        if (mode == QClipboard::Selection && !clipboard->supportsSelection())
+            return;
+        clipboard->setText(text, QClipboard::Mode(mode));
+    } }
+}
+
+/// Returns the text of the clipboard of the given `QClipboard::Mode`, if any
+#[cfg(not(no_qt))]
+fn qt_clipboard_text(mode: i32) -> Option<String> {
+    use cpp::cpp;
+    let has_text = cpp! {unsafe [mode as "int"] -> bool as "bool" {
+        ensure_initialized();
+        QClipboard *clipboard = QGuiApplication::clipboard();
+        if (mode == QClipboard::Selection && !clipboard->supportsSelection())
+            return false;
+        const QMimeData *mime_data = clipboard->mimeData(QClipboard::Mode(mode));
+        return mime_data && mime_data->hasText();
+    } };
+    if has_text {
+        return Some(
+            cpp! { unsafe [mode as "int"] -> qttypes::QString as "QString" {
+                return QGuiApplication::clipboard()->text(QClipboard::Mode(mode));
+            }}
+            .into(),
+        );
     }
+    None
 }
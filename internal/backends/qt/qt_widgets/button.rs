@@ -253,14 +253,16 @@ fn input_event(
         Self::FIELD_OFFSETS.pressed.apply_pin(self).set(match event {
             MouseEvent::Pressed { .. } => true,
             MouseEvent::Exit | MouseEvent::Released { .. } => false,
-            MouseEvent::Moved { .. } => {
+            MouseEvent::Moved { .. } | MouseEvent::LongPress { .. } => {
                 return if self.pressed() {
                     InputEventResult::GrabMouse
                 } else {
                     InputEventResult::EventIgnored
                 }
             }
-            MouseEvent::Wheel { .. } => return InputEventResult::EventIgnored,
+            MouseEvent::Wheel { .. } | MouseEvent::Enter { .. } | MouseEvent::Pinch { .. } => {
+                return InputEventResult::EventIgnored
+            }
         });
         if let MouseEvent::Released { position, .. } = event {
             if euclid::rect(0., 0., self.width(), self.height()).contains(position) {
@@ -74,14 +74,16 @@ fn input_event(
         Self::FIELD_OFFSETS.pressed.apply_pin(self).set(match event {
             MouseEvent::Pressed { .. } => true,
             MouseEvent::Exit | MouseEvent::Released { .. } => false,
-            MouseEvent::Moved { .. } => {
+            MouseEvent::Moved { .. } | MouseEvent::LongPress { .. } => {
                 return if self.pressed() {
                     InputEventResult::GrabMouse
                 } else {
                     InputEventResult::EventIgnored
                 }
             }
-            MouseEvent::Wheel { .. } => return InputEventResult::EventIgnored,
+            MouseEvent::Wheel { .. } | MouseEvent::Enter { .. } | MouseEvent::Pinch { .. } => {
+                return InputEventResult::EventIgnored
+            }
         });
         if matches!(event, MouseEvent::Released { .. }) {
             Self::FIELD_OFFSETS.is_open.apply_pin(self).set(true);
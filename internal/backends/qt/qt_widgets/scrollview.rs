@@ -175,6 +175,16 @@ fn input_event(
                     data.pressed = 0;
                     InputEventResult::EventIgnored
                 }
+                MouseEvent::Enter { .. } | MouseEvent::Pinch { .. } => {
+                    InputEventResult::EventIgnored
+                }
+                MouseEvent::LongPress { .. } => {
+                    if data.pressed != 0 {
+                        InputEventResult::GrabMouse
+                    } else {
+                        InputEventResult::EventIgnored
+                    }
+                }
                 MouseEvent::Released { .. } => {
                     data.pressed = 0;
                     let new_val = cpp!(unsafe [active_controls as "int", value as "int", max as "int", page_size as "int"] -> i32 as "int" {
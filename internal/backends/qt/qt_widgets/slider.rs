@@ -187,6 +187,14 @@ fn input_event(
                 debug_assert_ne!(button, PointerEventButton::Left);
                 InputEventResult::EventIgnored
             }
+            MouseEvent::Enter { .. } | MouseEvent::Pinch { .. } => InputEventResult::EventIgnored,
+            MouseEvent::LongPress { .. } => {
+                if data.pressed != 0 {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
         };
         data.active_controls = new_control;
 
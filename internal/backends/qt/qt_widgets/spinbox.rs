@@ -178,7 +178,10 @@ fn input_event(
                     }
                     true
                 }
-                MouseEvent::Moved { .. } => false,
+                MouseEvent::Moved { .. }
+                | MouseEvent::Enter { .. }
+                | MouseEvent::LongPress { .. }
+                | MouseEvent::Pinch { .. } => false,
                 MouseEvent::Wheel { .. } => false, // TODO
             };
         data.active_controls = new_control;
@@ -3,7 +3,7 @@
 
 // cSpell: ignore deinit
 
-use i_slint_core::items::LayoutAlignment;
+use i_slint_core::items::{ColorScheme, LayoutAlignment};
 
 use super::*;
 
@@ -44,6 +44,7 @@ pub struct NativeStyleMetrics {
     pub placeholder_color_disabled: Property<Color>,
 
     pub dark_style: Property<bool>,
+    pub color_scheme: Property<ColorScheme>,
 
     // Tab Bar metrics:
     pub tab_bar_alignment: Property<LayoutAlignment>,
@@ -72,6 +73,7 @@ pub fn new() -> Pin<Rc<Self>> {
             placeholder_color: Default::default(),
             placeholder_color_disabled: Default::default(),
             dark_style: Default::default(),
+            color_scheme: Default::default(),
             tab_bar_alignment: Default::default(),
             style_change_listener: core::cell::Cell::new(core::ptr::null()),
         })
@@ -142,13 +144,9 @@ fn init_impl(self: Pin<&Self>) {
         });
         self.placeholder_color_disabled.set(Color::from_argb_encoded(placeholder_color_disabled));
 
-        self.dark_style.set(
-            (window_background.red() as u32
-                + window_background.green() as u32
-                + window_background.blue() as u32)
-                / 3
-                < 128,
-        );
+        let color_scheme = color_scheme_for_window_background(window_background);
+        self.dark_style.set(color_scheme == ColorScheme::Dark);
+        self.color_scheme.set(color_scheme);
 
         let tab_bar_alignment = cpp!(unsafe[] -> u32 as "uint32_t" {
             switch (qApp->style()->styleHint(QStyle::SH_TabBar_Alignment)) {
@@ -186,3 +184,17 @@ pub fn native_style_metrics_deinit(self_: Pin<&mut NativeStyleMetrics>) {
     cpp!(unsafe [scl as "StyleChangeListener*"] { delete scl; });
     self_.style_change_listener.set(core::ptr::null());
 }
+
+/// Returns the color scheme of a style whose windows have the `window_background` color: it is dark
+/// when the average of the color components is dark.
+pub fn color_scheme_for_window_background(window_background: Color) -> ColorScheme {
+    let average = (window_background.red() as u32
+        + window_background.green() as u32
+        + window_background.blue() as u32)
+        / 3;
+    if average < 128 {
+        ColorScheme::Dark
+    } else {
+        ColorScheme::Light
+    }
+}
@@ -13,18 +13,18 @@
 use i_slint_core::graphics::{
     Brush, Color, FontRequest, Image, IntSize, Point, Rect, SharedImageBuffer, Size,
 };
-use i_slint_core::input::{KeyEvent, KeyEventType, MouseEvent};
+use i_slint_core::input::{InputMethodEvent, KeyEvent, KeyEventType, MouseEvent};
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::{
-    self, FillRule, ImageRendering, InputType, ItemRc, ItemRef, Layer, MouseCursor, Opacity,
-    PointerEventButton, RenderingResult, TextOverflow, TextWrap,
+    self, CaretStyle, FillRule, ImageRendering, InputType, ItemRc, ItemRef, Layer, MouseCursor,
+    Opacity, PointerEventButton, RenderingResult, TextOverflow, TextWrap,
 };
 use i_slint_core::layout::Orientation;
 use i_slint_core::window::{PlatformWindow, WindowHandleAccess};
 use i_slint_core::{ImageInner, PathData, Property, SharedString};
 use items::{ImageFit, TextHorizontalAlignment, TextVerticalAlignment};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::pin::Pin;
 use std::ptr::NonNull;
 use std::rc::{Rc, Weak};
@@ -107,9 +107,11 @@ struct SlintWidget : QWidget {
             isMouseButtonDown = true;
             QPoint pos = event->pos();
             int button = event->button();
-            rust!(Slint_mousePressEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", button: u32 as "int" ] {
+            uint modifiers = event->modifiers();
+            rust!(Slint_mousePressEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", button: u32 as "int", modifiers: u32 as "uint" ] {
                 let position = Point::new(pos.x as _, pos.y as _);
                 let button = from_qt_button(button);
+                rust_window.window.window_handle().set_keyboard_modifiers(from_qt_modifiers(modifiers));
                 rust_window.mouse_event(MouseEvent::Pressed{ position, button })
             });
         }
@@ -157,10 +159,13 @@ struct SlintWidget : QWidget {
             if (delta.isNull()) {
                 delta = event->angleDelta();
             }
-            rust!(Slint_mouseWheelEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPointF as "QPointF", delta: qttypes::QPoint as "QPoint"] {
+            uint modifiers = event->modifiers();
+            rust!(Slint_mouseWheelEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPointF as "QPointF", delta: qttypes::QPoint as "QPoint", modifiers: u32 as "uint"] {
                 let position = Point::new(pos.x as _, pos.y as _);
                 let delta = Point::new(delta.x as _, delta.y as _);
-                rust_window.mouse_event(MouseEvent::Wheel{position, delta})
+                let modifiers = from_qt_modifiers(modifiers);
+                rust_window.window.window_handle().set_keyboard_modifiers(modifiers);
+                rust_window.mouse_event(MouseEvent::Wheel{position, delta, modifiers})
             });
         }
         void leaveEvent(QEvent *) override {
@@ -186,6 +191,35 @@ struct SlintWidget : QWidget {
             });
         }
 
+        void inputMethodEvent(QInputMethodEvent *event) override {
+            QString commit_string = event->commitString();
+            QString preedit_string = event->preeditString();
+            // The position of the cursor in the pre-edit string, or -1 if it is hidden
+            int cursor = -1;
+            for (const auto &attribute : event->attributes()) {
+                if (attribute.type == QInputMethodEvent::Cursor && attribute.length > 0)
+                    cursor = attribute.start;
+            }
+            rust!(Slint_inputMethodEvent [rust_window: &QtWindow as "void*", commit_string: qttypes::QString as "QString", preedit_string: qttypes::QString as "QString", cursor: i32 as "int"] {
+                rust_window.input_method_event(commit_string.clone(), preedit_string.clone(), cursor);
+            });
+        }
+
+        QVariant inputMethodQuery(Qt::InputMethodQuery query) const override {
+            if (query == Qt::ImCursorRectangle) {
+                return rust!(Slint_inputMethodCursorRectangle [rust_window: &QtWindow as "void*"] -> qttypes::QRectF as "QRectF" {
+                    let rect = rust_window.ime_cursor_area.get();
+                    qttypes::QRectF {
+                        x: rect.origin.x as _,
+                        y: rect.origin.y as _,
+                        width: rect.width() as _,
+                        height: rect.height() as _,
+                    }
+                });
+            }
+            return QWidget::inputMethodQuery(query);
+        }
+
         void customEvent(QEvent *event) override {
             if (event->type() == QEvent::User) {
                 rust!(Slint_updateWindowProps [rust_window: &QtWindow as "void*"] {
@@ -236,13 +270,31 @@ struct SlintWidget : QWidget {
         }
     };
 
+    // Elides `line` like QFontMetrics::elidedText with Qt::ElideRight, but with `ellipsis` instead of "…".
+    // With `force`, the ellipsis is added even if the line fits in the width.
+    static QString elide_line(const QFontMetrics &fm, const QString &line, int width, const QString &ellipsis, bool force = false) {
+        if (!force && fm.horizontalAdvance(line) <= width)
+            return line;
+        const QChar qt_ellipsis(0x2026);
+        QString elided = fm.elidedText(line + qt_ellipsis, Qt::ElideRight,
+                width - fm.horizontalAdvance(ellipsis) + fm.horizontalAdvance(qt_ellipsis));
+        if (elided.endsWith(qt_ellipsis)) {
+            elided.chop(1);
+            elided += ellipsis;
+        }
+        return elided;
+    }
+
     // Helper function used for the TextInput layouting
     //
     // if line_for_y_pos > 0, then the function will return the line at this y position
     static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, int line_for_y_pos = -1) {
         QTextOption options;
-        options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
+        options.setWrapMode((flags & Qt::TextWrapAnywhere) ? QTextOption::WrapAnywhere
+                : (flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
         options.setFlags(QTextOption::IncludeTrailingSpaces);
+        // Same as the tab stops used when measuring the text, in QFont::text_size
+        options.setTabStopDistance(4 * QFontMetricsF(layout.font()).horizontalAdvance(QLatin1Char(' ')));
         layout.setTextOption(options);
         layout.setCacheEnabled(true);
         QFontMetrics fm(layout.font());
@@ -421,6 +473,15 @@ fn from_qt_button(qt_button: u32) -> PointerEventButton {
     }
 }
 
+fn from_qt_modifiers(qt_modifiers: u32) -> i_slint_core::input::KeyboardModifiers {
+    i_slint_core::input::KeyboardModifiers {
+        control: (qt_modifiers & key_generated::Qt_KeyboardModifier_ControlModifier) != 0,
+        alt: (qt_modifiers & key_generated::Qt_KeyboardModifier_AltModifier) != 0,
+        shift: (qt_modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier) != 0,
+        meta: (qt_modifiers & key_generated::Qt_KeyboardModifier_MetaModifier) != 0,
+    }
+}
+
 /// Given a position offset and an object of a given type that has x,y,width,height properties,
 /// create a QRectF that fits it.
 macro_rules! get_geometry {
@@ -435,6 +496,17 @@ macro_rules! get_geometry {
     }};
 }
 
+/// The area in which the text of a TextInput is laid out, inside its border
+fn text_input_text_rect(text_input: Pin<&items::TextInput>) -> qttypes::QRectF {
+    let rect = text_input.text_rect();
+    qttypes::QRectF {
+        x: rect.origin.x as _,
+        y: rect.origin.y as _,
+        width: rect.width() as _,
+        height: rect.height() as _,
+    }
+}
+
 fn adjust_rect_and_border_for_inner_drawing(rect: &mut qttypes::QRectF, border_width: &mut f32) {
     // If the border width exceeds the width, just fill the rectangle.
     *border_width = border_width.min((rect.width as f32) / 2.);
@@ -525,30 +597,55 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
         } | match text.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+            TextWrap::CharWrap => key_generated::Qt_TextFlag_TextWrapAnywhere,
         };
         let elide = text.overflow() == TextOverflow::Elide;
+        let ellipsis: qttypes::QString = text.ellipsis().as_str().into();
+        let stroke_brush: qttypes::QBrush =
+            into_qbrush(text.text_stroke_color(), rect.width, rect.height);
+        let stroke_width: f32 = text.text_stroke_width();
+        // drawText clips the text to this rectangle
+        let layout_rect = text.layout_rect(self.window.window_handle());
+        let rect = qttypes::QRectF {
+            x: layout_rect.origin.x as _,
+            y: layout_rect.origin.y as _,
+            width: layout_rect.width() as _,
+            height: layout_rect.height() as _,
+        };
         let painter: &mut QPainterPtr = &mut self.painter;
-        cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool"] {
+        cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool", ellipsis as "QString", stroke_brush as "QBrush", stroke_width as "float"] {
             (*painter)->setFont(font);
-            (*painter)->setPen(QPen(fill_brush, 0));
             (*painter)->setBrush(Qt::NoBrush);
+            auto draw_text = [&](const QString &to_draw) {
+                if (stroke_width > 0 && stroke_brush.style() != Qt::NoBrush) {
+                    // Approximate the outline with copies of the text shifted around the glyphs
+                    const qreal d = stroke_width * 0.7071; // sqrt(1/2)
+                    const QPointF offsets[] = { {stroke_width, 0}, {-stroke_width, 0}, {0, stroke_width}, {0, -stroke_width},
+                                                {d, d}, {d, -d}, {-d, d}, {-d, -d} };
+                    (*painter)->setPen(QPen(stroke_brush, 0));
+                    for (const auto &offset : offsets)
+                        (*painter)->drawText(rect.translated(offset), flags, to_draw);
+                }
+                (*painter)->setPen(QPen(fill_brush, 0));
+                (*painter)->drawText(rect, flags, to_draw);
+            };
             if (!elide) {
-                (*painter)->drawText(rect, flags, string);
-            } else if (!(flags & Qt::TextWordWrap)) {
+                draw_text(string);
+            } else if (!(flags & (Qt::TextWordWrap | Qt::TextWrapAnywhere))) {
                 QString elided;
                 QFontMetrics fm(font);
                 while (!string.isEmpty()) {
                     int pos = string.indexOf('\n');
                     if (pos < 0) {
-                        elided += fm.elidedText(string, Qt::ElideRight, rect.width());
+                        elided += elide_line(fm, string, rect.width(), ellipsis);
                         break;
                     }
                     QString line = string.left(pos);
-                    elided += fm.elidedText(line, Qt::ElideRight, rect.width());
+                    elided += elide_line(fm, line, rect.width(), ellipsis);
                     elided += '\n';
                     string = string.mid(pos + 1);
                 }
-                (*painter)->drawText(rect, flags, elided);
+                draw_text(elided);
             } else {
                 // elide and word wrap: we need to add the ellipsis manually on the last line
                 string.replace(QChar('\n'), QChar::LineSeparator);
@@ -556,7 +653,7 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
                 QFontMetrics fm(font);
                 QTextLayout layout(string, font);
                 QTextOption options;
-                options.setWrapMode(QTextOption::WordWrap);
+                options.setWrapMode((flags & Qt::TextWrapAnywhere) ? QTextOption::WrapAnywhere : QTextOption::WordWrap);
                 layout.setTextOption(options);
                 layout.setCacheEnabled(true);
                 layout.beginLayout();
@@ -579,23 +676,25 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
                 }
                 if (last_line_begin < string.size()) {
                     elided = string.left(last_line_begin);
-                    QString to_elide = QStringView(string).mid(last_line_begin, last_line_size).trimmed() % QStringView(QT_UNICODE_LITERAL("…"));
-                    elided += fm.elidedText(to_elide, Qt::ElideRight, rect.width());
+                    QString to_elide = QStringView(string).mid(last_line_begin, last_line_size).trimmed().toString();
+                    elided += elide_line(fm, to_elide, rect.width(), ellipsis, true);
                 }
-                (*painter)->drawText(rect, flags, elided);
+                draw_text(elided);
             }
         }}
     }
 
     fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &ItemRc) {
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
+        self.draw_text_input_frame(text_input, rect);
+        let rect = text_input_text_rect(text_input);
         let fill_brush: qttypes::QBrush = into_qbrush(text_input.color(), rect.width, rect.height);
         let selection_foreground_color: u32 =
             text_input.selection_foreground_color().as_argb_encoded();
         let selection_background_color: u32 =
             text_input.selection_background_color().as_argb_encoded();
 
-        let text = text_input.text();
+        let (text, preedit_range) = text_input.text_with_preedit();
         let mut string: qttypes::QString = text.as_str().into();
 
         if let InputType::Password = text_input.input_type() {
@@ -617,12 +716,27 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
         } | match text_input.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+            TextWrap::CharWrap => key_generated::Qt_TextFlag_TextWrapAnywhere,
         };
 
         // convert byte offsets to offsets in Qt UTF-16 encoded string, as that's
         // what QTextLayout expects.
-        let cursor_position_as_offset: i32 = text_input.cursor_position();
-        let anchor_position_as_offset: i32 = text_input.anchor_position();
+        let (cursor_position_as_offset, anchor_position_as_offset) = match &preedit_range {
+            // The selection is hidden while composing
+            Some(preedit_range) => {
+                let cursor = (preedit_range.start + text_input.preedit_cursor_position()) as i32;
+                (cursor, cursor)
+            }
+            None => (text_input.cursor_position(), text_input.anchor_position()),
+        };
+        let (preedit_start, preedit_length): (i32, i32) = match &preedit_range {
+            Some(preedit_range) => {
+                let start = utf8_byte_offset_to_utf16_units(text.as_str(), preedit_range.start);
+                let end = utf8_byte_offset_to_utf16_units(text.as_str(), preedit_range.end);
+                (start as i32, (end - start) as i32)
+            }
+            None => (0, 0),
+        };
         let cursor_position: i32 = if cursor_position_as_offset > 0 {
             utf8_byte_offset_to_utf16_units(text.as_str(), cursor_position_as_offset as usize)
                 as i32
@@ -643,7 +757,38 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                 0.
             };
 
+        // The suggestion is drawn after the end of the text, and doesn't affect the layout
+        let suggestion: qttypes::QString = if text_input.suggestion_visible() {
+            text_input.suggestion_text().as_str().into()
+        } else {
+            Default::default()
+        };
+        let suggestion_brush: qttypes::QBrush =
+            into_qbrush(text_input.suggestion_color(), rect.width, rect.height);
+
         let single_line: bool = text_input.single_line();
+        let caret_style: i32 = match text_input.caret_style() {
+            CaretStyle::Bar => 0,
+            CaretStyle::Block => 1,
+            CaretStyle::Underline => 2,
+        };
+
+        if let Some(line_rect) =
+            text_input.current_line_rect(&self.window.window_handle().platform_window())
+        {
+            let line_rect = qttypes::QRectF {
+                x: line_rect.origin.x as _,
+                y: line_rect.origin.y as _,
+                width: line_rect.width() as _,
+                height: line_rect.height() as _,
+            };
+            let line_brush: qttypes::QBrush =
+                into_qbrush(text_input.current_line_color(), line_rect.width, line_rect.height);
+            let painter: &mut QPainterPtr = &mut self.painter;
+            cpp! { unsafe [painter as "QPainterPtr*", line_rect as "QRectF", line_brush as "QBrush"] {
+                (*painter)->fillRect(line_rect, line_brush);
+            }}
+        }
 
         let painter: &mut QPainterPtr = &mut self.painter;
         cpp! { unsafe [
@@ -658,7 +803,12 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                 font as "QFont",
                 cursor_position as "int",
                 anchor_position as "int",
-                text_cursor_width as "float"] {
+                text_cursor_width as "float",
+                caret_style as "int",
+                preedit_start as "int",
+                preedit_length as "int",
+                suggestion as "QString",
+                suggestion_brush as "QBrush"] {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
@@ -676,11 +826,125 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                     fmt
                 };
             }
+            if (preedit_length > 0) {
+                QTextCharFormat fmt;
+                fmt.setFontUnderline(true);
+                selections << QTextLayout::FormatRange{ preedit_start, preedit_length, fmt };
+            }
+            // Block and underline cursors cover the grapheme after the cursor, or the width of
+            // a space at the end of a line.
+            QRectF grapheme_rect;
+            if (text_cursor_width > 0 && caret_style != 0) {
+                QTextLine line = layout.lineForTextPosition(cursor_position);
+                if (!line.isValid() && layout.lineCount() > 0)
+                    line = layout.lineAt(layout.lineCount() - 1);
+                if (line.isValid()) {
+                    qreal x = line.cursorToX(cursor_position);
+                    int next = layout.nextCursorPosition(cursor_position);
+                    qreal width = next > cursor_position && next <= line.textStart() + line.textLength()
+                        ? line.cursorToX(next) - x
+                        : QFontMetricsF(font).horizontalAdvance(QLatin1Char(' '));
+                    grapheme_rect = QRectF(rect.x() + x, rect.y() + line.y(), width, line.height());
+                    if (caret_style == 1 && next > cursor_position) {
+                        QTextCharFormat fmt;
+                        fmt.setBackground(fill_brush);
+                        fmt.setForeground(QColor::fromRgba(selection_foreground_color));
+                        selections << QTextLayout::FormatRange{ cursor_position, next - cursor_position, fmt };
+                    }
+                }
+            }
             layout.draw(painter->get(), rect.topLeft(), selections);
+            if (!suggestion.isEmpty() && layout.lineCount() > 0) {
+                QTextLine last_line = layout.lineAt(layout.lineCount() - 1);
+                (*painter)->setFont(font);
+                (*painter)->setPen(QPen(suggestion_brush, 0));
+                (*painter)->drawText(QPointF(rect.x() + last_line.x() + last_line.naturalTextWidth(),
+                        rect.y() + last_line.y() + last_line.ascent()), suggestion);
+                (*painter)->setPen(QPen(fill_brush, 0));
+            }
             if (text_cursor_width > 0) {
-                layout.drawCursor(painter->get(), rect.topLeft(), cursor_position, text_cursor_width);
+                if (grapheme_rect.isNull()) {
+                    layout.drawCursor(painter->get(), rect.topLeft(), cursor_position, text_cursor_width);
+                } else if (caret_style == 1) {
+                    // The grapheme itself was filled through the selections
+                    if (layout.nextCursorPosition(cursor_position) == cursor_position)
+                        (*painter)->fillRect(grapheme_rect, fill_brush);
+                } else {
+                    (*painter)->fillRect(QRectF(grapheme_rect.x(),
+                            grapheme_rect.bottom() - text_cursor_width,
+                            grapheme_rect.width(), text_cursor_width), fill_brush);
+                }
             }
         }}
+
+        for cursor_rect in
+            text_input.secondary_cursor_rects(&self.window.window_handle().platform_window())
+        {
+            let cursor_rect = qttypes::QRectF {
+                x: cursor_rect.origin.x as _,
+                y: cursor_rect.origin.y as _,
+                width: cursor_rect.width() as _,
+                height: cursor_rect.height() as _,
+            };
+            let cursor_brush: qttypes::QBrush =
+                into_qbrush(text_input.color(), cursor_rect.width, cursor_rect.height);
+            let painter: &mut QPainterPtr = &mut self.painter;
+            cpp! { unsafe [painter as "QPainterPtr*", cursor_rect as "QRectF", cursor_brush as "QBrush"] {
+                (*painter)->fillRect(cursor_rect, cursor_brush);
+            }}
+        }
+
+        for handle_rect in
+            text_input.selection_handle_rects(&self.window.window_handle().platform_window())
+        {
+            let handle_rect = qttypes::QRectF {
+                x: handle_rect.origin.x as _,
+                y: handle_rect.origin.y as _,
+                width: handle_rect.width() as _,
+                height: handle_rect.height() as _,
+            };
+            let painter: &mut QPainterPtr = &mut self.painter;
+            cpp! { unsafe [painter as "QPainterPtr*", handle_rect as "QRectF", selection_background_color as "QRgb"] {
+                (*painter)->setPen(Qt::NoPen);
+                (*painter)->setBrush(QColor::fromRgba(selection_background_color));
+                (*painter)->drawEllipse(handle_rect);
+            }}
+        }
+    }
+
+    fn draw_text_input_placeholder(
+        &mut self,
+        text_input: std::pin::Pin<&items::TextInput>,
+        _: &ItemRc,
+    ) {
+        let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
+        self.draw_text_input_frame(text_input, rect);
+        let rect = text_input_text_rect(text_input);
+        let fill_brush: qttypes::QBrush =
+            into_qbrush(text_input.placeholder_color(), rect.width, rect.height);
+        let string: qttypes::QString = text_input.placeholder_text().as_str().into();
+        let font: QFont =
+            get_font(text_input.font_request(&self.window.window_handle().platform_window()));
+        let flags = match text_input.horizontal_alignment() {
+            TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
+            TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
+            TextHorizontalAlignment::Right => key_generated::Qt_AlignmentFlag_AlignRight,
+        } | match text_input.vertical_alignment() {
+            TextVerticalAlignment::Top => key_generated::Qt_AlignmentFlag_AlignTop,
+            TextVerticalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignVCenter,
+            TextVerticalAlignment::Bottom => key_generated::Qt_AlignmentFlag_AlignBottom,
+        } | match text_input.wrap() {
+            TextWrap::NoWrap => 0,
+            TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+            TextWrap::CharWrap => key_generated::Qt_TextFlag_TextWrapAnywhere,
+        };
+        let painter: &mut QPainterPtr = &mut self.painter;
+        cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", string as "QString", flags as "int", font as "QFont"] {
+            (*painter)->setFont(font);
+            (*painter)->setPen(QPen(fill_brush, 0));
+            (*painter)->setBrush(Qt::NoBrush);
+            (*painter)->drawText(rect, flags, string);
+        }}
     }
 
     fn draw_path(&mut self, path: Pin<&items::Path>, _: &ItemRc) {
@@ -1104,6 +1368,18 @@ fn draw_image_impl(
         }};
     }
 
+    /// Draws the `background` and the border of the text input, in `rect`, behind the text.
+    fn draw_text_input_frame(&mut self, text_input: Pin<&items::TextInput>, rect: qttypes::QRectF) {
+        Self::draw_rectangle_impl(
+            &mut self.painter,
+            rect,
+            text_input.background(),
+            text_input.border_color(),
+            text_input.border_width(),
+            text_input.border_radius(),
+        );
+    }
+
     fn draw_rectangle_impl(
         painter: &mut QPainterPtr,
         mut rect: qttypes::QRectF,
@@ -1208,6 +1484,9 @@ pub struct QtWindow {
     cache: ItemCache<qttypes::QPixmap>,
 
     tree_structure_changed: RefCell<bool>,
+
+    /// The rectangle of the text cursor of the focused item, reported to the input method
+    ime_cursor_area: Cell<Rect>,
 }
 
 impl QtWindow {
@@ -1231,6 +1510,7 @@ pub fn new() -> Rc<Self> {
                 rendering_metrics_collector: Default::default(),
                 cache: Default::default(),
                 tree_structure_changed: RefCell::new(false),
+                ime_cursor_area: Default::default(),
             }
         });
         let widget_ptr = rc.widget_ptr();
@@ -1308,12 +1588,7 @@ fn mouse_event(&self, event: MouseEvent) {
     fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, released: bool) {
         i_slint_core::animations::update_animations();
         let text: String = text.into();
-        let modifiers = i_slint_core::input::KeyboardModifiers {
-            control: (qt_modifiers & key_generated::Qt_KeyboardModifier_ControlModifier) != 0,
-            alt: (qt_modifiers & key_generated::Qt_KeyboardModifier_AltModifier) != 0,
-            shift: (qt_modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier) != 0,
-            meta: (qt_modifiers & key_generated::Qt_KeyboardModifier_MetaModifier) != 0,
-        };
+        let modifiers = from_qt_modifiers(qt_modifiers);
 
         let text = qt_key_to_string(key as key_generated::Qt_Key, text);
 
@@ -1327,6 +1602,30 @@ fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, release
         timer_event();
     }
 
+    fn input_method_event(
+        &self,
+        commit_string: qttypes::QString,
+        preedit_string: qttypes::QString,
+        cursor: i32,
+    ) {
+        i_slint_core::animations::update_animations();
+        let runtime_window = self.window.window_handle();
+        let commit_string: String = commit_string.into();
+        if !commit_string.is_empty() {
+            runtime_window
+                .process_input_method_event(&InputMethodEvent::Commit(commit_string.into()));
+        }
+        let preedit_string: String = preedit_string.into();
+        let cursor = (cursor >= 0)
+            .then(|| utf16_units_to_utf8_byte_offset(&preedit_string, cursor as usize));
+        runtime_window.process_input_method_event(&InputMethodEvent::Preedit {
+            text: preedit_string.into(),
+            cursor,
+        });
+
+        timer_event();
+    }
+
     fn close_popup(&self) {
         self.window.window_handle().close_popup();
     }
@@ -1534,6 +1833,45 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         }};
     }
 
+    fn set_custom_cursor(
+        &self,
+        image: &i_slint_core::graphics::Image,
+        hotspot: i_slint_core::lengths::PhysicalPoint,
+    ) {
+        let pixmap = match image_to_pixmap(image.into(), None) {
+            Some(pixmap) => pixmap,
+            None => return self.set_mouse_cursor(MouseCursor::Default),
+        };
+        let widget_ptr = self.widget_ptr();
+        let (hot_x, hot_y) = (hotspot.x as i32, hotspot.y as i32);
+        cpp! {unsafe [widget_ptr as "QWidget*", pixmap as "QPixmap", hot_x as "int", hot_y as "int"] {
+            widget_ptr->setCursor(QCursor{pixmap, hot_x, hot_y});
+        }};
+    }
+
+    fn show_virtual_keyboard(&self, _: InputType) {
+        let widget_ptr = self.widget_ptr();
+        cpp! {unsafe [widget_ptr as "QWidget*"] {
+            widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, true);
+            QGuiApplication::inputMethod()->update(Qt::ImEnabled);
+        }};
+    }
+
+    fn hide_virtual_keyboard(&self) {
+        let widget_ptr = self.widget_ptr();
+        cpp! {unsafe [widget_ptr as "QWidget*"] {
+            widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, false);
+            QGuiApplication::inputMethod()->update(Qt::ImEnabled);
+        }};
+    }
+
+    fn set_ime_cursor_area(&self, rect: Rect) {
+        self.ime_cursor_area.set(rect);
+        cpp! {unsafe [] {
+            QGuiApplication::inputMethod()->update(Qt::ImCursorRectangle);
+        }};
+    }
+
     fn renderer(&self) -> &dyn Renderer {
         self
     }
@@ -1592,8 +1930,9 @@ fn text_size(
         text: &str,
         max_width: Option<f32>,
         _scale_factor: f32,
+        rules: i_slint_core::graphics::TextMeasureRules,
     ) -> Size {
-        get_font(font_request).text_size(text, max_width)
+        get_font(font_request).text_size(text, max_width, rules)
     }
 
     fn text_input_byte_offset_for_position(
@@ -1601,14 +1940,14 @@ fn text_input_byte_offset_for_position(
         text_input: Pin<&i_slint_core::items::TextInput>,
         pos: Point,
     ) -> usize {
+        let rect: qttypes::QRectF = text_input_text_rect(text_input);
+        let pos = qttypes::QPointF { x: pos.x as f64 - rect.x, y: pos.y as f64 - rect.y };
         if pos.y < 0. {
             return 0;
         }
-        let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
-        let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
         let font: QFont =
             get_font(text_input.font_request(&self.window.window_handle().platform_window()));
-        let string = qttypes::QString::from(text_input.text().as_str());
+        let string = qttypes::QString::from(text_input.text_with_preedit().0.as_str());
         let flags = match text_input.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
@@ -1620,6 +1959,7 @@ fn text_input_byte_offset_for_position(
         } | match text_input.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+            TextWrap::CharWrap => key_generated::Qt_TextFlag_TextWrapAnywhere,
         };
         let single_line: bool = text_input.single_line();
         let is_password: bool = matches!(text_input.input_type(), InputType::Password);
@@ -1661,10 +2001,10 @@ fn text_input_cursor_rect_for_byte_offset(
         text_input: Pin<&i_slint_core::items::TextInput>,
         byte_offset: usize,
     ) -> Rect {
-        let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
+        let rect: qttypes::QRectF = text_input_text_rect(text_input);
         let font: QFont =
             get_font(text_input.font_request(&self.window.window_handle().platform_window()));
-        let text = text_input.text();
+        let (text, _) = text_input.text_with_preedit();
         let mut string = qttypes::QString::from(text.as_str());
         let offset: u32 = utf8_byte_offset_to_utf16_units(text.as_str(), byte_offset) as _;
         let flags = match text_input.horizontal_alignment() {
@@ -1678,6 +2018,7 @@ fn text_input_cursor_rect_for_byte_offset(
         } | match text_input.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+            TextWrap::CharWrap => key_generated::Qt_TextFlag_TextWrapAnywhere,
         };
         let single_line: bool = text_input.single_line();
         let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool"]
@@ -1699,7 +2040,10 @@ fn text_input_cursor_rect_for_byte_offset(
             return QFontInfo(font).pixelSize();
         }};
 
-        Rect::new(Point::new(r.x as _, r.y as _), Size::new(1.0, font_size as f32))
+        Rect::new(
+            Point::new((rect.x + r.x) as _, (rect.y + r.y) as _),
+            Size::new(1.0, font_size as f32),
+        )
     }
 
     fn register_font_from_memory(
@@ -1782,16 +2126,32 @@ fn get_font(request: FontRequest) -> QFont {
 cpp_class! {pub unsafe struct QFont as "QFont"}
 
 impl QFont {
-    fn text_size(&self, text: &str, max_width: Option<f32>) -> i_slint_core::graphics::Size {
-        let string = qttypes::QString::from(text);
+    fn text_size(
+        &self,
+        text: &str,
+        max_width: Option<f32>,
+        rules: i_slint_core::graphics::TextMeasureRules,
+    ) -> i_slint_core::graphics::Size {
+        // Measure without the trailing newline, and add the empty line it starts only if the
+        // rules say so.
+        let adds_trailing_line = rules.adds_trailing_line(text);
+        let string = qttypes::QString::from(text.strip_suffix('\n').unwrap_or(text));
         let mut r = qttypes::QRectF::default();
         if let Some(max) = max_width {
             r.height = f32::MAX as _;
             r.width = max as _;
         }
-        let size = cpp! { unsafe [self as "const QFont*", string as "QString", r as "QRectF"]
+        let break_anywhere = rules.break_anywhere;
+        let size = cpp! { unsafe [self as "const QFont*", string as "QString", r as "QRectF", adds_trailing_line as "bool", break_anywhere as "bool"]
                 -> qttypes::QSizeF as "QSizeF"{
-            return QFontMetricsF(*self).boundingRect(r, r.isEmpty() ? 0 : Qt::TextWordWrap , string).size();
+            QFontMetricsF fm(*self);
+            int flags = Qt::TextExpandTabs | (r.isEmpty() ? 0 : break_anywhere ? Qt::TextWrapAnywhere : Qt::TextWordWrap);
+            // Same tab stops as when drawing the text, in do_text_layout
+            int tab_stops = qRound(4 * fm.horizontalAdvance(QLatin1Char(' ')));
+            QSizeF size = fm.boundingRect(r, flags, string, tab_stops).size();
+            if (adds_trailing_line)
+                size.rheight() += fm.height();
+            return size;
         }};
         i_slint_core::graphics::Size::new(size.width as _, size.height as _)
     }
@@ -1950,6 +2310,17 @@ pub extern "C" fn slint_qt_get_widget(
     }
 }
 
+fn utf16_units_to_utf8_byte_offset(str: &str, utf16_units: usize) -> usize {
+    let mut current_units = 0;
+    for (offset, ch) in str.char_indices() {
+        if current_units >= utf16_units {
+            return offset;
+        }
+        current_units += ch.len_utf16();
+    }
+    str.len()
+}
+
 fn utf8_byte_offset_to_utf16_units(str: &str, byte_offset: usize) -> usize {
     let mut current_offset = 0;
     let mut utf16_units = 0;
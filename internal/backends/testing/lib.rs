@@ -16,18 +16,23 @@
 #[derive(Default)]
 pub struct TestingBackend {
     clipboard: Mutex<Option<String>>,
+    primary_selection: Mutex<Option<String>>,
+    clipboard_image: Mutex<Option<i_slint_core::graphics::Image>>,
 }
 
 impl i_slint_core::platform::PlatformAbstraction for TestingBackend {
     fn create_window(&self) -> Rc<dyn PlatformWindow> {
         Rc::new_cyclic(|self_weak| TestingWindow {
             window: i_slint_core::api::Window::new(self_weak.clone() as _),
+            ime_cursor_area: Default::default(),
+            redraw_requested: Default::default(),
         })
     }
 
     fn duration_since_start(&self) -> core::time::Duration {
-        // The slint::testing::mock_elapsed_time updates the animation tick directly
-        core::time::Duration::from_millis(i_slint_core::animations::current_tick().0)
+        // The time is mocked: slint::testing::mock_elapsed_time and advance_time() update the
+        // animation tick directly
+        mock_time()
     }
 
     fn set_clipboard_text(&self, text: &str) {
@@ -37,10 +42,28 @@ fn set_clipboard_text(&self, text: &str) {
     fn clipboard_text(&self) -> Option<String> {
         self.clipboard.lock().unwrap().clone()
     }
+
+    fn set_primary_selection_text(&self, text: &str) {
+        *self.primary_selection.lock().unwrap() = Some(text.into());
+    }
+
+    fn primary_selection_text(&self) -> Option<String> {
+        self.primary_selection.lock().unwrap().clone()
+    }
+
+    fn set_clipboard_image(&self, image: &i_slint_core::graphics::Image) {
+        *self.clipboard_image.lock().unwrap() = Some(image.clone());
+    }
+
+    fn clipboard_image(&self) -> Option<i_slint_core::graphics::Image> {
+        self.clipboard_image.lock().unwrap().clone()
+    }
 }
 
 pub struct TestingWindow {
     window: i_slint_core::api::Window,
+    ime_cursor_area: std::cell::Cell<Option<Rect>>,
+    redraw_requested: std::cell::Cell<bool>,
 }
 
 impl PlatformWindow for TestingWindow {
@@ -50,7 +73,9 @@ fn show(&self) {
 
     fn hide(&self) {}
 
-    fn request_redraw(&self) {}
+    fn request_redraw(&self) {
+        self.redraw_requested.set(true);
+    }
 
     fn register_component(&self) {}
 
@@ -76,6 +101,10 @@ fn apply_geometry_constraint(
 
     fn set_mouse_cursor(&self, _cursor: i_slint_core::items::MouseCursor) {}
 
+    fn set_ime_cursor_area(&self, rect: Rect) {
+        self.ime_cursor_area.set(Some(rect));
+    }
+
     fn renderer(&self) -> &dyn Renderer {
         self
     }
@@ -102,10 +131,24 @@ fn text_size(
         &self,
         _font_request: i_slint_core::graphics::FontRequest,
         text: &str,
-        _max_width: Option<f32>,
+        max_width: Option<f32>,
         _scale_factor: f32,
+        rules: i_slint_core::graphics::TextMeasureRules,
     ) -> Size {
-        Size::new(text.len() as f32 * 10., 10.)
+        // Every byte is 10 pixels wide, except tabs which are as wide as four bytes, like in the
+        // software renderer
+        let tabs = text.matches('\t').count();
+        let width = (text.len() + tabs * 3) as f32 * 10.;
+        let lines = if rules.adds_trailing_line(text) { 2. } else { 1. };
+        match max_width {
+            // Only breaking anywhere wraps: the bytes are split in lines that fit in the width
+            Some(max_width) if rules.break_anywhere && width > max_width.max(0.) => {
+                let bytes_per_line = ((max_width / 10.) as usize).max(1);
+                let wrapped_lines = ((width / 10.) as usize - 1) / bytes_per_line + 1;
+                Size::new(bytes_per_line as f32 * 10., (lines + wrapped_lines as f32 - 1.) * 10.)
+            }
+            _ => Size::new(width, lines * 10.),
+        }
     }
 
     fn text_input_byte_offset_for_position(
@@ -118,10 +161,19 @@ fn text_input_byte_offset_for_position(
 
     fn text_input_cursor_rect_for_byte_offset(
         &self,
-        _text_input: Pin<&i_slint_core::items::TextInput>,
-        _byte_offset: usize,
+        text_input: Pin<&i_slint_core::items::TextInput>,
+        byte_offset: usize,
     ) -> Rect {
-        Default::default()
+        // Consistent with text_size: every byte is 10 pixels wide, and every line 10 pixels high
+        let (text, _) = text_input.text_with_preedit();
+        let before = &text[..byte_offset.min(text.len())];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let line = before.matches('\n').count();
+        let origin = text_input.text_rect().origin;
+        Rect::new(
+            origin + euclid::vec2((before.len() - line_start) as f32 * 10., line as f32 * 10.),
+            Size::new(1., 10.),
+        )
     }
 
     fn register_font_from_memory(
@@ -146,3 +198,88 @@ pub fn init() {
     i_slint_core::platform::set_platform_abstraction(Box::new(TestingBackend::default()))
         .expect("platform already initialized");
 }
+
+/// Returns the mocked time of the testing backend, which starts at zero and only changes when the test
+/// advances it.
+pub fn mock_time() -> core::time::Duration {
+    core::time::Duration::from_millis(i_slint_core::animations::current_tick().0)
+}
+
+/// Sets the mocked time to `time`, then fires the timers that expired meanwhile and updates the
+/// animations. A repeated timer fires once for every interval that elapsed. The time can't go
+/// backwards: setting a time earlier than [`mock_time()`] does nothing.
+pub fn set_mock_time(time: core::time::Duration) {
+    // Step from one timer to the next, so that the timers fire in order and the repeated ones
+    // as often as they would have with the real time
+    while let Some(until_next_timer) = i_slint_core::platform::duration_until_next_timer_update() {
+        let next_timer = mock_time() + until_next_timer.max(core::time::Duration::from_millis(1));
+        if next_timer > time {
+            break;
+        }
+        i_slint_core::platform::update_timers_and_animations_with_time(next_timer);
+    }
+    i_slint_core::platform::update_timers_and_animations_with_time(time);
+}
+
+/// Advances the mocked time by `duration`, see [`set_mock_time()`]. This makes timer driven
+/// behavior, such as the blinking of the text cursor, testable without sleeping.
+pub fn advance_time(duration: core::time::Duration) {
+    set_mock_time(mock_time() + duration)
+}
+
+/// Returns the last text cursor rectangle that was reported to the input method of the window,
+/// in logical window coordinates.
+pub fn ime_cursor_area(window: &i_slint_core::api::Window) -> Option<Rect> {
+    use i_slint_core::window::WindowHandleAccess;
+    window
+        .window_handle()
+        .platform_window()
+        .as_any()
+        .downcast_ref::<TestingWindow>()
+        .and_then(|window| window.ime_cursor_area.get())
+}
+
+/// Returns true if a redraw of the window was requested since the last call of this function.
+pub fn take_redraw_request(window: &i_slint_core::api::Window) -> bool {
+    use i_slint_core::window::WindowHandleAccess;
+    window
+        .window_handle()
+        .platform_window()
+        .as_any()
+        .downcast_ref::<TestingWindow>()
+        .map_or(false, |window| window.redraw_requested.take())
+}
+
+/// Sets the secondary cursors of the `TextInput` that has the keyboard focus in the window,
+/// at the byte offsets `positions`. Returns false if the focus item is not a `TextInput`.
+/// See [`TextInput::set_secondary_cursors()`](i_slint_core::items::TextInput::set_secondary_cursors)
+/// for the renderers that draw them.
+pub fn set_secondary_cursors_of_focus_item(
+    window: &i_slint_core::api::Window,
+    positions: &[usize],
+) -> bool {
+    use i_slint_core::window::WindowHandleAccess;
+    let focus_item = match window.window_handle().focus_item() {
+        Some(focus_item) => focus_item,
+        None => return false,
+    };
+    let item = focus_item.borrow();
+    match i_slint_core::items::ItemRef::downcast_pin::<i_slint_core::items::TextInput>(item) {
+        Some(text_input) => {
+            text_input.set_secondary_cursors(positions);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns the item that has the keyboard focus in the window, if it is a `TextInput`.
+pub fn focused_text_input(
+    window: &i_slint_core::api::Window,
+) -> Option<i_slint_core::items::ItemRc> {
+    use i_slint_core::window::WindowHandleAccess;
+    window
+        .window_handle()
+        .focus_item()
+        .filter(|item| item.downcast::<i_slint_core::items::TextInput>().is_some())
+}
@@ -14,7 +14,9 @@
 
 use corelib::api::euclid;
 use corelib::graphics::Point;
-use corelib::input::{KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
+use corelib::input::{
+    InputMethodEvent, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent, TouchPhase,
+};
 use corelib::window::*;
 use corelib::{Coord, SharedString};
 use std::cell::{Cell, RefCell, RefMut};
@@ -164,7 +166,7 @@ fn input_method_focused(&self) -> bool {
 }
 
 struct NotRunningEventLoop {
-    clipboard: RefCell<Box<dyn copypasta::ClipboardProvider>>,
+    clipboard: RefCell<Clipboards>,
     instance: winit::event_loop::EventLoop<CustomEvent>,
     event_loop_proxy: winit::event_loop::EventLoopProxy<CustomEvent>,
 }
@@ -181,13 +183,13 @@ fn new() -> Self {
 struct RunningEventLoop<'a> {
     event_loop_target: &'a winit::event_loop::EventLoopWindowTarget<CustomEvent>,
     event_loop_proxy: &'a winit::event_loop::EventLoopProxy<CustomEvent>,
-    clipboard: &'a RefCell<Box<dyn copypasta::ClipboardProvider>>,
+    clipboard: &'a RefCell<Clipboards>,
 }
 
 pub(crate) trait EventLoopInterface {
     fn event_loop_target(&self) -> &winit::event_loop::EventLoopWindowTarget<CustomEvent>;
     fn event_loop_proxy(&self) -> &winit::event_loop::EventLoopProxy<CustomEvent>;
-    fn clipboard(&self) -> RefMut<'_, dyn ClipboardProvider>;
+    fn clipboard(&self) -> RefMut<'_, Clipboards>;
 }
 
 impl EventLoopInterface for NotRunningEventLoop {
@@ -199,8 +201,8 @@ fn event_loop_proxy(&self) -> &winit::event_loop::EventLoopProxy<CustomEvent> {
         &self.event_loop_proxy
     }
 
-    fn clipboard(&self) -> RefMut<'_, dyn ClipboardProvider> {
-        RefMut::map(self.clipboard.borrow_mut(), |clipboard_box| clipboard_box.as_mut())
+    fn clipboard(&self) -> RefMut<'_, Clipboards> {
+        self.clipboard.borrow_mut()
     }
 }
 
@@ -213,8 +215,8 @@ fn event_loop_proxy(&self) -> &winit::event_loop::EventLoopProxy<CustomEvent> {
         self.event_loop_proxy
     }
 
-    fn clipboard(&self) -> RefMut<'_, dyn ClipboardProvider> {
-        RefMut::map(self.clipboard.borrow_mut(), |clipboard_box| clipboard_box.as_mut())
+    fn clipboard(&self) -> RefMut<'_, Clipboards> {
+        self.clipboard.borrow_mut()
     }
 }
 
@@ -420,6 +422,21 @@ fn key_event(
             event.event_type = KeyEventType::KeyReleased;
             runtime_window.process_key_input(&event);
         }
+        WindowEvent::Ime(winit::event::Ime::Preedit(text, cursor)) => {
+            runtime_window.process_input_method_event(&InputMethodEvent::Preedit {
+                text: text.into(),
+                cursor: cursor.map(|(start, _)| start),
+            });
+        }
+        WindowEvent::Ime(winit::event::Ime::Commit(text)) => {
+            runtime_window.process_input_method_event(&InputMethodEvent::Commit(text.into()));
+        }
+        WindowEvent::Ime(winit::event::Ime::Disabled) => {
+            runtime_window.process_input_method_event(&InputMethodEvent::Preedit {
+                text: Default::default(),
+                cursor: None,
+            });
+        }
         WindowEvent::Focused(have_focus) => {
             let have_focus = have_focus || window.input_method_focused();
             // We don't render popups as separate windows yet, so treat
@@ -468,6 +485,7 @@ fn key_event(
             let modifiers =
                 KeyboardModifiers { shift: state.shift(), alt: state.alt(), control, meta };
             window.current_keyboard_modifiers().set(modifiers);
+            runtime_window.set_keyboard_modifiers(modifiers);
         }
         WindowEvent::CursorMoved { position, .. } => {
             let position = position.to_logical(runtime_window.scale_factor() as f64);
@@ -492,7 +510,12 @@ fn key_event(
                 }
             }
             .cast::<Coord>();
-            runtime_window.process_mouse_input(MouseEvent::Wheel { position: *cursor_pos, delta });
+            let modifiers = window.current_keyboard_modifiers().get();
+            runtime_window.process_mouse_input(MouseEvent::Wheel {
+                position: *cursor_pos,
+                delta,
+                modifiers,
+            });
         }
         WindowEvent::MouseInput { state, button, .. } => {
             let button = match button {
@@ -516,18 +539,22 @@ fn key_event(
         WindowEvent::Touch(touch) => {
             let location = touch.location.to_logical(runtime_window.scale_factor() as f64);
             let position = euclid::point2(location.x, location.y);
-            let ev = match touch.phase {
+            let phase = match touch.phase {
                 winit::event::TouchPhase::Started => {
                     *pressed = true;
-                    MouseEvent::Pressed { position, button: PointerEventButton::Left }
+                    TouchPhase::Started
+                }
+                winit::event::TouchPhase::Ended => {
+                    *pressed = false;
+                    TouchPhase::Ended
                 }
-                winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
+                winit::event::TouchPhase::Cancelled => {
                     *pressed = false;
-                    MouseEvent::Released { position, button: PointerEventButton::Left }
+                    TouchPhase::Cancelled
                 }
-                winit::event::TouchPhase::Moved => MouseEvent::Moved { position },
+                winit::event::TouchPhase::Moved => TouchPhase::Moved,
             };
-            runtime_window.process_mouse_input(ev);
+            runtime_window.process_touch_point(touch.id, position, phase);
         }
         WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size: size } => {
             if std::env::var("SLINT_SCALE_FACTOR").is_err() {
@@ -536,6 +563,12 @@ fn key_event(
                 runtime_window.set_scale_factor(scale_factor as f32);
             }
         }
+        WindowEvent::ThemeChanged(theme) => {
+            crate::stylemetrics::set_color_scheme(match theme {
+                winit::window::Theme::Dark => corelib::platform::ColorScheme::Dark,
+                winit::window::Theme::Light => corelib::platform::ColorScheme::Light,
+            });
+        }
         _ => {}
     }
 }
@@ -763,11 +796,25 @@ fn winit_key_code_to_string(virtual_keycode: winit::event::VirtualKeyCode) -> Op
     )
 }
 
-fn create_clipboard<T>(
-    _event_loop: &winit::event_loop::EventLoopWindowTarget<T>,
-) -> Box<dyn copypasta::ClipboardProvider> {
+/// The standard clipboard, and the primary selection on the platforms that have one
+pub(crate) struct Clipboards {
+    standard: Box<dyn ClipboardProvider>,
+    primary: Option<Box<dyn ClipboardProvider>>,
+}
+
+impl Clipboards {
+    pub(crate) fn standard(&mut self) -> &mut dyn ClipboardProvider {
+        self.standard.as_mut()
+    }
+
+    pub(crate) fn primary(&mut self) -> Option<&mut dyn ClipboardProvider> {
+        self.primary.as_deref_mut()
+    }
+}
+
+fn create_clipboard<T>(_event_loop: &winit::event_loop::EventLoopWindowTarget<T>) -> Clipboards {
     #[allow(unused_mut)]
-    let mut clipboard: Option<Box<dyn copypasta::ClipboardProvider>> = None;
+    let mut clipboards: Option<Clipboards> = None;
 
     cfg_if::cfg_if! {
         if #[cfg(all(any(
@@ -794,14 +841,39 @@ fn create_clipboard<T>(
         use winit::platform::unix::EventLoopWindowTargetExtUnix;
         #[cfg(feature = "wayland")]
         if let Some(wayland_display) = _event_loop.wayland_display() {
-            clipboard = unsafe {
-                Some(Box::new(
-                    copypasta::wayland_clipboard::create_clipboards_from_external(wayland_display)
-                        .1,
-                ))
+            let (primary, standard) = unsafe {
+                copypasta::wayland_clipboard::create_clipboards_from_external(wayland_display)
             };
+            clipboards = Some(Clipboards {
+                standard: Box::new(standard),
+                primary: Some(Box::new(primary)),
+            });
         }
     }
 
-    clipboard.unwrap_or_else(|| Box::new(DefaultClipboard::new().unwrap()))
+    clipboards.unwrap_or_else(|| Clipboards {
+        standard: Box::new(DefaultClipboard::new().unwrap()),
+        primary: create_x11_primary_selection(),
+    })
+}
+
+/// Returns the primary selection of X11, which the other platforms don't have
+fn create_x11_primary_selection() -> Option<Box<dyn ClipboardProvider>> {
+    #[cfg(all(
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ),
+        feature = "x11"
+    ))]
+    {
+        use copypasta::x11_clipboard::{Primary, X11ClipboardContext};
+        if let Ok(primary) = X11ClipboardContext::<Primary>::new() {
+            return Some(Box::new(primary));
+        }
+    }
+    None
 }
@@ -1,38 +1,120 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use std::cell::RefCell;
-#[cfg(target_arch = "wasm32")]
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
-// glutin::WindowedContext tries to enforce being current or not. Since we need the WindowedContext's window() function
-// in the GL renderer regardless whether we're current or not, we wrap the two states back into one type.
+// glutin::RawContext tries to enforce being current or not. Since we need to access the context in the GL renderer
+// regardless whether we're current or not, we wrap the two states back into one type. The window is kept separately,
+// so that a lost context can be replaced without re-creating the window.
 enum OpenGLContextState {
     #[cfg(not(target_arch = "wasm32"))]
-    NotCurrent(glutin::WindowedContext<glutin::NotCurrent>),
+    NotCurrent(glutin::RawContext<glutin::NotCurrent>),
     #[cfg(not(target_arch = "wasm32"))]
-    Current(glutin::WindowedContext<glutin::PossiblyCurrent>),
+    Current(glutin::RawContext<glutin::PossiblyCurrent>),
     #[cfg(target_arch = "wasm32")]
-    Current { window: Rc<winit::window::Window>, canvas: web_sys::HtmlCanvasElement },
+    Current { canvas: web_sys::HtmlCanvasElement },
 }
 
-pub struct OpenGLContext(RefCell<Option<OpenGLContextState>>);
+pub struct OpenGLContext {
+    // The context must be destroyed before the window it renders into, so this field comes first.
+    state: RefCell<Option<OpenGLContextState>>,
+    window: Rc<winit::window::Window>,
+    /// Set when the driver or the browser reported that the context was lost, for example after a GPU reset or
+    /// when the browser reclaimed the resources of a background tab. A lost context can't be used anymore and must
+    /// be replaced with a new one, along with all the GPU resources created with it.
+    context_lost: Rc<Cell<bool>>,
+    /// The vsync setting of the context, for re-creating it the same way.
+    #[cfg(not(target_arch = "wasm32"))]
+    vsync: Cell<bool>,
+    /// The context replaced by [`Self::recreate()`], kept until [`Self::release_lost_context()`].
+    #[cfg(not(target_arch = "wasm32"))]
+    lost_state: RefCell<Option<OpenGLContextState>>,
+}
 
 impl OpenGLContext {
-    pub fn window(&self) -> std::cell::Ref<winit::window::Window> {
-        std::cell::Ref::map(self.0.borrow(), |state| match state.as_ref().unwrap() {
-            #[cfg(not(target_arch = "wasm32"))]
-            OpenGLContextState::NotCurrent(context) => context.window(),
-            #[cfg(not(target_arch = "wasm32"))]
-            OpenGLContextState::Current(context) => context.window(),
-            #[cfg(target_arch = "wasm32")]
-            OpenGLContextState::Current { window, .. } => window.as_ref(),
-        })
+    /// Returns false if the context was lost. Nothing must be rendered until [`Self::recreate()`]
+    /// replaced it, and the renderer must then re-create its GPU resources. On the web, this is
+    /// reported once the browser restored the context, as a new one can't be created before.
+    pub fn is_valid(&self) -> bool {
+        !self.context_lost.get()
+    }
+
+    /// Replaces a lost context with a new one for the same window, and makes it current. Returns
+    /// false if that failed, for example while the GPU is still resetting; rendering must then be
+    /// skipped and the re-creation tried again later.
+    ///
+    /// The new context doesn't share its objects with the other contexts, as these are usually
+    /// lost as well. The lost context is kept until [`Self::release_lost_context()`], so that the
+    /// renderer can delete the objects it created with it.
+    ///
+    /// Re-creating a context isn't supported on macOS, see `build_raw_context()`.
+    pub fn recreate(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let builder = glutin::ContextBuilder::new().with_vsync(self.vsync.get());
+            #[cfg(target_os = "windows")]
+            let builder = builder.with_srgb(false);
+            let context = match build_raw_context(builder, &self.window) {
+                Ok(context) => context,
+                Err(err) => {
+                    i_slint_core::debug_log!(
+                        "Failed to re-create the lost OpenGL context: {}",
+                        err
+                    );
+                    return false;
+                }
+            };
+            let context = match unsafe { context.make_current() } {
+                Ok(context) => context,
+                Err((_, err)) => {
+                    i_slint_core::debug_log!(
+                        "Failed to re-create the lost OpenGL context: {}",
+                        err
+                    );
+                    return false;
+                }
+            };
+            context.resize(self.window.inner_size());
+            *self.lost_state.borrow_mut() =
+                self.state.borrow_mut().replace(OpenGLContextState::Current(context));
+        }
+        // On the web, the browser restored the context of the canvas already.
+        self.context_lost.set(false);
+        true
+    }
+
+    /// Calls `release` to delete the objects of the context that [`Self::recreate()`] replaced, and then
+    /// destroys that context. The lost context is made current while `release` runs, or no context at all
+    /// if the driver refuses, so that the deletions can't hit the objects of the new context that have the
+    /// same names. On the web, deleting the objects of a lost context does nothing.
+    pub fn release_lost_context(&self, release: impl FnOnce()) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let lost_state = match self.lost_state.borrow_mut().take() {
+                Some(lost_state) => lost_state,
+                None => return release(),
+            };
+            self.make_not_current();
+            let lost_context = match lost_state {
+                OpenGLContextState::NotCurrent(context) => unsafe { context.make_current() }.ok(),
+                OpenGLContextState::Current(context) => unsafe { context.make_current() }.ok(),
+            };
+            release();
+            drop(lost_context);
+            self.make_current();
+        }
+        #[cfg(target_arch = "wasm32")]
+        release();
+    }
+
+    pub fn window(&self) -> &winit::window::Window {
+        &self.window
     }
 
     #[cfg(target_arch = "wasm32")]
     pub fn html_canvas_element(&self) -> std::cell::Ref<web_sys::HtmlCanvasElement> {
-        std::cell::Ref::map(self.0.borrow(), |state| match state.as_ref().unwrap() {
+        std::cell::Ref::map(self.state.borrow(), |state| match state.as_ref().unwrap() {
             OpenGLContextState::Current { canvas, .. } => canvas,
         })
     }
@@ -41,10 +123,8 @@ pub fn html_canvas_element(&self) -> std::cell::Ref<web_sys::HtmlCanvasElement>
         feature = "renderer-skia",
         not(any(target_os = "macos", target_family = "windows", target_arch = "wasm32"))
     ))]
-    pub fn glutin_context(
-        &self,
-    ) -> std::cell::Ref<glutin::WindowedContext<glutin::PossiblyCurrent>> {
-        std::cell::Ref::map(self.0.borrow(), |state| match state.as_ref().unwrap() {
+    pub fn glutin_context(&self) -> std::cell::Ref<glutin::RawContext<glutin::PossiblyCurrent>> {
+        std::cell::Ref::map(self.state.borrow(), |state| match state.as_ref().unwrap() {
             OpenGLContextState::Current(gl_context) => gl_context,
             OpenGLContextState::NotCurrent(..) => {
                 panic!("internal error: glutin_context() called without current context")
@@ -53,12 +133,18 @@ pub fn glutin_context(
     }
 
     pub fn make_current(&self) {
-        let mut ctx = self.0.borrow_mut();
+        let mut ctx = self.state.borrow_mut();
         *ctx = Some(match ctx.take().unwrap() {
             #[cfg(not(target_arch = "wasm32"))]
             OpenGLContextState::NotCurrent(not_current_ctx) => {
-                let current_ctx = unsafe { not_current_ctx.make_current().unwrap() };
-                OpenGLContextState::Current(current_ctx)
+                match unsafe { not_current_ctx.make_current() } {
+                    Ok(current_ctx) => OpenGLContextState::Current(current_ctx),
+                    Err((not_current_ctx, glutin::ContextError::ContextLost)) => {
+                        self.context_lost.set(true);
+                        OpenGLContextState::NotCurrent(not_current_ctx)
+                    }
+                    Err((_, err)) => panic!("Failed to make OpenGL context current: {}", err),
+                }
             }
             state @ OpenGLContextState::Current { .. } => state,
         });
@@ -67,7 +153,7 @@ pub fn make_current(&self) {
     pub fn make_not_current(&self) {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let mut ctx = self.0.borrow_mut();
+            let mut ctx = self.state.borrow_mut();
             *ctx = Some(match ctx.take().unwrap() {
                 state @ OpenGLContextState::NotCurrent(_) => state,
                 OpenGLContextState::Current(current_ctx_rc) => {
@@ -80,7 +166,7 @@ pub fn make_not_current(&self) {
     }
 
     pub fn with_current_context<T>(&self, cb: impl FnOnce(&Self) -> T) -> T {
-        if matches!(self.0.borrow().as_ref().unwrap(), OpenGLContextState::Current { .. }) {
+        if matches!(self.state.borrow().as_ref().unwrap(), OpenGLContextState::Current { .. }) {
             cb(self)
         } else {
             self.make_current();
@@ -92,74 +178,144 @@ pub fn with_current_context<T>(&self, cb: impl FnOnce(&Self) -> T) -> T {
 
     pub fn swap_buffers(&self) {
         #[cfg(not(target_arch = "wasm32"))]
-        match &self.0.borrow().as_ref().unwrap() {
+        match &self.state.borrow().as_ref().unwrap() {
             OpenGLContextState::NotCurrent(_) => {}
-            OpenGLContextState::Current(current_ctx) => {
-                current_ctx.swap_buffers().unwrap();
-            }
+            OpenGLContextState::Current(current_ctx) => match current_ctx.swap_buffers() {
+                Ok(()) => {}
+                Err(glutin::ContextError::ContextLost) => self.context_lost.set(true),
+                Err(err) => panic!("Failed to swap OpenGL buffers: {}", err),
+            },
         }
     }
 
     pub fn ensure_resized(&self) {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let mut ctx = self.0.borrow_mut();
+            // A lost context is resized when it's re-created
+            if !self.is_valid() {
+                return;
+            }
+            let mut ctx = self.state.borrow_mut();
             *ctx = Some(match ctx.take().unwrap() {
                 #[cfg(not(target_arch = "wasm32"))]
                 OpenGLContextState::NotCurrent(not_current_ctx) => {
-                    let current_ctx = unsafe { not_current_ctx.make_current().unwrap() };
-                    current_ctx.resize(current_ctx.window().inner_size());
-                    OpenGLContextState::NotCurrent(unsafe {
-                        current_ctx.make_not_current().unwrap()
-                    })
+                    match unsafe { not_current_ctx.make_current() } {
+                        Ok(current_ctx) => {
+                            current_ctx.resize(self.window.inner_size());
+                            OpenGLContextState::NotCurrent(unsafe {
+                                current_ctx.make_not_current().unwrap()
+                            })
+                        }
+                        Err((not_current_ctx, glutin::ContextError::ContextLost)) => {
+                            self.context_lost.set(true);
+                            OpenGLContextState::NotCurrent(not_current_ctx)
+                        }
+                        Err((_, err)) => panic!("Failed to make OpenGL context current: {}", err),
+                    }
                 }
                 OpenGLContextState::Current(current) => {
-                    current.resize(current.window().inner_size());
+                    current.resize(self.window.inner_size());
                     OpenGLContextState::Current(current)
                 }
             });
         }
     }
 
+    /// Changes the swap interval of the context: when `enabled` is true, buffer swaps wait for the
+    /// vertical blank. This is a request only; some drivers ignore it or force their own setting.
+    /// On the web this is a no-op, as the browser always synchronizes with the display.
+    pub fn set_vsync(&self, enabled: bool) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.vsync.set(enabled);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.with_current_context(|context| match context.state.borrow().as_ref().unwrap() {
+            OpenGLContextState::Current(current_context) => {
+                set_swap_interval(current_context, enabled as std::os::raw::c_int)
+            }
+            // The context was lost, it's re-created with the new setting
+            OpenGLContextState::NotCurrent(_) => {}
+        });
+        #[cfg(target_arch = "wasm32")]
+        let _ = enabled;
+    }
+
+    /// Creates a new window with an OpenGL context. If `shared_context` is set, the new context shares its
+    /// textures, buffers and other objects with that context (and all the contexts it already shares with), so
+    /// that multiple windows can use the same GPU resources. When that's not possible, for example because the
+    /// windows use different pixel formats, the context is created without sharing. Sharing is not supported
+    /// with WebGL, where the parameter is ignored.
     pub fn new_context(
         window_builder: winit::window::WindowBuilder,
+        vsync: bool,
+        shared_context: Option<&OpenGLContext>,
         #[cfg(target_arch = "wasm32")] canvas_id: &str,
     ) -> Self {
         #[cfg(not(target_arch = "wasm32"))]
         {
             use glutin::ContextBuilder;
             let windowed_context = crate::event_loop::with_window_target(|event_loop| {
-                let builder = ContextBuilder::new().with_vsync(true);
+                let builder = ContextBuilder::new().with_vsync(vsync);
                 // With latest Windows 10 and VmWare glutin's default for srgb produces surfaces that are always rendered black :(
                 #[cfg(target_os = "windows")]
                 let builder = builder.with_srgb(false);
-                match builder.build_windowed(window_builder, event_loop.event_loop_target()) {
+                let target = event_loop.event_loop_target();
+                // The state of the shared context is borrowed only while creating the new one, it may be current or not.
+                let shared_state = shared_context.map(|context| context.state.borrow());
+                let shared_result = match shared_state.as_ref().and_then(|state| state.as_ref()) {
+                    None => None,
+                    Some(OpenGLContextState::NotCurrent(shared)) => Some(
+                        builder
+                            .clone()
+                            .with_shared_lists(shared.context())
+                            .build_windowed(window_builder.clone(), target),
+                    ),
+                    Some(OpenGLContextState::Current(shared)) => Some(
+                        builder
+                            .clone()
+                            .with_shared_lists(shared.context())
+                            .build_windowed(window_builder.clone(), target),
+                    ),
+                };
+                let result = match shared_result {
+                    Some(Ok(new_context)) => Ok(new_context),
+                    _ => builder.build_windowed(window_builder, target),
+                };
+                match result {
                     Ok(new_context) => new_context,
                     Err(creation_error) => {
                         panic!("Failed to create OpenGL context: {}", creation_error)
                     }
                 }
             });
-            let windowed_context = unsafe { windowed_context.make_current().unwrap() };
+            let (context, window) = unsafe { windowed_context.split() };
+            let context = unsafe { context.make_current().unwrap() };
 
             #[cfg(target_os = "macos")]
             {
                 use cocoa::appkit::NSView;
                 use winit::platform::macos::WindowExtMacOS;
-                let ns_view = windowed_context.window().ns_view();
+                let ns_view = window.ns_view();
                 let view_id: cocoa::base::id = ns_view as *const _ as *mut _;
                 unsafe {
                     NSView::setLayerContentsPlacement(view_id, cocoa::appkit::NSViewLayerContentsPlacement::NSViewLayerContentsPlacementTopLeft)
                 }
             }
 
-            Self(RefCell::new(Some(OpenGLContextState::Current(windowed_context))))
+            Self {
+                state: RefCell::new(Some(OpenGLContextState::Current(context))),
+                window: Rc::new(window),
+                context_lost: Default::default(),
+                vsync: Cell::new(vsync),
+                lost_state: Default::default(),
+            }
         }
 
         #[cfg(target_arch = "wasm32")]
         {
             use wasm_bindgen::JsCast;
 
+            let _ = shared_context;
+
             let canvas = web_sys::window()
                 .unwrap()
                 .document()
@@ -233,15 +389,146 @@ pub fn new_context(
                 }
             }
 
-            Self(RefCell::new(Some(OpenGLContextState::Current { window, canvas })))
+            // The browser may reclaim the WebGL context at any time, for example when the tab is in the background.
+            // Preventing the default action of the event allows the browser to restore it later. Drawing into a lost
+            // context does nothing, so we only report the loss once the context is restored, when the renderer can
+            // re-create its resources.
+            let context_lost = Rc::new(Cell::new(false));
+
+            let on_context_lost: Box<dyn FnMut(web_sys::Event)> =
+                Box::new(|event: web_sys::Event| event.prevent_default());
+            let context_lost_closure = wasm_bindgen::closure::Closure::wrap(on_context_lost);
+            canvas
+                .add_event_listener_with_callback(
+                    "webglcontextlost",
+                    context_lost_closure.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+            context_lost_closure.forget();
+
+            let on_context_restored: Box<dyn FnMut(web_sys::Event)> = Box::new({
+                let context_lost = context_lost.clone();
+                let window = window.clone();
+                move |_: web_sys::Event| {
+                    context_lost.set(true);
+                    window.request_redraw();
+                }
+            });
+            let context_restored_closure =
+                wasm_bindgen::closure::Closure::wrap(on_context_restored);
+            canvas
+                .add_event_listener_with_callback(
+                    "webglcontextrestored",
+                    context_restored_closure.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+            context_restored_closure.forget();
+
+            Self {
+                state: RefCell::new(Some(OpenGLContextState::Current { canvas })),
+                window,
+                context_lost,
+            }
         }
     }
 
     #[cfg(not(target_arch = "wasm32"))]
     pub fn get_proc_address(&self, name: &str) -> *const std::ffi::c_void {
-        match &self.0.borrow().as_ref().unwrap() {
+        match &self.state.borrow().as_ref().unwrap() {
             OpenGLContextState::NotCurrent(_) => std::ptr::null(),
             OpenGLContextState::Current(current_ctx) => current_ctx.get_proc_address(name),
         }
     }
 }
+
+/// Creates a context for a window that exists already, which glutin only supports on some platforms.
+/// On macOS, this always fails with `NotSupported`: glutin can only create a CGL context along with its
+/// window there. A lost context can't be recovered on macOS then, but the system doesn't lose contexts.
+#[cfg(not(target_arch = "wasm32"))]
+fn build_raw_context(
+    builder: glutin::ContextBuilder<'_, glutin::NotCurrent>,
+    window: &winit::window::Window,
+) -> Result<glutin::RawContext<glutin::NotCurrent>, glutin::CreationError> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_family = "windows")] {
+            use glutin::platform::windows::RawContextExt;
+            use winit::platform::windows::WindowExtWindows;
+            unsafe { builder.build_raw_context(window.hwnd() as *mut _) }
+        } else if #[cfg(all(unix, not(target_os = "macos")))] {
+            use glutin::platform::unix::RawContextExt;
+            use winit::platform::unix::WindowExtUnix;
+            #[cfg(feature = "wayland")]
+            if let (Some(display), Some(surface)) = (window.wayland_display(), window.wayland_surface()) {
+                let size = window.inner_size();
+                return unsafe {
+                    builder.build_raw_wayland_context(display as *const _, surface, size.width, size.height)
+                };
+            }
+            #[cfg(feature = "x11")]
+            if let (Some(xconnection), Some(xwindow)) = (window.xlib_xconnection(), window.xlib_window()) {
+                return unsafe { builder.build_raw_x11_context(xconnection, xwindow) };
+            }
+            let _ = (builder, window);
+            Err(glutin::CreationError::NotSupported("no X11 or Wayland window".into()))
+        } else {
+            let _ = (builder, window);
+            Err(glutin::CreationError::NotSupported("re-creating a context for a window".into()))
+        }
+    }
+}
+
+/// Sets the swap interval of the current `context` with the function of the API that created it: EGL, which is
+/// used with Wayland and ANGLE, GLX or WGL. macOS is not supported.
+#[cfg(not(target_arch = "wasm32"))]
+fn set_swap_interval(
+    context: &glutin::RawContext<glutin::PossiblyCurrent>,
+    interval: std::os::raw::c_int,
+) {
+    use std::os::raw::{c_int, c_uint, c_void};
+
+    type EglSwapIntervalFn = extern "system" fn(*const c_void, c_int) -> c_uint;
+    type SwapIntervalFn = extern "system" fn(c_int) -> c_int;
+
+    let egl_swap_interval = || {
+        let display = unsafe { glutin::platform::ContextTraitExt::get_egl_display(&**context) };
+        let proc_address = context.get_proc_address("eglSwapInterval");
+        if let (Some(display), false) = (display, proc_address.is_null()) {
+            let swap_interval: EglSwapIntervalFn = unsafe { std::mem::transmute(proc_address) };
+            swap_interval(display, interval);
+        }
+    };
+    let swap_interval = |candidates: &[&str]| {
+        for name in candidates {
+            let proc_address = context.get_proc_address(name);
+            if !proc_address.is_null() {
+                let swap_interval: SwapIntervalFn = unsafe { std::mem::transmute(proc_address) };
+                swap_interval(interval);
+                break;
+            }
+        }
+    };
+
+    cfg_if::cfg_if! {
+        if #[cfg(target_family = "windows")] {
+            use glutin::platform::windows::RawHandle;
+            match unsafe { glutin::platform::ContextTraitExt::raw_handle(&**context) } {
+                RawHandle::Egl(_) => egl_swap_interval(),
+                RawHandle::Wgl(_) => swap_interval(&["wglSwapIntervalEXT"]),
+            }
+        } else if #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))] {
+            use glutin::platform::unix::RawHandle;
+            match unsafe { glutin::platform::ContextTraitExt::raw_handle(&**context) } {
+                RawHandle::Egl(_) => egl_swap_interval(),
+                RawHandle::Glx(_) => swap_interval(&["glXSwapIntervalMESA", "glXSwapIntervalSGI"]),
+            }
+        } else {
+            let _ = (egl_swap_interval, swap_interval);
+        }
+    }
+}
@@ -31,6 +31,7 @@ pub(crate) struct GLWindow<Renderer: WinitCompatibleRenderer + 'static> {
     map_state: RefCell<GraphicsWindowBackendState<Renderer>>,
     keyboard_modifiers: std::cell::Cell<KeyboardModifiers>,
     currently_pressed_key_code: std::cell::Cell<Option<winit::event::VirtualKeyCode>>,
+    vsync: std::cell::Cell<bool>,
 
     renderer: Renderer,
 
@@ -55,6 +56,7 @@ pub(crate) fn new(#[cfg(target_arch = "wasm32")] canvas_id: String) -> Rc<dyn Pl
             }),
             keyboard_modifiers: Default::default(),
             currently_pressed_key_code: Default::default(),
+            vsync: std::cell::Cell::new(true),
             renderer: Renderer::new(
                 &(self_weak.clone() as _),
                 #[cfg(target_arch = "wasm32")]
@@ -116,7 +118,24 @@ fn draw(&self) {
             None => return, // caller bug, doesn't make sense to call draw() when not mapped
         };
 
+        // Nothing can be rendered into a lost context. If it can't be re-created yet, for example
+        // while the GPU is still resetting, try again a bit later.
+        if !window.canvas.is_valid() && !self.renderer.recreate_lost_canvas(&window.canvas) {
+            let self_weak = self.self_weak.clone();
+            corelib::timers::Timer::single_shot(std::time::Duration::from_millis(100), move || {
+                if let Some(self_) = self_weak.upgrade() {
+                    self_.request_redraw();
+                }
+            });
+            return;
+        }
+
         self.renderer.render(&window.canvas, self);
+
+        if !window.canvas.is_valid() {
+            // The context was lost while rendering, draw again with a new one.
+            window.canvas.with_window_handle(|winit_window| winit_window.request_redraw());
+        }
     }
 
     fn with_window_handle(&self, callback: &mut dyn FnMut(&winit::window::Window)) {
@@ -340,7 +359,7 @@ fn show(&self) {
             window_builder
         };
 
-        let canvas = self.renderer.create_canvas(window_builder);
+        let canvas = self.renderer.create_canvas(window_builder, self.vsync.get());
 
         let id = canvas.with_window_handle(|winit_window| {
             self.window.window_handle().set_scale_factor(
@@ -408,6 +427,15 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         });
     }
 
+    fn set_ime_cursor_area(&self, rect: corelib::graphics::Rect) {
+        // winit places the candidate window of the input method at the given position, so use
+        // the bottom of the cursor to not cover the text being composed.
+        self.with_window_handle(&mut |winit_window| {
+            winit_window
+                .set_ime_position(winit::dpi::LogicalPosition::new(rect.min_x(), rect.max_y()));
+        });
+    }
+
     fn renderer(&self) -> &dyn i_slint_core::renderer::Renderer {
         &self.renderer
     }
@@ -429,10 +457,36 @@ fn hide_virtual_keyboard(&self) {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_virtual_keyboard(&self, _it: corelib::items::InputType) {
+        // winit only sends the events of the input method while it is allowed
+        self.with_window_handle(&mut |winit_window| winit_window.set_ime_allowed(true));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn hide_virtual_keyboard(&self) {
+        self.with_window_handle(&mut |winit_window| winit_window.set_ime_allowed(false));
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn take_snapshot(
+        &self,
+    ) -> Option<corelib::graphics::SharedPixelBuffer<corelib::graphics::Rgba8Pixel>> {
+        let mapped_window = self.borrow_mapped_window();
+        self.renderer.take_snapshot(mapped_window.as_ref().map(|window| &window.canvas), self)
+    }
+
+    fn set_vsync(&self, enabled: bool) {
+        if self.vsync.replace(enabled) != enabled {
+            if let Some(mapped_window) = self.borrow_mapped_window() {
+                mapped_window.canvas.set_vsync(enabled);
+            }
+        }
+    }
+
     fn position(&self) -> euclid::Point2D<i32, PhysicalPx> {
         match &*self.map_state.borrow() {
             GraphicsWindowBackendState::Unmapped { requested_position, .. } => {
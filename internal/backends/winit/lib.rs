@@ -22,6 +22,7 @@
 mod renderer {
     use std::rc::Weak;
 
+    use i_slint_core::graphics::{Rgba8Pixel, SharedPixelBuffer};
     use i_slint_core::window::PlatformWindow;
 
     mod boxshadowcache;
@@ -34,10 +35,28 @@ fn new(
             #[cfg(target_arch = "wasm32")] canvas_id: String,
         ) -> Self;
 
-        fn create_canvas(&self, window_builder: winit::window::WindowBuilder) -> Self::Canvas;
+        fn create_canvas(
+            &self,
+            window_builder: winit::window::WindowBuilder,
+            vsync: bool,
+        ) -> Self::Canvas;
         fn release_canvas(&self, canvas: Self::Canvas);
+        /// Replaces the graphics context of a canvas that was lost, and re-creates the GPU resources that the
+        /// renderer created with it. The window of the canvas is kept. Returns false if that failed; the canvas
+        /// must then not be rendered until it's tried again.
+        fn recreate_lost_canvas(&self, canvas: &Self::Canvas) -> bool;
 
         fn render(&self, canvas: &Self::Canvas, window: &dyn PlatformWindow);
+
+        /// Renders the window into a buffer instead of presenting it. The canvas is None when the
+        /// window is not shown.
+        fn take_snapshot(
+            &self,
+            _canvas: Option<&Self::Canvas>,
+            _window: &dyn PlatformWindow,
+        ) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+            None
+        }
     }
 
     pub(crate) trait WinitCompatibleCanvas {
@@ -47,6 +66,15 @@ pub(crate) trait WinitCompatibleCanvas {
 
         fn resize_event(&self);
 
+        /// Requests that presenting the canvas waits (or not) for the vertical blank.
+        fn set_vsync(&self, _enabled: bool) {}
+
+        /// Returns false when the graphics context was lost, for example after a GPU reset. The canvas
+        /// must then be re-created with [`WinitCompatibleRenderer::recreate_lost_canvas()`].
+        fn is_valid(&self) -> bool {
+            true
+        }
+
         #[cfg(target_arch = "wasm32")]
         fn html_canvas_element(&self) -> std::cell::Ref<web_sys::HtmlCanvasElement>;
     }
@@ -82,7 +110,8 @@ pub mod native_widgets {
 }
 pub const HAS_NATIVE_STYLE: bool = false;
 
-use i_slint_core::platform::EventLoopProxy;
+use i_slint_core::api::euclid;
+use i_slint_core::platform::{ColorScheme, EventLoopProxy, ScreenInfo};
 pub use stylemetrics::native_style_metrics_deinit;
 pub use stylemetrics::native_style_metrics_init;
 
@@ -194,17 +223,106 @@ fn invoke_from_event_loop(&self, event: Box<dyn FnOnce() + Send>) {
         Some(Box::new(Proxy))
     }
 
+    fn screens(&self) -> Vec<ScreenInfo> {
+        crate::event_loop::with_window_target(|event_loop_target| {
+            event_loop_target
+                .event_loop_target()
+                .available_monitors()
+                .map(|monitor| ScreenInfo {
+                    name: monitor.name(),
+                    position: euclid::point2(monitor.position().x, monitor.position().y),
+                    size: euclid::size2(monitor.size().width, monitor.size().height),
+                    scale_factor: monitor.scale_factor() as f32,
+                })
+                .collect()
+        })
+    }
+
+    fn color_scheme(&self) -> ColorScheme {
+        stylemetrics::color_scheme()
+    }
+
     fn set_clipboard_text(&self, text: &str) {
         crate::event_loop::with_window_target(|event_loop_target| {
-            event_loop_target.clipboard().set_contents(text.into()).ok()
+            event_loop_target.clipboard().standard().set_contents(text.into()).ok()
         });
     }
 
     fn clipboard_text(&self) -> Option<String> {
         crate::event_loop::with_window_target(|event_loop_target| {
-            event_loop_target.clipboard().get_contents().ok()
+            event_loop_target.clipboard().standard().get_contents().ok()
         })
     }
+
+    fn set_primary_selection_text(&self, text: &str) {
+        crate::event_loop::with_window_target(|event_loop_target| {
+            event_loop_target.clipboard().primary()?.set_contents(text.into()).ok()
+        });
+    }
+
+    fn primary_selection_text(&self) -> Option<String> {
+        crate::event_loop::with_window_target(|event_loop_target| {
+            event_loop_target.clipboard().primary()?.get_contents().ok()
+        })
+    }
+
+    // copypasta only handles text, so the images go through arboard
+    #[cfg(not(target_arch = "wasm32"))]
+    fn set_clipboard_image(&self, image: &i_slint_core::graphics::Image) {
+        use i_slint_core::graphics::SharedImageBuffer;
+        let image_inner: &i_slint_core::ImageInner = image.into();
+        let buffer = match image_inner.render_to_buffer(None) {
+            Some(buffer) => buffer,
+            None => return,
+        };
+        let bytes: Vec<u8> = match &buffer {
+            SharedImageBuffer::RGB8(buffer) => buffer
+                .as_slice()
+                .iter()
+                .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, 255])
+                .collect(),
+            SharedImageBuffer::RGBA8(buffer) => buffer.as_bytes().to_vec(),
+            SharedImageBuffer::RGBA8Premultiplied(buffer) => buffer
+                .as_slice()
+                .iter()
+                .flat_map(|pixel| {
+                    let unpremultiply = |channel: u8| match pixel.a {
+                        0 => 0,
+                        alpha => (channel as u16 * 255 / alpha as u16) as u8,
+                    };
+                    [
+                        unpremultiply(pixel.r),
+                        unpremultiply(pixel.g),
+                        unpremultiply(pixel.b),
+                        pixel.a,
+                    ]
+                })
+                .collect(),
+        };
+        let image_data = arboard::ImageData {
+            width: buffer.width() as usize,
+            height: buffer.height() as usize,
+            bytes: bytes.into(),
+        };
+        arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_image(image_data)).ok();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn clipboard_image(&self) -> Option<i_slint_core::graphics::Image> {
+        use i_slint_core::graphics::{Image, Rgba8Pixel, SharedPixelBuffer};
+        let image_data =
+            arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_image()).ok()?;
+        if image_data.width == 0
+            || image_data.height == 0
+            || image_data.bytes.len() != image_data.width * image_data.height * 4
+        {
+            return None;
+        }
+        let mut buffer =
+            SharedPixelBuffer::<Rgba8Pixel>::new(image_data.width as u32, image_data.height as u32);
+        buffer.make_mut_bytes().copy_from_slice(&image_data.bytes);
+        Some(Image::from_rgba8(buffer))
+    }
 }
 
 pub(crate) trait WindowSystemName {
@@ -9,7 +9,8 @@
     euclid, GraphicsAPI, RenderingNotifier, RenderingState, SetRenderingNotifierError,
 };
 use i_slint_core::graphics::{
-    rendering_metrics_collector::RenderingMetricsCollector, Point, Rect, Size,
+    rendering_metrics_collector::RenderingMetricsCollector, Point, Rect, Rgba8Pixel,
+    SharedPixelBuffer, Size,
 };
 use i_slint_core::renderer::Renderer;
 use i_slint_core::window::{PlatformWindow, WindowHandleAccess};
@@ -47,9 +48,15 @@ fn new(
         }
     }
 
-    fn create_canvas(&self, window_builder: winit::window::WindowBuilder) -> FemtoVGCanvas {
+    fn create_canvas(
+        &self,
+        window_builder: winit::window::WindowBuilder,
+        vsync: bool,
+    ) -> FemtoVGCanvas {
         let opengl_context = crate::OpenGLContext::new_context(
             window_builder,
+            vsync,
+            None,
             #[cfg(target_arch = "wasm32")]
             &self.canvas_id,
         );
@@ -62,46 +69,7 @@ fn create_canvas(&self, window_builder: winit::window::WindowBuilder) -> FemtoVG
             ),
         );
 
-        #[cfg(not(target_arch = "wasm32"))]
-        let gl_renderer = unsafe {
-            femtovg::renderer::OpenGl::new_from_function(|s| {
-                opengl_context.get_proc_address(s) as *const _
-            })
-            .unwrap()
-        };
-
-        #[cfg(target_arch = "wasm32")]
-        let gl_renderer = match femtovg::renderer::OpenGl::new_from_html_canvas(
-            &opengl_context.html_canvas_element(),
-        ) {
-            Ok(gl_renderer) => gl_renderer,
-            Err(_) => {
-                use wasm_bindgen::JsCast;
-
-                // I don't believe that there's a way of disabling the 2D canvas.
-                let context_2d = opengl_context
-                    .html_canvas_element()
-                    .get_context("2d")
-                    .unwrap()
-                    .unwrap()
-                    .dyn_into::<web_sys::CanvasRenderingContext2d>()
-                    .unwrap();
-                context_2d.set_font("20px serif");
-                // We don't know if we're rendering on dark or white background, so choose a "color" in the middle for the text.
-                context_2d.set_fill_style(&wasm_bindgen::JsValue::from_str("red"));
-                context_2d
-                    .fill_text("Slint requires WebGL to be enabled in your browser", 0., 30.)
-                    .unwrap();
-                panic!("Cannot proceed without WebGL - aborting")
-            }
-        };
-
-        let canvas = femtovg::Canvas::new_with_text_context(
-            gl_renderer,
-            self::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone()),
-        )
-        .unwrap();
-        let canvas = Rc::new(RefCell::new(canvas));
+        let canvas = Rc::new(RefCell::new(create_femtovg_canvas(&opengl_context)));
         let result = FemtoVGCanvas {
             canvas,
             graphics_cache: Default::default(),
@@ -127,13 +95,109 @@ fn release_canvas(&self, canvas: Self::Canvas) {
         })
     }
 
+    fn recreate_lost_canvas(&self, canvas: &FemtoVGCanvas) -> bool {
+        if let Some(callback) = self.rendering_notifier.borrow_mut().as_mut() {
+            canvas.with_graphics_api(|api| callback.notify(RenderingState::RenderingTeardown, &api))
+        }
+        canvas.graphics_cache.clear_all();
+        canvas.texture_cache.borrow_mut().clear();
+        if !canvas.opengl_context.recreate() {
+            return false;
+        }
+        let lost_canvas = canvas.canvas.replace(create_femtovg_canvas(&canvas.opengl_context));
+        canvas.opengl_context.release_lost_context(|| drop(lost_canvas));
+        if let Some(callback) = self.rendering_notifier.borrow_mut().as_mut() {
+            canvas.with_graphics_api(|api| callback.notify(RenderingState::RenderingSetup, &api))
+        }
+        true
+    }
+
     fn render(&self, canvas: &FemtoVGCanvas, platform_window: &dyn PlatformWindow) {
+        canvas.opengl_context.make_current();
+        if !canvas.opengl_context.is_valid() {
+            return;
+        }
+        self.draw_frame(canvas, platform_window);
+        canvas.opengl_context.swap_buffers();
+        canvas.opengl_context.make_not_current();
+    }
+
+    fn take_snapshot(
+        &self,
+        canvas: Option<&FemtoVGCanvas>,
+        platform_window: &dyn PlatformWindow,
+    ) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+        let canvas = canvas?;
+        canvas.opengl_context.make_current();
+        if !canvas.opengl_context.is_valid() {
+            return None;
+        }
+        self.draw_frame(canvas, platform_window);
+        // Read back the frame that was drawn in the back buffer, which is not swapped to the screen
+        let screenshot = canvas.canvas.borrow_mut().screenshot();
+        canvas.opengl_context.make_not_current();
+        let screenshot = screenshot.ok()?;
+        let mut buffer =
+            SharedPixelBuffer::new(screenshot.width() as u32, screenshot.height() as u32);
+        for (target, pixel) in buffer.make_mut_slice().iter_mut().zip(screenshot.pixels()) {
+            *target = pixel;
+        }
+        Some(buffer)
+    }
+}
+
+/// Creates the FemtoVG canvas that renders into the current `opengl_context`
+fn create_femtovg_canvas(
+    opengl_context: &crate::OpenGLContext,
+) -> femtovg::Canvas<femtovg::renderer::OpenGl> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let gl_renderer = unsafe {
+        femtovg::renderer::OpenGl::new_from_function(|s| {
+            opengl_context.get_proc_address(s) as *const _
+        })
+        .unwrap()
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    let gl_renderer = match femtovg::renderer::OpenGl::new_from_html_canvas(
+        &opengl_context.html_canvas_element(),
+    ) {
+        Ok(gl_renderer) => gl_renderer,
+        Err(_) => {
+            use wasm_bindgen::JsCast;
+
+            // I don't believe that there's a way of disabling the 2D canvas.
+            let context_2d = opengl_context
+                .html_canvas_element()
+                .get_context("2d")
+                .unwrap()
+                .unwrap()
+                .dyn_into::<web_sys::CanvasRenderingContext2d>()
+                .unwrap();
+            context_2d.set_font("20px serif");
+            // We don't know if we're rendering on dark or white background, so choose a "color" in the middle for the text.
+            context_2d.set_fill_style(&wasm_bindgen::JsValue::from_str("red"));
+            context_2d
+                .fill_text("Slint requires WebGL to be enabled in your browser", 0., 30.)
+                .unwrap();
+            panic!("Cannot proceed without WebGL - aborting")
+        }
+    };
+
+    femtovg::Canvas::new_with_text_context(
+        gl_renderer,
+        self::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone()),
+    )
+    .unwrap()
+}
+
+impl FemtoVGRenderer {
+    /// Draws the window into the back buffer of the current OpenGL context.
+    fn draw_frame(&self, canvas: &FemtoVGCanvas, platform_window: &dyn PlatformWindow) {
         let size = canvas.opengl_context.window().inner_size();
         let width = size.width;
         let height = size.height;
 
-        canvas.opengl_context.make_current();
-
         let window = platform_window.window().window_handle();
 
         window.draw_contents(|components| {
@@ -200,9 +264,6 @@ fn render(&self, canvas: &FemtoVGCanvas, platform_window: &dyn PlatformWindow) {
         if let Some(callback) = self.rendering_notifier.borrow_mut().as_mut() {
             canvas.with_graphics_api(|api| callback.notify(RenderingState::AfterRendering, &api))
         }
-
-        canvas.opengl_context.swap_buffers();
-        canvas.opengl_context.make_not_current();
     }
 }
 
@@ -213,8 +274,24 @@ fn text_size(
         text: &str,
         max_width: Option<Coord>,
         scale_factor: f32,
+        rules: i_slint_core::graphics::TextMeasureRules,
     ) -> Size {
-        crate::renderer::femtovg::fonts::text_size(&font_request, scale_factor, text, max_width)
+        crate::renderer::femtovg::fonts::text_size(
+            &font_request,
+            scale_factor,
+            text,
+            max_width,
+            rules,
+        )
+    }
+
+    fn missing_glyphs(
+        &self,
+        font_request: i_slint_core::graphics::FontRequest,
+        text: &str,
+        scale_factor: f32,
+    ) -> Vec<std::ops::Range<usize>> {
+        crate::renderer::femtovg::fonts::missing_glyphs(font_request, scale_factor, text)
     }
 
     fn text_input_byte_offset_for_position(
@@ -230,23 +307,20 @@ fn text_input_byte_offset_for_position(
         let window = platform_window.window().window_handle();
 
         let scale_factor = window.scale_factor();
-        let pos = pos * scale_factor;
-        let text = text_input.text();
+        let text_rect = text_input.text_rect();
+        let pos = (pos - text_rect.origin.to_vector()) * scale_factor;
+        let (text, _) = text_input.text_with_preedit();
 
         let mut result = text.len();
 
-        let width = text_input.width() * scale_factor;
-        let height = text_input.height() * scale_factor;
+        let width = text_rect.width() * scale_factor;
+        let height = text_rect.height() * scale_factor;
         if width <= 0. || height <= 0. || pos.y < 0. {
             return 0;
         }
 
         let font = crate::renderer::femtovg::fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text_input.font_request(&platform_window),
-                scale_factor,
-                &text_input.text(),
-            )
+            cache.borrow_mut().font(text_input.font_request(&platform_window), scale_factor, &text)
         });
 
         let is_password =
@@ -269,8 +343,9 @@ fn text_input_byte_offset_for_position(
             Size::new(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
-            i_slint_core::items::TextOverflow::Clip,
+            None,
             text_input.single_line(),
+            text_input.measure_rules(),
             paint,
             |line_text, line_pos, start, metrics| {
                 if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
@@ -308,7 +383,7 @@ fn text_input_cursor_rect_for_byte_offset(
 
         let window = platform_window.window().window_handle();
 
-        let text = text_input.text();
+        let (text, _) = text_input.text_with_preedit();
         let scale_factor = window.scale_factor();
 
         let font_size = text_input
@@ -316,20 +391,17 @@ fn text_input_cursor_rect_for_byte_offset(
             .pixel_size
             .unwrap_or(fonts::DEFAULT_FONT_SIZE);
 
+        let text_rect = text_input.text_rect();
         let mut result = Point::default();
 
-        let width = text_input.width() * scale_factor;
-        let height = text_input.height() * scale_factor;
+        let width = text_rect.width() * scale_factor;
+        let height = text_rect.height() * scale_factor;
         if width <= 0. || height <= 0. {
-            return Rect::new(result, Size::new(1.0, font_size));
+            return Rect::new(text_rect.origin, Size::new(1.0, font_size));
         }
 
         let font = crate::renderer::femtovg::fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text_input.font_request(&platform_window),
-                scale_factor,
-                &text_input.text(),
-            )
+            cache.borrow_mut().font(text_input.font_request(&platform_window), scale_factor, &text)
         });
 
         let paint = font.init_paint(text_input.letter_spacing() * scale_factor, Default::default());
@@ -339,8 +411,9 @@ fn text_input_cursor_rect_for_byte_offset(
             Size::new(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
-            i_slint_core::items::TextOverflow::Clip,
+            None,
             text_input.single_line(),
+            text_input.measure_rules(),
             paint,
             |line_text, line_pos, start, metrics| {
                 if (start..=(start + line_text.len())).contains(&byte_offset) {
@@ -352,12 +425,15 @@ fn text_input_cursor_rect_for_byte_offset(
                     }
                     if let Some(last) = metrics.glyphs.last() {
                         result = line_pos + euclid::vec2(last.x + last.advance_x, last.y);
+                    } else {
+                        // The empty line after a trailing newline
+                        result = line_pos;
                     }
                 }
             },
         );
 
-        Rect::new(result / scale_factor, Size::new(1.0, font_size))
+        Rect::new(text_rect.origin + (result / scale_factor).to_vector(), Size::new(1.0, font_size))
     }
 
     fn register_font_from_memory(
@@ -402,13 +478,21 @@ fn component_destroyed(&self, component: i_slint_core::component::ComponentRef)
     }
 
     fn with_window_handle<T>(&self, callback: impl FnOnce(&winit::window::Window) -> T) -> T {
-        callback(&*self.opengl_context.window())
+        callback(self.opengl_context.window())
     }
 
     fn resize_event(&self) {
         self.opengl_context.ensure_resized()
     }
 
+    fn set_vsync(&self, enabled: bool) {
+        self.opengl_context.set_vsync(enabled)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.opengl_context.is_valid()
+    }
+
     #[cfg(target_arch = "wasm32")]
     fn html_canvas_element(&self) -> std::cell::Ref<web_sys::HtmlCanvasElement> {
         self.opengl_context.html_canvas_element()
@@ -5,11 +5,12 @@
 
 use femtovg::TextContext;
 use i_slint_core::api::euclid;
-use i_slint_core::graphics::{FontRequest, Point, Size};
-use i_slint_core::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use i_slint_core::graphics::{FontRequest, Point, Size, TextMeasureRules};
+use i_slint_core::items::{TextHorizontalAlignment, TextVerticalAlignment, TextWrap};
 use i_slint_core::{SharedString, SharedVector};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 pub const DEFAULT_FONT_SIZE: f32 = 12.;
 pub const DEFAULT_FONT_WEIGHT: i32 = 400; // CSS normal
@@ -85,7 +86,13 @@ pub fn init_paint(&self, letter_spacing: f32, mut paint: femtovg::Paint) -> femt
         paint
     }
 
-    pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>) -> Size {
+    pub fn text_size(
+        &self,
+        letter_spacing: f32,
+        text: &str,
+        max_width: Option<f32>,
+        rules: TextMeasureRules,
+    ) -> Size {
         let paint = self.init_paint(letter_spacing, femtovg::Paint::default());
         let font_metrics = self.text_context.measure_font(paint).unwrap();
         let mut lines = 0;
@@ -93,7 +100,13 @@ pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>)
         let mut start = 0;
         if let Some(max_width) = max_width {
             while start < text.len() {
-                let index = self.text_context.break_text(max_width, &text[start..], paint).unwrap();
+                let index = break_text(
+                    &self.text_context,
+                    max_width,
+                    &text[start..],
+                    paint,
+                    rules.break_anywhere,
+                );
                 if index == 0 {
                     break;
                 }
@@ -111,20 +124,58 @@ pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>)
                 width = measure.width().max(width);
             }
         }
+        if rules.adds_trailing_line(text) {
+            lines += 1;
+        }
         euclid::size2(width, lines as f32 * font_metrics.height())
     }
 }
 
+/// Returns the byte index at which the first line of `text` ends so that it fits in `max_width`,
+/// like `TextContext::break_text`. With `break_anywhere`, the line can end at any character and not
+/// only at a word boundary, and it contains at least one character.
+fn break_text(
+    text_context: &TextContext,
+    max_width: f32,
+    text: &str,
+    paint: femtovg::Paint,
+    break_anywhere: bool,
+) -> usize {
+    if !break_anywhere {
+        return text_context.break_text(max_width, text, paint).unwrap();
+    }
+    let line_end = text.find('\n').map_or(text.len(), |i| i + 1);
+    let line = text[..line_end].trim_end_matches('\n');
+    let metrics = text_context.measure_text(0., 0., line, paint).unwrap();
+    let mut x = 0.;
+    for glyph in &metrics.glyphs {
+        x += glyph.advance_x;
+        if x > max_width && glyph.byte_index > 0 {
+            return glyph.byte_index;
+        }
+    }
+    line_end
+}
+
+pub(crate) fn missing_glyphs(
+    font_request: i_slint_core::graphics::FontRequest,
+    scale_factor: f32,
+    text: &str,
+) -> Vec<Range<usize>> {
+    FONT_CACHE.with(|cache| cache.borrow_mut().missing_glyphs(font_request, scale_factor, text))
+}
+
 pub(crate) fn text_size(
     font_request: &i_slint_core::graphics::FontRequest,
     scale_factor: f32,
     text: &str,
     max_width: Option<f32>,
+    rules: TextMeasureRules,
 ) -> Size {
     let font =
         FONT_CACHE.with(|cache| cache.borrow_mut().font(font_request.clone(), scale_factor, text));
     let letter_spacing = font_request.letter_spacing.unwrap_or_default();
-    font.text_size(letter_spacing, text, max_width.map(|x| x * scale_factor)) / scale_factor
+    font.text_size(letter_spacing, text, max_width.map(|x| x * scale_factor), rules) / scale_factor
 }
 
 #[derive(Copy, Clone)]
@@ -311,12 +362,32 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         new_font
     }
 
-    pub fn font(
+    pub fn font(&mut self, request: FontRequest, scale_factor: f32, reference_text: &str) -> Font {
+        self.font_with_faces(request, scale_factor, reference_text).0
+    }
+
+    /// Returns the byte ranges of the characters of `text` that are neither in the font selected
+    /// for the request, nor in any of its fallback fonts.
+    pub fn missing_glyphs(
+        &mut self,
+        request: FontRequest,
+        scale_factor: f32,
+        text: &str,
+    ) -> Vec<Range<usize>> {
+        let (_, face_ids) = self.font_with_faces(request, scale_factor, text);
+        i_slint_core::renderer::missing_glyph_ranges(text, |ch| {
+            face_ids.iter().any(|face_id| self.has_glyph(*face_id, ch))
+        })
+    }
+
+    // Same as font(), but also returns the fontdb faces of the primary font and of the selected
+    // fallback fonts.
+    fn font_with_faces(
         &mut self,
         mut request: FontRequest,
         scale_factor: f32,
         reference_text: &str,
-    ) -> Font {
+    ) -> (Font, Vec<fontdb::ID>) {
         request.pixel_size = Some(request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE) * scale_factor);
         request.weight = request.weight.or(Some(DEFAULT_FONT_WEIGHT));
 
@@ -356,7 +427,7 @@ pub fn font(
             Vec::new()
         };
 
-        let fonts = core::iter::once(primary_font.femtovg_font_id)
+        let loaded_fonts = core::iter::once(primary_font)
             .chain(fallbacks.iter().filter_map(|fallback_request| {
                 if matches!(coverage_result, GlyphCoverageCheckResult::Complete) {
                     return None;
@@ -371,18 +442,35 @@ pub fn font(
                 );
 
                 if matches!(coverage_result, GlyphCoverageCheckResult::Improved) {
-                    Some(fallback_font.femtovg_font_id)
+                    Some(fallback_font)
                 } else {
                     None
                 }
             }))
-            .collect::<SharedVector<_>>();
+            .collect::<Vec<_>>();
 
-        Font {
-            fonts,
+        let font = Font {
+            fonts: loaded_fonts.iter().map(|font| font.femtovg_font_id).collect(),
             text_context: self.text_context.clone(),
             pixel_size: request.pixel_size.unwrap(),
+        };
+        (font, loaded_fonts.iter().map(|font| font.fontdb_face_id).collect())
+    }
+
+    fn has_glyph(&mut self, face_id: fontdb::ID, ch: char) -> bool {
+        let coverage = self.loaded_font_coverage.entry(face_id).or_default();
+        if let Some(has_glyph) = coverage.exact_glyph_coverage.get(&ch) {
+            return *has_glyph;
         }
+        let has_glyph = self
+            .available_fonts
+            .with_face_data(face_id, |face_data, face_index| {
+                let face = ttf_parser::Face::from_slice(face_data, face_index).unwrap();
+                face.glyph_index(ch).is_some()
+            })
+            .unwrap_or(false);
+        coverage.exact_glyph_coverage.insert(ch, has_glyph);
+        has_glyph
     }
 
     #[cfg(target_os = "macos")]
@@ -626,6 +714,9 @@ fn check_and_update_script_coverage(
 /// Layout the given string in lines, and call the `layout_line` callback with the line to draw at position y.
 /// The signature of the `layout_line` function is: `(canvas, text, pos, start_index, line_metrics)`.
 /// start index is the starting byte of the text in the string.
+/// When `rules` say so, a trailing newline is followed by an empty line at the end of the string,
+/// which is where the cursor goes when it is at the end of the text.
+/// The text that doesn't fit is replaced with `ellipsis`, or clipped when it is `None`.
 /// Returns the baseline y coordinate.
 pub(crate) fn layout_text_lines(
     string: &str,
@@ -633,13 +724,16 @@ pub(crate) fn layout_text_lines(
     Size { width: max_width, height: max_height, .. }: Size,
     (horizontal_alignment, vertical_alignment): (TextHorizontalAlignment, TextVerticalAlignment),
     wrap: TextWrap,
-    overflow: TextOverflow,
+    ellipsis: Option<&str>,
     single_line: bool,
+    rules: TextMeasureRules,
     paint: femtovg::Paint,
     mut layout_line: impl FnMut(&str, Point, usize, &femtovg::TextMetrics),
 ) -> f32 {
-    let wrap = wrap == TextWrap::WordWrap;
-    let elide = overflow == TextOverflow::Elide;
+    let rules = TextMeasureRules { break_anywhere: wrap == TextWrap::CharWrap, ..rules };
+    let wrap = wrap != TextWrap::NoWrap;
+    let elide = ellipsis.is_some();
+    let ellipsis = ellipsis.unwrap_or_default();
 
     let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
     let font_metrics = text_context.measure_font(paint).unwrap();
@@ -654,6 +748,7 @@ pub(crate) fn layout_text_lines(
                 paint.letter_spacing(),
                 string,
                 if wrap { Some(max_width) } else { None },
+                rules,
             )
             .height
         }
@@ -682,7 +777,8 @@ pub(crate) fn layout_text_lines(
     let mut start = 0;
     'lines: while start < string.len() && y + font_height <= max_height {
         if wrap && (!elide || y + 2. * font_height <= max_height) {
-            let index = text_context.break_text(max_width, &string[start..], paint).unwrap();
+            let index =
+                break_text(&text_context, max_width, &string[start..], paint, rules.break_anywhere);
             if index == 0 {
                 // FIXME the word is too big to be shown, but we should still break, ideally
                 break;
@@ -706,7 +802,7 @@ pub(crate) fn layout_text_lines(
             if text_metrics.width() > max_width || elide_last_line {
                 let w = max_width
                     - if elide {
-                        text_context.measure_text(0., 0., "…", paint).unwrap().width()
+                        text_context.measure_text(0., 0., ellipsis, paint).unwrap().width()
                     } else {
                         0.
                     };
@@ -716,7 +812,7 @@ pub(crate) fn layout_text_lines(
                     if current_x >= w {
                         let txt = &line[..glyph.byte_index];
                         if elide {
-                            let elided = format!("{}…", txt);
+                            let elided = format!("{}{}", txt, ellipsis);
                             process_line(&elided, y, start, &text_metrics);
                         } else {
                             process_line(txt, y, start, &text_metrics);
@@ -727,7 +823,7 @@ pub(crate) fn layout_text_lines(
                     }
                 }
                 if elide_last_line {
-                    let elided = format!("{}…", line);
+                    let elided = format!("{}{}", line, ellipsis);
                     process_line(&elided, y, start, &text_metrics);
                     y += font_height;
                     start = index;
@@ -739,5 +835,9 @@ pub(crate) fn layout_text_lines(
             start = index;
         }
     }
+    if !single_line && rules.adds_trailing_line(string) && y + font_height <= max_height {
+        let text_metrics = text_context.measure_text(0., 0., "", paint).unwrap();
+        process_line("", y, string.len(), &text_metrics);
+    }
     baseline_y
 }
@@ -172,34 +172,13 @@ fn draw_rectangle(&mut self, rect: Pin<&items::Rectangle>, _: &ItemRc) {
     }
 
     fn draw_border_rectangle(&mut self, rect: Pin<&items::BorderRectangle>, _: &ItemRc) {
-        let mut geometry = item_rect(rect, self.scale_factor);
-        if geometry.is_empty() {
-            return;
-        }
-
-        let mut border_width = rect.border_width() * self.scale_factor;
-        // In CSS the border is entirely towards the inside of the boundary
-        // geometry, while in femtovg the line with for a stroke is 50% in-
-        // and 50% outwards. We choose the CSS model, so the inner rectangle
-        // is adjusted accordingly.
-        adjust_rect_and_border_for_inner_drawing(&mut geometry, &mut border_width);
-
-        let mut path = rect_with_radius_to_path(geometry, rect.border_radius() * self.scale_factor);
-
-        let fill_paint = self.brush_to_paint(rect.background(), &mut path);
-
-        let border_paint = self.brush_to_paint(rect.border_color(), &mut path).map(|mut paint| {
-            paint.set_line_width(border_width);
-            paint
-        });
-
-        let mut canvas = self.canvas.borrow_mut();
-        if let Some(paint) = fill_paint {
-            canvas.fill_path(&mut path, paint);
-        }
-        if let Some(border_paint) = border_paint {
-            canvas.stroke_path(&mut path, border_paint);
-        }
+        self.draw_border_rectangle_impl(
+            item_rect(rect, self.scale_factor),
+            rect.background(),
+            rect.border_color(),
+            rect.border_width(),
+            rect.border_radius(),
+        );
     }
 
     fn draw_image(&mut self, image: Pin<&items::ImageItem>, item_rc: &ItemRc) {
@@ -234,8 +213,11 @@ fn draw_clipped_image(&mut self, clipped_image: Pin<&items::ClippedImage>, item_
     }
 
     fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
-        let max_width = text.width() * self.scale_factor;
-        let max_height = text.height() * self.scale_factor;
+        let layout_rect = text
+            .layout_rect(self.window.window_handle())
+            .scale(self.scale_factor, self.scale_factor);
+        let max_width = layout_rect.width();
+        let max_height = layout_rect.height();
 
         if max_width <= 0. || max_height <= 0. {
             return;
@@ -243,21 +225,37 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
 
         let string = text.text();
         let string = string.as_str();
-        let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text.font_request(self.window.window_handle()),
-                self.scale_factor,
-                &text.text(),
-            )
-        });
-
-        let paint = match self
-            .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
-        {
-            Some(paint) => font.init_paint(text.letter_spacing() * self.scale_factor, paint),
+        // With auto-shrink, the font request measures the text, which uses the font cache too
+        let font_request = text.font_request(self.window.window_handle());
+        let font = fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request, self.scale_factor, &text.text()));
+
+        let letter_spacing = text.letter_spacing() * self.scale_factor;
+        let mut text_path = rect_to_path(item_rect(text, self.scale_factor));
+        let paint = self
+            .brush_to_paint(text.color(), &mut text_path)
+            .map(|paint| font.init_paint(letter_spacing, paint));
+        let stroke_width = text.text_stroke_width() * self.scale_factor;
+        let stroke_paint = if stroke_width > 0. {
+            self.brush_to_paint(text.text_stroke_color(), &mut text_path).map(|paint| {
+                let mut paint = font.init_paint(letter_spacing, paint);
+                // The stroke is centered on the outline of the glyphs, and its inner half is
+                // covered by the fill
+                paint.set_line_width(stroke_width * 2.);
+                paint.set_line_join(femtovg::LineJoin::Round);
+                paint
+            })
+        } else {
+            None
+        };
+        let layout_paint = match paint.or(stroke_paint) {
+            Some(paint) => paint,
             None => return,
         };
 
+        let ellipsis = text.ellipsis();
+        let ellipsis = (text.overflow() == items::TextOverflow::Elide).then(|| ellipsis.as_str());
+
         let mut canvas = self.canvas.borrow_mut();
         fonts::layout_text_lines(
             string,
@@ -265,18 +263,28 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             Size::new(max_width, max_height),
             (text.horizontal_alignment(), text.vertical_alignment()),
             text.wrap(),
-            text.overflow(),
+            ellipsis,
             false,
-            paint,
+            Default::default(),
+            layout_paint,
             |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+                let to_draw = to_draw.trim_end();
+                let pos = pos + layout_rect.origin.to_vector();
+                if let Some(stroke_paint) = stroke_paint {
+                    canvas.stroke_text(pos.x, pos.y, to_draw, stroke_paint).unwrap();
+                }
+                if let Some(paint) = paint {
+                    canvas.fill_text(pos.x, pos.y, to_draw, paint).unwrap();
+                }
             },
         );
     }
 
     fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
-        let width = text_input.width() * self.scale_factor;
-        let height = text_input.height() * self.scale_factor;
+        self.draw_text_input_frame(text_input);
+        let text_rect = text_input.text_rect().scale(self.scale_factor, self.scale_factor);
+        let width = text_rect.width();
+        let height = text_rect.height();
         if width <= 0. || height <= 0. {
             return;
         }
@@ -297,6 +305,33 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             None => return,
         };
 
+        if let Some(line_rect) =
+            text_input.current_line_rect(&self.window.window_handle().platform_window())
+        {
+            let mut line_path = rect_to_path(line_rect.scale(self.scale_factor, self.scale_factor));
+            if let Some(line_paint) =
+                self.brush_to_paint(text_input.current_line_color(), &mut line_path)
+            {
+                self.canvas.borrow_mut().fill_path(&mut line_path, line_paint);
+            }
+        }
+
+        // The suggestion is drawn after the end of the text, and doesn't affect the layout
+        let suggestion = if text_input.suggestion_visible() {
+            self.brush_to_paint(
+                text_input.suggestion_color(),
+                &mut rect_to_path(item_rect(text_input, self.scale_factor)),
+            )
+            .map(|suggestion_paint| {
+                let suggestion_paint = font
+                    .init_paint(text_input.letter_spacing() * self.scale_factor, suggestion_paint);
+                (text_input.suggestion_text(), suggestion_paint)
+            })
+        } else {
+            None
+        };
+        let mut suggestion_point: Option<Point> = None;
+
         let (mut min_select, mut max_select) = text_input.selection_anchor_and_cursor();
         let cursor_pos = text_input.cursor_position();
         let cursor_visible = cursor_pos >= 0
@@ -306,25 +341,43 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
         let mut cursor_pos = cursor_pos as usize;
         let mut canvas = self.canvas.borrow_mut();
         let font_height = canvas.measure_font(paint).unwrap().height();
-        let mut text = text_input.text();
+        let (mut text, mut preedit_range) = text_input.text_with_preedit();
+        if let Some(preedit_range) = &preedit_range {
+            // The selection is hidden while composing
+            cursor_pos = preedit_range.start + text_input.preedit_cursor_position();
+            min_select = cursor_pos;
+            max_select = cursor_pos;
+        }
 
         if let InputType::Password = text_input.input_type() {
             min_select = text[..min_select].chars().count() * PASSWORD_CHARACTER.len();
             max_select = text[..max_select].chars().count() * PASSWORD_CHARACTER.len();
             cursor_pos = text[..cursor_pos].chars().count() * PASSWORD_CHARACTER.len();
+            preedit_range = preedit_range.map(|range| {
+                text[..range.start].chars().count() * PASSWORD_CHARACTER.len()
+                    ..text[..range.end].chars().count() * PASSWORD_CHARACTER.len()
+            });
             text = SharedString::from(PASSWORD_CHARACTER.repeat(text.chars().count()));
         };
+        let underline_thickness = self.scale_factor;
 
+        let caret_style = text_input.caret_style();
         let mut cursor_point: Option<Point> = None;
+        // The text and width of the grapheme covered by a block or underline cursor
+        let mut cursor_grapheme: Option<(String, f32)> = None;
 
+        // The text is laid out inside the border
+        canvas.save();
+        canvas.translate(text_rect.origin.x, text_rect.origin.y);
         let baseline_y = fonts::layout_text_lines(
             text.as_str(),
             &font,
             Size::new(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
-            items::TextOverflow::Clip,
+            None,
             text_input.single_line(),
+            text_input.measure_rules(),
             paint,
             |to_draw, pos, start, metrics| {
                 let range = start..(start + to_draw.len());
@@ -400,6 +453,28 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
                     // no selection on this line
                     canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
                 };
+                if let Some(preedit_range) = &preedit_range {
+                    let underline_start = preedit_range.start.max(range.start);
+                    let underline_end = preedit_range.end.min(range.end);
+                    if underline_start < underline_end {
+                        let x_at = |offset: usize| {
+                            metrics
+                                .glyphs
+                                .iter()
+                                .find(|glyph| glyph.byte_index == offset - start)
+                                .map_or(metrics.width(), |glyph| glyph.x - glyph.bearing_x)
+                        };
+                        let start_x = x_at(underline_start);
+                        let underline_rect = Rect::new(
+                            pos + euclid::vec2(start_x, font_height - underline_thickness),
+                            Size::new(x_at(underline_end) - start_x, underline_thickness),
+                        );
+                        canvas.fill_path(&mut rect_to_path(underline_rect), paint);
+                    }
+                }
+                if suggestion.is_some() && range.end == text.len() {
+                    suggestion_point = Some([pos.x + metrics.width(), pos.y].into());
+                }
                 if cursor_visible
                     && (range.contains(&cursor_pos)
                         || (cursor_pos == range.end && cursor_pos == text.len()))
@@ -416,22 +491,132 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
                         })
                         .unwrap_or_else(|| metrics.width());
                     cursor_point = Some([pos.x + cursor_x, pos.y].into());
+                    if caret_style != items::CaretStyle::Bar {
+                        // The glyph following the cursor marks the end of the grapheme under it
+                        let next = metrics
+                            .glyphs
+                            .iter()
+                            .filter(|glyph| {
+                                glyph.byte_index > cursor_pos - start
+                                    && glyph.byte_index < to_draw.len()
+                            })
+                            .map(|glyph| (glyph.byte_index, glyph.x))
+                            .min_by_key(|(byte_index, _)| *byte_index);
+                        let (end, end_x) = next.unwrap_or((to_draw.len(), metrics.width()));
+                        let grapheme = to_draw[cursor_pos - start..end].trim_end();
+                        if !grapheme.is_empty() {
+                            cursor_grapheme = Some((grapheme.to_string(), end_x - cursor_x));
+                        }
+                    }
                 }
             },
         );
 
+        if let Some((suggestion_text, suggestion_paint)) = &suggestion {
+            let suggestion_point = suggestion_point.unwrap_or_else(|| [0., baseline_y].into());
+            canvas
+                .fill_text(
+                    suggestion_point.x,
+                    suggestion_point.y,
+                    suggestion_text.as_str(),
+                    *suggestion_paint,
+                )
+                .unwrap();
+        }
+
         if let Some(cursor_point) =
             cursor_point.or_else(|| cursor_visible.then(|| [0., baseline_y].into()))
         {
-            let mut cursor_rect = femtovg::Path::new();
-            cursor_rect.rect(
-                cursor_point.x,
-                cursor_point.y,
-                text_input.text_cursor_width() * self.scale_factor,
+            let grapheme_width = match &cursor_grapheme {
+                Some((_, width)) => *width,
+                None => canvas.measure_text(0., 0., " ", paint).map_or(0., |m| m.width()),
+            };
+            let cursor_rect = items::text_cursor_rect(
+                caret_style,
+                cursor_point,
                 font_height,
+                text_input.text_cursor_width() * self.scale_factor,
+                grapheme_width,
+            );
+            canvas.fill_path(&mut rect_to_path(cursor_rect), paint);
+            if let (items::CaretStyle::Block, Some((grapheme, _))) = (caret_style, cursor_grapheme)
+            {
+                let mut inverted_paint = paint;
+                inverted_paint
+                    .set_color(to_femtovg_color(&text_input.selection_foreground_color()));
+                canvas.fill_text(cursor_point.x, cursor_point.y, grapheme, inverted_paint).unwrap();
+            }
+        }
+        canvas.restore();
+
+        // The rectangles from the text input are already in its coordinates, including the border
+        for cursor_rect in
+            text_input.secondary_cursor_rects(&self.window.window_handle().platform_window())
+        {
+            canvas.fill_path(
+                &mut rect_to_path(cursor_rect.scale(self.scale_factor, self.scale_factor)),
+                paint,
             );
-            canvas.fill_path(&mut cursor_rect, paint);
         }
+
+        let handle_paint =
+            femtovg::Paint::color(to_femtovg_color(&text_input.selection_background_color()));
+        for handle_rect in
+            text_input.selection_handle_rects(&self.window.window_handle().platform_window())
+        {
+            let handle_rect = handle_rect.scale(self.scale_factor, self.scale_factor);
+            let mut handle_path = femtovg::Path::new();
+            handle_path.circle(
+                handle_rect.center().x,
+                handle_rect.center().y,
+                handle_rect.width() / 2.,
+            );
+            canvas.fill_path(&mut handle_path, handle_paint);
+        }
+    }
+
+    fn draw_text_input_placeholder(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
+        self.draw_text_input_frame(text_input);
+        let text_rect = text_input.text_rect().scale(self.scale_factor, self.scale_factor);
+        let max_width = text_rect.width();
+        let max_height = text_rect.height();
+        if max_width <= 0. || max_height <= 0. {
+            return;
+        }
+
+        let placeholder_text = text_input.placeholder_text();
+        let font = fonts::FONT_CACHE.with(|cache| {
+            cache.borrow_mut().font(
+                text_input.font_request(&self.window.window_handle().platform_window()),
+                self.scale_factor,
+                &placeholder_text,
+            )
+        });
+
+        let paint = match self.brush_to_paint(
+            text_input.placeholder_color(),
+            &mut rect_to_path(item_rect(text_input, self.scale_factor)),
+        ) {
+            Some(paint) => font.init_paint(text_input.letter_spacing() * self.scale_factor, paint),
+            None => return,
+        };
+
+        let mut canvas = self.canvas.borrow_mut();
+        fonts::layout_text_lines(
+            placeholder_text.as_str(),
+            &font,
+            Size::new(max_width, max_height),
+            (text_input.horizontal_alignment(), text_input.vertical_alignment()),
+            text_input.wrap(),
+            None,
+            text_input.single_line(),
+            text_input.measure_rules(),
+            paint,
+            |to_draw, pos, _, _| {
+                let pos = text_rect.origin + pos.to_vector();
+                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+            },
+        );
     }
 
     fn draw_path(&mut self, path: Pin<&items::Path>, _: &ItemRc) {
@@ -1249,6 +1434,57 @@ fn draw_image_impl(
         })
     }
 
+    /// Draws the `background` and the border of the text input, behind the text.
+    fn draw_text_input_frame(&mut self, text_input: Pin<&items::TextInput>) {
+        self.draw_border_rectangle_impl(
+            item_rect(text_input, self.scale_factor),
+            text_input.background(),
+            text_input.border_color(),
+            text_input.border_width(),
+            text_input.border_radius(),
+        );
+    }
+
+    /// Fills `geometry` with the `background` and strokes the border inside of it, with rounded
+    /// corners if `border_radius` is not zero. Used for the `BorderRectangle` and the frame of
+    /// the `TextInput`.
+    fn draw_border_rectangle_impl(
+        &mut self,
+        mut geometry: Rect,
+        background: Brush,
+        border_color: Brush,
+        border_width: f32,
+        border_radius: f32,
+    ) {
+        if geometry.is_empty() {
+            return;
+        }
+
+        let mut border_width = border_width * self.scale_factor;
+        // In CSS the border is entirely towards the inside of the boundary
+        // geometry, while in femtovg the line with for a stroke is 50% in-
+        // and 50% outwards. We choose the CSS model, so the inner rectangle
+        // is adjusted accordingly.
+        adjust_rect_and_border_for_inner_drawing(&mut geometry, &mut border_width);
+
+        let mut path = rect_with_radius_to_path(geometry, border_radius * self.scale_factor);
+
+        let fill_paint = self.brush_to_paint(background, &mut path);
+
+        let border_paint = self.brush_to_paint(border_color, &mut path).map(|mut paint| {
+            paint.set_line_width(border_width);
+            paint
+        });
+
+        let mut canvas = self.canvas.borrow_mut();
+        if let Some(paint) = fill_paint {
+            canvas.fill_path(&mut path, paint);
+        }
+        if let Some(border_paint) = border_paint {
+            canvas.stroke_path(&mut path, border_paint);
+        }
+    }
+
     fn brush_to_paint(&self, brush: Brush, path: &mut femtovg::Path) -> Option<femtovg::Paint> {
         if brush.is_transparent() {
             return None;
@@ -51,8 +51,12 @@ fn new(platform_window_weak: &Weak<dyn PlatformWindow>) -> Self {
         }
     }
 
-    fn create_canvas(&self, window_builder: winit::window::WindowBuilder) -> Self::Canvas {
-        let surface = DefaultSurface::new(window_builder);
+    fn create_canvas(
+        &self,
+        window_builder: winit::window::WindowBuilder,
+        vsync: bool,
+    ) -> Self::Canvas {
+        let surface = DefaultSurface::new(window_builder, vsync);
 
         let rendering_metrics_collector = RenderingMetricsCollector::new(
             self.platform_window_weak.clone(),
@@ -83,6 +87,20 @@ fn release_canvas(&self, canvas: Self::Canvas) {
         });
     }
 
+    fn recreate_lost_canvas(&self, canvas: &Self::Canvas) -> bool {
+        if let Some(callback) = self.rendering_notifier.borrow_mut().as_mut() {
+            canvas.with_graphics_api(|api| callback.notify(RenderingState::RenderingTeardown, &api))
+        }
+        canvas.image_cache.clear_all();
+        if !canvas.surface.recreate_lost() {
+            return false;
+        }
+        if let Some(callback) = self.rendering_notifier.borrow_mut().as_mut() {
+            canvas.with_graphics_api(|api| callback.notify(RenderingState::RenderingSetup, &api))
+        }
+        true
+    }
+
     fn render(&self, canvas: &Self::Canvas, platform_window: &dyn PlatformWindow) {
         let window = platform_window.window().window_handle();
 
@@ -144,7 +162,12 @@ fn text_size(
         text: &str,
         max_width: Option<i_slint_core::Coord>,
         scale_factor: f32,
+        rules: i_slint_core::graphics::TextMeasureRules,
     ) -> i_slint_core::graphics::Size {
+        // Measure without the trailing newline, and add the empty line it starts only if the
+        // rules say so.
+        let adds_trailing_line = rules.adds_trailing_line(text);
+        let text = text.strip_suffix('\n').unwrap_or(text);
         let layout = textlayout::create_layout(
             font_request,
             scale_factor,
@@ -153,10 +176,15 @@ fn text_size(
             max_width.map(|w| w * scale_factor),
             Default::default(),
             Default::default(),
+            rules.break_anywhere,
         );
 
-        [layout.max_intrinsic_width().ceil() / scale_factor, layout.height().ceil() / scale_factor]
-            .into()
+        let mut height = layout.height();
+        if adds_trailing_line {
+            height += height / layout.line_number().max(1) as f32;
+        }
+
+        [layout.max_intrinsic_width().ceil() / scale_factor, height.ceil() / scale_factor].into()
     }
 
     fn text_input_byte_offset_for_position(
@@ -207,7 +235,7 @@ fn set_rendering_notifier(
 
 pub trait Surface {
     const SUPPORTS_GRAPHICS_API: bool;
-    fn new(window_builder: winit::window::WindowBuilder) -> Self;
+    fn new(window_builder: winit::window::WindowBuilder, vsync: bool) -> Self;
     fn name(&self) -> &'static str;
     fn with_graphics_api(&self, callback: impl FnOnce(GraphicsAPI<'_>));
     fn with_window_handle<T>(&self, callback: impl FnOnce(&winit::window::Window) -> T) -> T;
@@ -219,6 +247,15 @@ fn render(
         callback: impl FnOnce(&mut skia_safe::Canvas, &mut skia_safe::gpu::DirectContext),
     );
     fn resize_event(&self);
+    fn set_vsync(&self, _enabled: bool) {}
+    fn is_valid(&self) -> bool {
+        true
+    }
+    /// Replaces the lost graphics context and the Skia objects created with it. Returns false if
+    /// that failed.
+    fn recreate_lost(&self) -> bool {
+        true
+    }
 }
 
 pub struct SkiaCanvas<SurfaceType: Surface> {
@@ -239,6 +276,14 @@ fn with_window_handle<T>(&self, callback: impl FnOnce(&winit::window::Window) ->
     fn resize_event(&self) {
         self.surface.resize_event()
     }
+
+    fn set_vsync(&self, enabled: bool) {
+        self.surface.set_vsync(enabled)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.surface.is_valid()
+    }
 }
 
 impl<SurfaceType: Surface> SkiaCanvas<SurfaceType> {
@@ -275,7 +275,7 @@ pub struct D3DSurface {
 impl super::Surface for D3DSurface {
     const SUPPORTS_GRAPHICS_API: bool = false;
 
-    fn new(window_builder: winit::window::WindowBuilder) -> Self {
+    fn new(window_builder: winit::window::WindowBuilder, _vsync: bool) -> Self {
         let window = crate::event_loop::with_window_target(|event_loop| {
             window_builder.build(event_loop.event_loop_target()).unwrap()
         });
@@ -350,8 +350,11 @@ fn draw_text(
         text: std::pin::Pin<&i_slint_core::items::Text>,
         _self_rc: &i_slint_core::items::ItemRc,
     ) {
-        let max_width = text.width() * self.scale_factor;
-        let max_height = text.height() * self.scale_factor;
+        let layout_rect = text
+            .layout_rect(self.window.window_handle())
+            .scale(self.scale_factor, self.scale_factor);
+        let max_width = layout_rect.width();
+        let max_height = layout_rect.height();
 
         if max_width <= 0. || max_height <= 0. {
             return;
@@ -361,39 +364,110 @@ fn draw_text(
         let string = string.as_str();
         let font_request = text.font_request(self.window.window_handle());
 
-        let paint = match self.brush_to_paint(text.color(), max_width, max_height) {
+        let paint = self.brush_to_paint(text.color(), max_width, max_height);
+        let stroke_width = text.text_stroke_width() * self.scale_factor;
+        let stroke_paint = if stroke_width > 0. {
+            self.brush_to_paint(text.text_stroke_color(), max_width, max_height).map(|mut paint| {
+                // The stroke is centered on the outline of the glyphs, and its inner half is
+                // covered by the fill
+                paint.set_style(skia_safe::PaintStyle::Stroke);
+                paint.set_stroke_width(stroke_width * 2.);
+                paint.set_stroke_join(skia_safe::paint::Join::Round);
+                paint
+            })
+        } else {
+            None
+        };
+
+        let ellipsis = text.ellipsis();
+        self.canvas.save();
+        self.canvas.clip_rect(to_skia_rect(&layout_rect), None, None);
+        // The outline is drawn first, so that the fill is drawn over it
+        for paint in stroke_paint.into_iter().chain(paint) {
+            let mut text_style = skia_safe::textlayout::TextStyle::new();
+            text_style.set_foreground_color(paint);
+
+            let layout = super::textlayout::create_layout(
+                font_request.clone(),
+                self.scale_factor,
+                string,
+                Some(text_style),
+                Some(max_width),
+                text.horizontal_alignment(),
+                (text.overflow() == items::TextOverflow::Elide).then(|| ellipsis.as_str()),
+                text.wrap() == items::TextWrap::CharWrap,
+            );
+
+            let y = match text.vertical_alignment() {
+                items::TextVerticalAlignment::Top => 0.,
+                items::TextVerticalAlignment::Center => (max_height - layout.height()) / 2.,
+                items::TextVerticalAlignment::Bottom => max_height - layout.height(),
+            };
+
+            layout.paint(
+                &mut self.canvas,
+                skia_safe::Point::new(layout_rect.origin.x, layout_rect.origin.y + y),
+            );
+        }
+        self.canvas.restore();
+    }
+
+    fn draw_text_input(
+        &mut self,
+        _text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) {
+        //todo!()
+    }
+
+    fn draw_text_input_placeholder(
+        &mut self,
+        text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) {
+        let text_rect = text_input.text_rect().scale(self.scale_factor, self.scale_factor);
+        let max_width = text_rect.width();
+        let max_height = text_rect.height();
+
+        if max_width <= 0. || max_height <= 0. {
+            return;
+        }
+
+        let placeholder_text = text_input.placeholder_text();
+        let font_request = text_input.font_request(&self.window.window_handle().platform_window());
+
+        let paint = match self.brush_to_paint(text_input.placeholder_color(), max_width, max_height)
+        {
             Some(paint) => paint,
             None => return,
         };
-
         let mut text_style = skia_safe::textlayout::TextStyle::new();
         text_style.set_foreground_color(paint);
 
         let layout = super::textlayout::create_layout(
             font_request,
             self.scale_factor,
-            string,
+            placeholder_text.as_str(),
             Some(text_style),
             Some(max_width),
-            text.horizontal_alignment(),
-            text.overflow(),
+            text_input.horizontal_alignment(),
+            None,
+            text_input.wrap() == items::TextWrap::CharWrap,
         );
 
-        let y = match text.vertical_alignment() {
+        let y = match text_input.vertical_alignment() {
             items::TextVerticalAlignment::Top => 0.,
             items::TextVerticalAlignment::Center => (max_height - layout.height()) / 2.,
             items::TextVerticalAlignment::Bottom => max_height - layout.height(),
         };
 
-        layout.paint(&mut self.canvas, skia_safe::Point::new(0., y));
-    }
-
-    fn draw_text_input(
-        &mut self,
-        _text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
-        _self_rc: &i_slint_core::items::ItemRc,
-    ) {
-        //todo!()
+        self.canvas.save();
+        self.canvas.clip_rect(to_skia_rect(&text_rect), None, None);
+        layout.paint(
+            &mut self.canvas,
+            skia_safe::Point::new(text_rect.origin.x, text_rect.origin.y + y),
+        );
+        self.canvas.restore();
     }
 
     fn draw_path(
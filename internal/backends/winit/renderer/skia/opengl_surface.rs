@@ -1,12 +1,12 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use i_slint_core::api::GraphicsAPI;
 
 pub struct OpenGLSurface {
-    fb_info: skia_safe::gpu::gl::FramebufferInfo,
+    fb_info: Cell<skia_safe::gpu::gl::FramebufferInfo>,
     surface: RefCell<skia_safe::Surface>,
     gr_context: RefCell<skia_safe::gpu::DirectContext>,
     opengl_context: crate::OpenGLContext,
@@ -15,44 +15,17 @@ pub struct OpenGLSurface {
 impl super::Surface for OpenGLSurface {
     const SUPPORTS_GRAPHICS_API: bool = true;
 
-    fn new(window_builder: winit::window::WindowBuilder) -> Self {
-        let opengl_context = crate::OpenGLContext::new_context(window_builder);
+    fn new(window_builder: winit::window::WindowBuilder, vsync: bool) -> Self {
+        let opengl_context = crate::OpenGLContext::new_context(window_builder, vsync, None);
 
         let (fb_info, surface, gr_context) =
-            opengl_context.with_current_context(|opengl_context| {
-                let fb_info = {
-                    use glow::HasContext;
-
-                    let gl = unsafe {
-                        glow::Context::from_loader_function(|s| {
-                            opengl_context.get_proc_address(s) as *const _
-                        })
-                    };
-                    let fboid = unsafe { gl.get_parameter_i32(glow::FRAMEBUFFER_BINDING) };
-
-                    skia_safe::gpu::gl::FramebufferInfo {
-                        fboid: fboid.try_into().unwrap(),
-                        format: skia_safe::gpu::gl::Format::RGBA8.into(),
-                    }
-                };
-
-                let gl_interface = skia_safe::gpu::gl::Interface::new_load_with(|symbol| {
-                    opengl_context.get_proc_address(symbol)
-                });
-
-                let mut gr_context =
-                    skia_safe::gpu::DirectContext::new_gl(gl_interface, None).unwrap();
-
-                let surface = Self::create_internal_surface(
-                    fb_info,
-                    &opengl_context.glutin_context(),
-                    &mut gr_context,
-                )
-                .into();
-
-                (fb_info, surface, gr_context)
-            });
-        Self { fb_info, surface, gr_context: RefCell::new(gr_context), opengl_context }
+            opengl_context.with_current_context(Self::create_gpu_resources);
+        Self {
+            fb_info: Cell::new(fb_info),
+            surface: RefCell::new(surface),
+            gr_context: RefCell::new(gr_context),
+            opengl_context,
+        }
     }
 
     fn name(&self) -> &'static str {
@@ -67,7 +40,7 @@ fn with_graphics_api(&self, callback: impl FnOnce(GraphicsAPI<'_>)) {
     }
 
     fn with_window_handle<T>(&self, callback: impl FnOnce(&winit::window::Window) -> T) -> T {
-        callback(&*self.opengl_context.window())
+        callback(self.opengl_context.window())
     }
 
     fn with_active_surface<T>(&self, callback: impl FnOnce() -> T) -> T {
@@ -83,14 +56,18 @@ fn render(
         let height = size.height;
 
         self.opengl_context.make_current();
+        if !self.opengl_context.is_valid() {
+            return;
+        }
 
         let gr_context = &mut self.gr_context.borrow_mut();
 
         let mut surface = self.surface.borrow_mut();
         if width != surface.width() as u32 || height != surface.height() as u32 {
             *surface = Self::create_internal_surface(
-                self.fb_info,
+                self.fb_info.get(),
                 &self.opengl_context.glutin_context(),
+                size,
                 gr_context,
             );
         }
@@ -106,16 +83,74 @@ fn render(
     fn resize_event(&self) {
         self.opengl_context.ensure_resized();
     }
+
+    fn set_vsync(&self, enabled: bool) {
+        self.opengl_context.set_vsync(enabled)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.opengl_context.is_valid()
+    }
+
+    fn recreate_lost(&self) -> bool {
+        // The objects of the lost context are gone, they must not be deleted from the new one
+        self.gr_context.borrow_mut().abandon();
+        if !self.opengl_context.recreate() {
+            return false;
+        }
+        let (fb_info, surface, gr_context) = Self::create_gpu_resources(&self.opengl_context);
+        self.fb_info.set(fb_info);
+        *self.surface.borrow_mut() = surface;
+        *self.gr_context.borrow_mut() = gr_context;
+        true
+    }
 }
 
 impl OpenGLSurface {
+    /// Creates the Skia objects that render into the current `opengl_context`
+    fn create_gpu_resources(
+        opengl_context: &crate::OpenGLContext,
+    ) -> (skia_safe::gpu::gl::FramebufferInfo, skia_safe::Surface, skia_safe::gpu::DirectContext)
+    {
+        let fb_info = {
+            use glow::HasContext;
+
+            let gl = unsafe {
+                glow::Context::from_loader_function(|s| {
+                    opengl_context.get_proc_address(s) as *const _
+                })
+            };
+            let fboid = unsafe { gl.get_parameter_i32(glow::FRAMEBUFFER_BINDING) };
+
+            skia_safe::gpu::gl::FramebufferInfo {
+                fboid: fboid.try_into().unwrap(),
+                format: skia_safe::gpu::gl::Format::RGBA8.into(),
+            }
+        };
+
+        let gl_interface = skia_safe::gpu::gl::Interface::new_load_with(|symbol| {
+            opengl_context.get_proc_address(symbol)
+        });
+
+        let mut gr_context = skia_safe::gpu::DirectContext::new_gl(gl_interface, None).unwrap();
+
+        let surface = Self::create_internal_surface(
+            fb_info,
+            &opengl_context.glutin_context(),
+            opengl_context.window().inner_size(),
+            &mut gr_context,
+        );
+
+        (fb_info, surface, gr_context)
+    }
+
     fn create_internal_surface(
         fb_info: skia_safe::gpu::gl::FramebufferInfo,
-        gl_context: &glutin::WindowedContext<glutin::PossiblyCurrent>,
+        gl_context: &glutin::RawContext<glutin::PossiblyCurrent>,
+        size: winit::dpi::PhysicalSize<u32>,
         gr_context: &mut skia_safe::gpu::DirectContext,
     ) -> skia_safe::Surface {
         let pixel_format = gl_context.get_pixel_format();
-        let size = gl_context.window().inner_size();
         let backend_render_target = skia_safe::gpu::BackendRenderTarget::new_gl(
             (size.width.try_into().unwrap(), size.height.try_into().unwrap()),
             pixel_format.multisampling.map(|s| s.try_into().unwrap()),
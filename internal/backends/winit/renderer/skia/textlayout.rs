@@ -44,7 +44,8 @@ pub fn create_layout(
     text_style: Option<skia_safe::textlayout::TextStyle>,
     max_width: Option<Coord>,
     h_align: items::TextHorizontalAlignment,
-    overflow: items::TextOverflow,
+    ellipsis: Option<&str>,
+    break_anywhere: bool,
 ) -> skia_safe::textlayout::Paragraph {
     let mut text_style = text_style.unwrap_or_default();
 
@@ -66,8 +67,8 @@ pub fn create_layout(
 
     let mut style = skia_safe::textlayout::ParagraphStyle::new();
 
-    if overflow == items::TextOverflow::Elide {
-        style.set_ellipsis("…");
+    if let Some(ellipsis) = ellipsis {
+        style.set_ellipsis(ellipsis);
     }
 
     style.set_text_align(match h_align {
@@ -80,7 +81,14 @@ pub fn create_layout(
         skia_safe::textlayout::ParagraphBuilder::new(&style, font_cache.font_collection.clone())
     });
     builder.push_style(&text_style);
-    builder.add_text(text);
+    if break_anywhere {
+        // Skia's paragraph only breaks lines at word boundaries: a zero width space after every
+        // character allows breaking anywhere.
+        let text: String = text.chars().flat_map(|c| [c, '\u{200B}']).collect();
+        builder.add_text(&text);
+    } else {
+        builder.add_text(text);
+    }
     let mut paragraph = builder.build();
     paragraph.layout(max_width.unwrap_or(core::f32::MAX));
     paragraph
@@ -4,7 +4,7 @@
 //! Delegate the rendeing to the [`i_slint_core::swrenderer::SoftwareRenderer`]
 
 use super::WinitCompatibleCanvas;
-use i_slint_core::graphics::Rgb8Pixel;
+use i_slint_core::graphics::{Rgb8Pixel, Rgba8Pixel, SharedPixelBuffer};
 use i_slint_core::lengths::PhysicalLength;
 pub use i_slint_core::swrenderer::SoftwareRenderer;
 use i_slint_core::window::{PlatformWindow, WindowHandleAccess};
@@ -18,21 +18,51 @@ fn new(_platform_window_weak: &Weak<dyn PlatformWindow>) -> Self {
         SoftwareRenderer::new(i_slint_core::swrenderer::DirtyTracking::None)
     }
 
-    fn create_canvas(&self, window_builder: winit::window::WindowBuilder) -> Self::Canvas {
-        let opengl_context = crate::OpenGLContext::new_context(window_builder);
-
-        let gl_renderer = unsafe {
-            femtovg::renderer::OpenGl::new_from_function(|s| {
-                opengl_context.get_proc_address(s) as *const _
-            })
-            .unwrap()
-        };
-        let canvas = femtovg::Canvas::new(gl_renderer).unwrap().into();
+    fn create_canvas(
+        &self,
+        window_builder: winit::window::WindowBuilder,
+        vsync: bool,
+    ) -> Self::Canvas {
+        let opengl_context = crate::OpenGLContext::new_context(window_builder, vsync, None);
+        let canvas = create_femtovg_canvas(&opengl_context).into();
         SwCanvas { canvas, opengl_context }
     }
 
     fn release_canvas(&self, _canvas: Self::Canvas) {}
 
+    fn recreate_lost_canvas(&self, canvas: &SwCanvas) -> bool {
+        if !canvas.opengl_context.recreate() {
+            return false;
+        }
+        let lost_canvas = canvas.canvas.replace(create_femtovg_canvas(&canvas.opengl_context));
+        canvas.opengl_context.release_lost_context(|| drop(lost_canvas));
+        true
+    }
+
+    fn take_snapshot(
+        &self,
+        _canvas: Option<&SwCanvas>,
+        platform_window: &dyn PlatformWindow,
+    ) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+        // The software renderer doesn't need the window to be shown to render into a buffer
+        let size = platform_window.window().size();
+        let (width, height) = (size.width as usize, size.height as usize);
+        let mut buffer = vec![Rgb8Pixel::default(); width * height];
+        platform_window.window().window_handle().draw_contents(|_component| {
+            Self::render(
+                &self,
+                platform_window.window(),
+                buffer.as_mut_slice(),
+                PhysicalLength::new(width as _),
+            );
+        });
+        let mut snapshot = SharedPixelBuffer::new(size.width, size.height);
+        for (target, pixel) in snapshot.make_mut_slice().iter_mut().zip(buffer) {
+            *target = pixel.alpha(255);
+        }
+        Some(snapshot)
+    }
+
     fn render(&self, canvas: &SwCanvas, platform_window: &dyn PlatformWindow) {
         let size = canvas.opengl_context.window().inner_size();
         let width = size.width as usize;
@@ -55,6 +85,9 @@ fn render(&self, canvas: &SwCanvas, platform_window: &dyn PlatformWindow) {
             imgref::ImgRef::new(&buffer, width, height).into();
 
         canvas.opengl_context.make_current();
+        if !canvas.opengl_context.is_valid() {
+            return;
+        }
         {
             let mut canvas = canvas.canvas.borrow_mut();
 
@@ -83,6 +116,19 @@ fn render(&self, canvas: &SwCanvas, platform_window: &dyn PlatformWindow) {
     }
 }
 
+/// Creates the FemtoVG canvas that presents the rendered buffer in the current `opengl_context`
+fn create_femtovg_canvas(
+    opengl_context: &crate::OpenGLContext,
+) -> femtovg::Canvas<femtovg::renderer::OpenGl> {
+    let gl_renderer = unsafe {
+        femtovg::renderer::OpenGl::new_from_function(|s| {
+            opengl_context.get_proc_address(s) as *const _
+        })
+        .unwrap()
+    };
+    femtovg::Canvas::new(gl_renderer).unwrap()
+}
+
 pub(crate) struct SwCanvas {
     canvas: RefCell<femtovg::Canvas<femtovg::renderer::OpenGl>>,
     opengl_context: crate::OpenGLContext,
@@ -92,10 +138,18 @@ impl WinitCompatibleCanvas for SwCanvas {
     fn component_destroyed(&self, _component: i_slint_core::component::ComponentRef) {}
 
     fn with_window_handle<T>(&self, callback: impl FnOnce(&winit::window::Window) -> T) -> T {
-        callback(&*self.opengl_context.window())
+        callback(self.opengl_context.window())
     }
 
     fn resize_event(&self) {
         self.opengl_context.ensure_resized()
     }
+
+    fn set_vsync(&self, enabled: bool) {
+        self.opengl_context.set_vsync(enabled)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.opengl_context.is_valid()
+    }
 }
@@ -7,9 +7,10 @@
 
 use const_field_offset::FieldOffsets;
 use core::pin::Pin;
+use i_slint_core::items::{ColorScheme, LayoutAlignment};
 #[cfg(feature = "rtti")]
 use i_slint_core::rtti::*;
-use i_slint_core::{items::LayoutAlignment, Color, Property};
+use i_slint_core::{Color, Property};
 use i_slint_core_macros::*;
 
 #[repr(C)]
@@ -28,6 +29,7 @@ pub struct NativeStyleMetrics {
     pub textedit_text_color_disabled: Property<Color>,
 
     pub dark_style: Property<bool>,
+    pub color_scheme: Property<ColorScheme>,
 
     pub placeholder_color: Property<Color>,
     pub placeholder_color_disabled: Property<Color>,
@@ -55,6 +57,7 @@ pub fn new() -> Pin<Rc<Self>> {
             textedit_background_disabled: Default::default(),
             textedit_text_color_disabled: Default::default(),
             dark_style: Default::default(),
+            color_scheme: Default::default(),
             placeholder_color: Default::default(),
             placeholder_color_disabled: Default::default(),
             tab_bar_alignment: Default::default(),
@@ -71,15 +74,63 @@ pub fn init<T>(self: Pin<Rc<Self>>, _root: T) {
     // actually uses is dark_style.
     // The actual colors are defined in the theme's .slint file.
     pub fn init_impl(self: Pin<&Self>) {
-        use dark_light::Mode;
+        self.color_scheme.set_binding(color_scheme);
+        self.dark_style.set_binding(|| color_scheme() == ColorScheme::Dark);
+    }
+}
 
-        self.dark_style.set(match dark_light::detect() {
-            Mode::Light => false,
-            Mode::Dark => true,
-        });
+thread_local! {
+    /// The color scheme of the system. It is detected once and then updated when the windowing
+    /// system reports a change.
+    static COLOR_SCHEME: Pin<Box<Property<ColorScheme>>> = {
+        #[cfg(target_arch = "wasm32")]
+        watch_prefers_color_scheme();
+        Box::pin(Property::new(detect_color_scheme()))
+    };
+}
+
+fn detect_color_scheme() -> ColorScheme {
+    use dark_light::Mode;
+
+    match dark_light::detect() {
+        Mode::Light => ColorScheme::Light,
+        Mode::Dark => ColorScheme::Dark,
     }
 }
 
+/// Returns the color scheme of the system. Bindings that call this function are re-evaluated when it
+/// changes.
+pub fn color_scheme() -> ColorScheme {
+    COLOR_SCHEME.with(|color_scheme| color_scheme.as_ref().get())
+}
+
+pub(crate) fn set_color_scheme(color_scheme: ColorScheme) {
+    COLOR_SCHEME.with(|property| property.as_ref().set(color_scheme))
+}
+
+/// Browsers don't emit a winit event when the `prefers-color-scheme` media query changes, so listen
+/// to it directly.
+#[cfg(target_arch = "wasm32")]
+fn watch_prefers_color_scheme() {
+    use wasm_bindgen::JsCast;
+
+    let media_query_list = match web_sys::window()
+        .and_then(|window| window.match_media("(prefers-color-scheme: dark)").ok().flatten())
+    {
+        Some(media_query_list) => media_query_list,
+        None => return,
+    };
+    let on_change: Box<dyn FnMut(web_sys::MediaQueryListEvent)> =
+        Box::new(|event: web_sys::MediaQueryListEvent| {
+            set_color_scheme(if event.matches() { ColorScheme::Dark } else { ColorScheme::Light })
+        });
+    let on_change_closure = wasm_bindgen::closure::Closure::wrap(on_change);
+    media_query_list
+        .add_event_listener_with_callback("change", on_change_closure.as_ref().unchecked_ref())
+        .ok();
+    on_change_closure.forget();
+}
+
 #[cfg(feature = "rtti")]
 impl i_slint_core::rtti::BuiltinGlobal for NativeStyleMetrics {
     fn new() -> Pin<Rc<Self>> {
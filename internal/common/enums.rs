@@ -47,6 +47,9 @@ enum TextWrap {
                 NoWrap,
                 /// The text will be wrapped at word boundaries.
                 WordWrap,
+                /// The text will be wrapped at any character, for text without spaces such as
+                /// long URLs, or languages that don't separate words.
+                CharWrap,
             }
 
             /// This enum describes the how the text appear if it is too wide to fit in the Text width.
@@ -57,6 +60,18 @@ enum TextOverflow {
                 Elide,
             }
 
+            /// This enum describes in which directions the text is clipped to the bounds of the Text
+            /// element when it doesn't fit in them. In the other direction, the text overflows.
+            enum TextClipAxis {
+                /// The text is clipped both horizontally and vertically.
+                Both,
+                /// The text is clipped horizontally only, and overflows at the top or the bottom,
+                /// for example to show the descenders of the last line.
+                Horizontal,
+                /// The text is clipped vertically only, and overflows at the left or the right.
+                Vertical,
+            }
+
             /// This enum describes whether an event was rejected or accepted by an event handler.
             enum EventResult {
                 /// The event is rejected by this event handler and may then be handled by the parent item
@@ -209,6 +224,43 @@ enum InputType {
                 Text,
                 /// This will render all characters with a character that defaults to "*"
                 Password,
+                /// Only the digits from 0 to 9 can be typed or pasted
+                Number,
+                /// Only the digits from 0 to 9 and a single decimal point `.` can be typed or pasted
+                Decimal,
+            }
+
+            /// This enum defines how the text cursor of a TextInput is drawn.
+            enum CaretStyle {
+                /// The default value. The cursor is drawn as a thin vertical bar between two characters,
+                /// as wide as `text-cursor-width`
+                Bar,
+                /// The cursor is drawn as a filled rectangle covering the character after it, and that
+                /// character is drawn in the `selection-foreground-color`
+                Block,
+                /// The cursor is drawn as a line below the character after it, as thick as `text-cursor-width`
+                Underline,
+            }
+
+            /// This enum defines which key accepts the `suggestion-text` of a TextInput.
+            enum SuggestionAcceptKey {
+                /// The default value. Both the tab key and the right arrow key accept the suggestion
+                TabOrRightArrow,
+                /// Only the tab key accepts the suggestion, the right arrow key doesn't do anything at
+                /// the end of the text
+                Tab,
+                /// Only the right arrow key accepts the suggestion, the tab key moves the focus
+                RightArrow,
+            }
+
+            /// This enum describes whether the system uses light or dark colors.
+            enum ColorScheme {
+                /// The default value. The system doesn't tell, or the backend can't find out
+                Unknown,
+                /// The system uses dark colors: light text on a dark background
+                Dark,
+                /// The system uses light colors: dark text on a light background
+                Light,
             }
 
             /// Enum representing the alignment property of a BoxLayout or HorizontalLayout
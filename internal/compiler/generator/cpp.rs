@@ -1394,15 +1394,28 @@ fn generate_sub_component(
         ));
 
         let ensure_updated = if let Some(listview) = &repeated.listview {
+            let vp_x = access_member(&listview.viewport_x, &ctx);
             let vp_y = access_member(&listview.viewport_y, &ctx);
             let vp_h = access_member(&listview.viewport_height, &ctx);
             let lv_h = access_member(&listview.listview_height, &ctx);
             let vp_w = access_member(&listview.viewport_width, &ctx);
             let lv_w = access_member(&listview.listview_width, &ctx);
+            let horizontal = access_member(&listview.horizontal, &ctx);
 
             format!(
-                "self->{}.ensure_updated_listview(self, &{}, &{}, &{}, {}.get(), {}.get());",
-                repeater_id, vp_w, vp_h, vp_y, lv_w, lv_h
+                "if ({horizontal}.get()) {{
+                    self->{id}.ensure_updated_listview_horizontal(self, &{vp_w}, &{vp_h}, &{vp_x}, &{lv_w}, {lv_h}.get());
+                }} else {{
+                    self->{id}.ensure_updated_listview(self, &{vp_w}, &{vp_h}, &{vp_y}, {lv_w}.get(), {lv_h}.get());
+                }}",
+                horizontal = horizontal,
+                id = repeater_id,
+                vp_w = vp_w,
+                vp_h = vp_h,
+                vp_x = vp_x,
+                vp_y = vp_y,
+                lv_w = lv_w,
+                lv_h = lv_h,
             )
         } else {
             format!("self->{id}.ensure_updated(self);", id = repeater_id)
@@ -1646,6 +1659,7 @@ fn generate_repeated_component(
     ));
 
     if let Some(listview) = &repeated.listview {
+        let p_x = access_member(&listview.prop_x, &ctx);
         let p_y = access_member(&listview.prop_y, &ctx);
         let p_height = access_member(&listview.prop_height, &ctx);
         let p_width = access_member(&listview.prop_width, &ctx);
@@ -1670,6 +1684,25 @@ fn generate_repeated_component(
                 ..Function::default()
             }),
         ));
+        repeater_struct.members.push((
+            Access::Public, // Because Repeater accesses it
+            Declaration::Function(Function {
+                name: "listview_layout_horizontal".into(),
+                signature:
+                    "(float *offset_x, const slint::private_api::Property<float> *viewport_height) const -> void"
+                        .to_owned(),
+                statements: Some(vec![
+                    "[[maybe_unused]] auto self = this;".into(),
+                    "float vp_h = viewport_height->get();".to_owned(),
+                    format!("{}.set(*offset_x);", p_x),
+                    format!("*offset_x += {}.get();", p_width),
+                    format!("float h = {}.get();", p_height),
+                    "if (vp_h < h)".to_owned(),
+                    "    viewport_height->set(h);".to_owned(),
+                ]),
+                ..Function::default()
+            }),
+        ));
     } else {
         repeater_struct.members.push((
             Access::Public, // Because Repeater accesses it
@@ -2387,6 +2420,18 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to SetFocusItem {:?}", arguments)
             }
         }
+        BuiltinFunction::SetInputMask => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                let window = access_window_field(ctx);
+                let mask_item = access_item_rc(pr, ctx);
+                format!("{}.set_input_mask({});", window, mask_item)
+            } else {
+                panic!("internal error: invalid args to SetInputMask {:?}", arguments)
+            }
+        }
+        BuiltinFunction::ClearInputMask => {
+            format!("{}.clear_input_mask();", access_window_field(ctx))
+        }
         /*  std::from_chars is unfortunately not yet implemented in gcc
         BuiltinFunction::StringIsFloat => {
             "[](const auto &a){ double v; auto r = std::from_chars(std::begin(a), std::end(a), v); return r.ptr == std::end(a); }"
@@ -658,18 +658,58 @@ fn generate_sub_component(
                 }
             });
         });
+        if let Some(listview) = &repeated.listview {
+            let scrolled = access_member(&listview.scrolled, &ctx);
+            let end_reached = access_member(&listview.end_reached, &ctx);
+            init.push(quote! {
+                _self.#repeater_id.set_listview_scroll_callback({
+                    let self_weak = slint::re_exports::VRcMapped::downgrade(&self_rc);
+                    move |row, fraction| {
+                        let self_rc = self_weak.upgrade().unwrap();
+                        let _self = self_rc.as_pin_ref();
+                        #scrolled.call(&(row as _, fraction as _))
+                    }
+                });
+                _self.#repeater_id.set_listview_end_reached_callback(0, {
+                    let self_weak = slint::re_exports::VRcMapped::downgrade(&self_rc);
+                    move || {
+                        let self_rc = self_weak.upgrade().unwrap();
+                        let _self = self_rc.as_pin_ref();
+                        #end_reached.call(&())
+                    }
+                });
+            });
+        }
         let ensure_updated = if let Some(listview) = &repeated.listview {
+            let vp_x = access_member(&listview.viewport_x, &ctx);
             let vp_y = access_member(&listview.viewport_y, &ctx);
             let vp_h = access_member(&listview.viewport_height, &ctx);
             let lv_h = access_member(&listview.listview_height, &ctx);
             let vp_w = access_member(&listview.viewport_width, &ctx);
             let lv_w = access_member(&listview.listview_width, &ctx);
+            let threshold = access_member(&listview.windowing_threshold, &ctx);
+            let springback = access_member(&listview.overscroll_springback, &ctx);
+            let horizontal = access_member(&listview.horizontal, &ctx);
+            let end_reached_threshold = access_member(&listview.end_reached_threshold, &ctx);
 
             quote! {
-                #inner_component_id::FIELD_OFFSETS.#repeater_id.apply_pin(_self).ensure_updated_listview(
-                    || { #rep_inner_component_id::new(_self.self_weak.get().unwrap().clone()).into() },
-                    #vp_w, #vp_h, #vp_y, #lv_w.get(), #lv_h
+                _self.#repeater_id.set_listview_windowing_threshold(#threshold.get().max(0) as usize);
+                _self.#repeater_id.set_listview_end_reached_threshold(#end_reached_threshold.get().max(0) as usize);
+                let springback = #springback.get();
+                _self.#repeater_id.set_listview_overscroll_springback(
+                    (springback > 0).then(|| core::time::Duration::from_millis(springback as u64))
                 );
+                if #horizontal.get() {
+                    #inner_component_id::FIELD_OFFSETS.#repeater_id.apply_pin(_self).ensure_updated_listview_horizontal(
+                        || { #rep_inner_component_id::new(_self.self_weak.get().unwrap().clone()).into() },
+                        #vp_w, #vp_h, #vp_x, #lv_w, #lv_h.get()
+                    );
+                } else {
+                    #inner_component_id::FIELD_OFFSETS.#repeater_id.apply_pin(_self).ensure_updated_listview(
+                        || { #rep_inner_component_id::new(_self.self_weak.get().unwrap().clone()).into() },
+                        #vp_w, #vp_h, #vp_y, #lv_w.get(), #lv_h
+                    );
+                }
             }
         } else {
             quote! {
@@ -1330,6 +1370,7 @@ fn generate_repeated_component(
     // let inner_component_id = self::inner_component_id(&parent_compo);
 
     let extra_fn = if let Some(listview) = &repeated.listview {
+        let p_x = access_member(&listview.prop_x, &ctx);
         let p_y = access_member(&listview.prop_y, &ctx);
         let p_height = access_member(&listview.prop_height, &ctx);
         let p_width = access_member(&listview.prop_width, &ctx);
@@ -1349,6 +1390,21 @@ fn listview_layout(
                     viewport_width.set(w);
                 }
             }
+            fn listview_layout_horizontal(
+                self: core::pin::Pin<&Self>,
+                offset_x: &mut slint::re_exports::Coord,
+                viewport_height: core::pin::Pin<&slint::re_exports::Property<slint::re_exports::Coord>>,
+            ) {
+                use slint::re_exports::*;
+                let _self = self;
+                let vp_h = viewport_height.get();
+                #p_x.set(*offset_x);
+                *offset_x += #p_width.get();
+                let h = #p_height.get();
+                if vp_h < h {
+                    viewport_height.set(h);
+                }
+            }
         }
     } else {
         // TODO: we could generate this code only if we know that this component is in a box layout
@@ -1382,11 +1438,11 @@ fn box_layout_data(self: ::core::pin::Pin<&Self>, o: slint::re_exports::Orientat
 
         impl slint::re_exports::RepeatedComponent for #inner_component_id {
             type Data = #data_type;
-            fn update(&self, _index: usize, _data: Self::Data) {
+            fn update(&self, _index: usize, _data: &Self::Data) {
                 let self_rc = self.self_weak.get().unwrap().upgrade().unwrap();
                 let _self = self_rc.as_pin_ref();
                 #(#index_prop.set(_index as _);)*
-                #(#data_prop.set(_data);)*
+                #(#data_prop.set(_data.clone());)*
             }
             #extra_fn
         }
@@ -1994,6 +2050,21 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to SetFocusItem {:?}", arguments)
             }
         }
+        BuiltinFunction::SetInputMask => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let window_tokens = access_platform_window_field(ctx);
+                let mask_item = access_item_rc(pr, ctx);
+                quote!(
+                    #window_tokens.window().window_handle().set_input_mask(Some(#mask_item));
+                )
+            } else {
+                panic!("internal error: invalid args to SetInputMask {:?}", arguments)
+            }
+        }
+        BuiltinFunction::ClearInputMask => {
+            let window_tokens = access_platform_window_field(ctx);
+            quote!(#window_tokens.window().window_handle().set_input_mask(None);)
+        }
         BuiltinFunction::ShowPopupWindow => {
             if let [Expression::NumberLiteral(popup_index), x, y, Expression::PropertyReference(parent_ref)] =
                 arguments
@@ -76,6 +76,9 @@ pub struct CompilerConfiguration {
 
     /// expose the accessible role and properties
     pub accessibility: bool,
+
+    /// The format of the generated code. The compiler warns about the features that it doesn't support.
+    pub output_format: crate::generator::OutputFormat,
 }
 
 impl CompilerConfiguration {
@@ -124,6 +127,7 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             inline_all_elements,
             scale_factor,
             accessibility: true,
+            output_format,
         }
     }
 }
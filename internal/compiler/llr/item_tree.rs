@@ -97,6 +97,7 @@ pub struct Property {
 /// The property references might be either in the parent context, or in the
 /// repeated's component context
 pub struct ListViewInfo {
+    pub viewport_x: PropertyReference,
     pub viewport_y: PropertyReference,
     pub viewport_height: PropertyReference,
     pub viewport_width: PropertyReference,
@@ -104,7 +105,21 @@ pub struct ListViewInfo {
     pub listview_height: PropertyReference,
     /// The ListView's inner visible width (not counting eventual scrollbar)
     pub listview_width: PropertyReference,
+    /// The amount of rows from which only the visible rows are instantiated
+    pub windowing_threshold: PropertyReference,
+    /// When true, the rows are laid out from left to right
+    pub horizontal: PropertyReference,
+    /// How long the viewport takes to spring back after it was scrolled past its bounds
+    pub overscroll_springback: PropertyReference,
+    /// The callback called with the first visible row when the scroll position changes
+    pub scrolled: PropertyReference,
+    /// The amount of rows before the end of the model at which `end_reached` is called
+    pub end_reached_threshold: PropertyReference,
+    /// The callback called when the visible rows approach the end of the model
+    pub end_reached: PropertyReference,
 
+    // In the repeated component context
+    pub prop_x: PropertyReference,
     // In the repeated component context
     pub prop_y: PropertyReference,
     // In the repeated component context
@@ -453,12 +453,20 @@ fn lower_repeated_component(elem: &ElementRc, ctx: &ExpressionContext) -> Repeat
     };
 
     let listview = repeated.is_listview.as_ref().map(|lv| ListViewInfo {
+        viewport_x: ctx.map_property_reference(&lv.viewport_x),
         viewport_y: ctx.map_property_reference(&lv.viewport_y),
         viewport_height: ctx.map_property_reference(&lv.viewport_height),
         viewport_width: ctx.map_property_reference(&lv.viewport_width),
         listview_height: ctx.map_property_reference(&lv.listview_height),
         listview_width: ctx.map_property_reference(&lv.listview_width),
-
+        windowing_threshold: ctx.map_property_reference(&lv.windowing_threshold),
+        horizontal: ctx.map_property_reference(&lv.horizontal),
+        overscroll_springback: ctx.map_property_reference(&lv.overscroll_springback),
+        scrolled: ctx.map_property_reference(&lv.scrolled),
+        end_reached_threshold: ctx.map_property_reference(&lv.end_reached_threshold),
+        end_reached: ctx.map_property_reference(&lv.end_reached),
+
+        prop_x: map_inner_prop("x"),
         prop_y: map_inner_prop("y"),
         prop_width: map_inner_prop("width"),
         prop_height: map_inner_prop("height"),
@@ -52,11 +52,18 @@ pub fn count_property_use(root: &PublicComponent) {
         for (idx, r) in sc.repeated.iter().enumerate() {
             r.model.borrow().visit_recursive(&mut |e| visit_expression(e, ctx));
             if let Some(lv) = &r.listview {
+                visit_property(&lv.viewport_x, ctx);
                 visit_property(&lv.viewport_y, ctx);
                 visit_property(&lv.viewport_width, ctx);
                 visit_property(&lv.viewport_height, ctx);
                 visit_property(&lv.listview_width, ctx);
                 visit_property(&lv.listview_height, ctx);
+                visit_property(&lv.windowing_threshold, ctx);
+                visit_property(&lv.horizontal, ctx);
+                visit_property(&lv.overscroll_springback, ctx);
+                visit_property(&lv.scrolled, ctx);
+                visit_property(&lv.end_reached_threshold, ctx);
+                visit_property(&lv.end_reached, ctx);
 
                 let rep_ctx = EvaluationContext::new_sub_component(
                     root,
@@ -64,6 +71,7 @@ pub fn count_property_use(root: &PublicComponent) {
                     (),
                     Some(ParentCtx::new(ctx, Some(idx))),
                 );
+                visit_property(&lv.prop_x, &rep_ctx);
                 visit_property(&lv.prop_y, &rep_ctx);
                 visit_property(&lv.prop_width, &rep_ctx);
                 visit_property(&lv.prop_height, &rep_ctx);
@@ -608,6 +608,7 @@ pub fn is_used(&self) -> bool {
 
 #[derive(Debug, Clone)]
 pub struct ListViewInfo {
+    pub viewport_x: NamedReference,
     pub viewport_y: NamedReference,
     pub viewport_height: NamedReference,
     pub viewport_width: NamedReference,
@@ -615,6 +616,18 @@ pub struct ListViewInfo {
     pub listview_height: NamedReference,
     /// The ListView's inner visible width (not counting eventual scrollbar)
     pub listview_width: NamedReference,
+    /// The amount of rows from which only the visible rows are instantiated
+    pub windowing_threshold: NamedReference,
+    /// When true, the rows are laid out from left to right
+    pub horizontal: NamedReference,
+    /// How long the viewport takes to spring back after it was scrolled past its bounds
+    pub overscroll_springback: NamedReference,
+    /// The callback called with the first visible row when the scroll position changes
+    pub scrolled: NamedReference,
+    /// The amount of rows before the end of the model at which `end_reached` is called
+    pub end_reached_threshold: NamedReference,
+    /// The callback called when the visible rows approach the end of the model
+    pub end_reached: NamedReference,
 }
 
 #[derive(Debug, Clone)]
@@ -1010,11 +1023,18 @@ fn from_repeated_node(
     ) -> ElementRc {
         let is_listview = if parent.borrow().base_type.to_string() == "ListView" {
             Some(ListViewInfo {
+                viewport_x: NamedReference::new(parent, "viewport-x"),
                 viewport_y: NamedReference::new(parent, "viewport-y"),
                 viewport_height: NamedReference::new(parent, "viewport-height"),
                 viewport_width: NamedReference::new(parent, "viewport-width"),
                 listview_height: NamedReference::new(parent, "visible-height"),
                 listview_width: NamedReference::new(parent, "visible-width"),
+                windowing_threshold: NamedReference::new(parent, "windowing-threshold"),
+                horizontal: NamedReference::new(parent, "horizontal"),
+                overscroll_springback: NamedReference::new(parent, "overscroll-springback"),
+                scrolled: NamedReference::new(parent, "scrolled"),
+                end_reached_threshold: NamedReference::new(parent, "end-reached-threshold"),
+                end_reached: NamedReference::new(parent, "end-reached"),
             })
         } else {
             None
@@ -1617,11 +1637,18 @@ fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedRefe
     let mut repeated = std::mem::take(&mut elem.borrow_mut().repeated);
     if let Some(r) = &mut repeated {
         if let Some(lv) = &mut r.is_listview {
+            vis(&mut lv.viewport_x);
             vis(&mut lv.viewport_y);
             vis(&mut lv.viewport_height);
             vis(&mut lv.viewport_width);
             vis(&mut lv.listview_height);
             vis(&mut lv.listview_width);
+            vis(&mut lv.windowing_threshold);
+            vis(&mut lv.horizontal);
+            vis(&mut lv.overscroll_springback);
+            vis(&mut lv.scrolled);
+            vis(&mut lv.end_reached_threshold);
+            vis(&mut lv.end_reached);
         }
     }
     elem.borrow_mut().repeated = repeated;
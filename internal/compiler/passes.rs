@@ -40,6 +40,8 @@
 mod resolving;
 mod unique_id;
 mod visible;
+#[cfg(feature = "cpp")]
+mod warn_unsupported_in_cpp;
 mod z_order;
 
 use std::{collections::HashSet, rc::Rc};
@@ -93,6 +95,11 @@ pub async fn run_passes(
         check_aliases::check_aliases(component, diag)
     }
 
+    #[cfg(feature = "cpp")]
+    if compiler_config.output_format == crate::generator::OutputFormat::Cpp {
+        warn_unsupported_in_cpp::warn_unsupported_in_cpp(root_component, diag);
+    }
+
     inlining::inline(doc, inlining::InlineSelection::InlineOnlyRequiredComponents);
     collect_subcomponents::collect_subcomponents(root_component);
 
@@ -6,16 +6,31 @@
 //! Rectangles which do not draw anything and have no x or y don't need to be in
 //! the item tree, we can just remove them.
 
+use crate::expression_tree::Expression;
 use crate::{langtype::Type, object_tree::*};
+use by_address::ByAddress;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 pub fn optimize_useless_rectangles(root_component: &Rc<Component>) {
+    // The elements passed to a function, like with `set-input-mask()`, must stay in the item tree
+    let mut referenced_elements = HashSet::new();
+    visit_all_expressions(root_component, |expr, _| {
+        expr.visit_recursive(&mut |expr| {
+            if let Expression::ElementReference(elem) = expr {
+                if let Some(elem) = elem.upgrade() {
+                    referenced_elements.insert(ByAddress(elem));
+                }
+            }
+        })
+    });
+
     recurse_elem_including_sub_components(root_component, &(), &mut |parent, _| {
         let mut parent = parent.borrow_mut();
         let children = std::mem::take(&mut parent.children);
 
         for elem in children {
-            if !can_optimize(&elem) {
+            if !can_optimize(&elem) || referenced_elements.contains(&ByAddress(elem.clone())) {
                 parent.children.push(elem);
                 continue;
             }
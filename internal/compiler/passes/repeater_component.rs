@@ -53,23 +53,38 @@ fn create_repeater_components(component: &Rc<Component>) {
         });
 
         if let Some(listview) = is_listview {
+            // The rows have their preferred size in the direction of the ListView, and the visible
+            // size of the ListView in the other direction
+            let size_binding =
+                |horizontal: Expression, vertical: Expression| Expression::Condition {
+                    condition: Box::new(Expression::PropertyReference(listview.horizontal.clone())),
+                    true_expr: Box::new(horizontal),
+                    false_expr: Box::new(vertical),
+                };
+            let preferred =
+                |name| Expression::PropertyReference(NamedReference::new(&comp.root_element, name));
             if !comp.root_element.borrow().is_binding_set("height", false) {
-                let preferred = Expression::PropertyReference(NamedReference::new(
-                    &comp.root_element,
-                    "preferred-height",
-                ));
+                let binding = size_binding(
+                    Expression::PropertyReference(listview.listview_height.clone()),
+                    preferred("preferred-height"),
+                );
                 comp.root_element
                     .borrow_mut()
                     .bindings
-                    .insert("height".into(), RefCell::new(preferred.into()));
+                    .insert("height".into(), RefCell::new(binding.into()));
             }
             if !comp.root_element.borrow().is_binding_set("width", false) {
-                comp.root_element.borrow_mut().bindings.insert(
-                    "width".into(),
-                    RefCell::new(Expression::PropertyReference(listview.listview_width).into()),
+                let binding = size_binding(
+                    preferred("preferred-width"),
+                    Expression::PropertyReference(listview.listview_width.clone()),
                 );
+                comp.root_element
+                    .borrow_mut()
+                    .bindings
+                    .insert("width".into(), RefCell::new(binding.into()));
             }
 
+            NamedReference::new(&comp.root_element, "x").mark_as_set();
             NamedReference::new(&comp.root_element, "y").mark_as_set();
         }
 
@@ -0,0 +1,25 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Warn about the properties that are set, but that the generated C++ code ignores
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::object_tree::Component;
+use std::rc::Rc;
+
+pub fn warn_unsupported_in_cpp(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
+    crate::object_tree::recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
+        let elem = elem.borrow();
+        let listview = match elem.repeated.as_ref().and_then(|r| r.is_listview.as_ref()) {
+            Some(listview) => listview,
+            None => return,
+        };
+        let threshold = &listview.windowing_threshold;
+        if let Some(binding) = threshold.element().borrow().bindings.get(threshold.name()) {
+            diag.push_warning(
+                "The windowing-threshold property of ListView is not supported in C++, where all the rows are instantiated".into(),
+                &binding.borrow().span,
+            );
+        }
+    });
+}
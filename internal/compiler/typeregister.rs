@@ -118,6 +118,8 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
         .chain(IntoIterator::into_iter([
             ("forward-focus", Type::ElementReference),
             ("focus", BuiltinFunction::SetFocusItem.ty()),
+            ("set-input-mask", BuiltinFunction::SetInputMask.ty()),
+            ("clear-input-mask", BuiltinFunction::ClearInputMask.ty()),
             (
                 "dialog-button-role",
                 Type::Enumeration(BUILTIN_ENUMS.with(|e| e.DialogButtonRole.clone())),
@@ -159,6 +161,14 @@ pub fn reserved_property(name: &str) -> PropertyLookupResult {
 pub fn reserved_member_function(name: &str) -> Expression {
     for (m, e) in [
         ("focus", Expression::BuiltinFunctionReference(BuiltinFunction::SetFocusItem, None)), // match for callable "focus" property
+        (
+            "set-input-mask",
+            Expression::BuiltinFunctionReference(BuiltinFunction::SetInputMask, None),
+        ),
+        (
+            "clear-input-mask",
+            Expression::BuiltinFunctionReference(BuiltinFunction::ClearInputMask, None),
+        ),
     ]
     .iter()
     {
@@ -334,9 +334,17 @@ fn test_curve(name: &str, curve: &EasingCurve) {
 
 /// Update the global animation time to the current time
 pub fn update_animations() {
+    update_animations_with_time(Instant::duration_since_start())
+}
+
+/// Update the global animation time to the given time.
+///
+/// `now` is the time elapsed since the start of the program, in the same time base as
+/// [`PlatformAbstraction::duration_since_start()`](crate::platform::PlatformAbstraction::duration_since_start).
+pub fn update_animations_with_time(now: core::time::Duration) {
     CURRENT_ANIMATION_DRIVER.with(|driver| {
         #[allow(unused_mut)]
-        let mut duration = Instant::duration_since_start().as_millis() as u64;
+        let mut duration = now.as_millis() as u64;
         #[cfg(feature = "std")]
         if let Ok(val) = std::env::var("SLINT_SLOW_ANIMATIONS") {
             let factor = val.parse().unwrap_or(2);
@@ -180,6 +180,14 @@ pub fn on_close_requested(&self, callback: impl FnMut() -> CloseRequestResponse
         self.0.on_close_requested(callback);
     }
 
+    /// This function allows registering a callback that's invoked each time the window is about
+    /// to be rendered, after the layout of all elements was computed. The callback is invoked
+    /// before the [`RenderingState::BeforeRendering`] phase of the rendering notifier, so it can
+    /// read the final geometries, for example to place a popup next to an element.
+    pub fn on_layout_settled(&self, callback: impl FnMut() + 'static) {
+        self.0.on_layout_settled(callback);
+    }
+
     /// This function issues a request to the windowing system to redraw the contents of the window.
     pub fn request_redraw(&self) {
         self.0.platform_window().request_redraw();
@@ -218,6 +226,48 @@ pub fn size(&self) -> euclid::Size2D<u32, PhysicalPx> {
         self.0.inner_size.get()
     }
 
+    /// Renders the current content of the window into a buffer of RGBA pixels, of the size of the
+    /// window in physical pixels, without presenting it on the screen. This is useful to compare the
+    /// rendering with reference images in automated tests.
+    ///
+    /// Returns None if the renderer can't render into a buffer, or, with an OpenGL renderer, if the
+    /// window was not shown yet.
+    pub fn take_snapshot(
+        &self,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        self.0.platform_window().take_snapshot()
+    }
+
+    /// Uses the image as mouse cursor over the window, for example to show a preview of the brush in a drawing
+    /// application. `hotspot` is the position in the image, in physical pixels, that designates the pointed
+    /// location. The image replaces the default cursor: while the pointer is over an element that sets its own
+    /// `mouse-cursor`, that cursor is shown instead. Use [`Self::clear_custom_cursor()`] to restore the default
+    /// cursor.
+    ///
+    /// Custom cursors are supported with the Qt backend. Other backends, including winit, keep showing the
+    /// default cursor.
+    pub fn set_custom_cursor(
+        &self,
+        image: &crate::graphics::Image,
+        hotspot: crate::lengths::PhysicalPoint,
+    ) {
+        self.0.set_custom_cursor(Some((image.clone(), hotspot)))
+    }
+
+    /// Restores the default mouse cursor after a call to [`Self::set_custom_cursor()`].
+    pub fn clear_custom_cursor(&self) {
+        self.0.set_custom_cursor(None)
+    }
+
+    /// Enables or disables the synchronization of the rendering with the vertical blank of the
+    /// display (vsync). It is enabled by default. Disabling it lets the window render as fast as
+    /// possible, which can be useful to measure the performance.
+    ///
+    /// This is only a request: renderers that don't support it and some graphics drivers ignore it.
+    pub fn set_vsync(&self, enabled: bool) {
+        self.0.platform_window().set_vsync(enabled)
+    }
+
     /// Resizes the window to the specified size on the screen, in physical pixels and excluding
     /// a window frame (if present).
     pub fn set_size(&self, size: euclid::Size2D<u32, PhysicalPx>) {
@@ -234,10 +284,87 @@ pub fn set_size(&self, size: euclid::Size2D<u32, PhysicalPx>) {
     ///
     /// The position of the event should be in logical pixel relative to the window coordinate
     ///
+    /// A wheel event is sent with the keyboard modifiers that the backend last reported as held,
+    /// so that for example Shift+Wheel scrolls horizontally.
+    ///
     /// Note: This function is usually called by the Slint backend. You should only call this function
     /// if implementing your own backend or for testing purposes.
     pub fn dispatch_pointer_event(&self, event: PointerEvent) {
-        self.0.process_mouse_input(event.into())
+        self.0.process_mouse_input(self.mouse_event(event))
+    }
+
+    /// Dispatch a pointer event that comes from a touch screen to the window
+    ///
+    /// Unlike with [`Self::dispatch_pointer_event`], items that are smaller than the size set with
+    /// [`Self::set_minimum_touch_target_size`] are hit in an enlarged area around them.
+    ///
+    /// Note: This function is usually called by the Slint backend. You should only call this function
+    /// if implementing your own backend or for testing purposes.
+    pub fn dispatch_touch_event(&self, event: PointerEvent) {
+        self.0.process_touch_input(self.mouse_event(event))
+    }
+
+    /// Converts the `event` and adds the keyboard modifiers held to the wheel events
+    fn mouse_event(&self, event: PointerEvent) -> crate::input::MouseEvent {
+        let mut event = crate::input::MouseEvent::from(event);
+        if let crate::input::MouseEvent::Wheel { modifiers, .. } = &mut event {
+            *modifiers = self.0.keyboard_modifiers();
+        }
+        event
+    }
+
+    /// Sets the minimum size, in logical pixels, of the area in which touch events hit an item.
+    ///
+    /// An item that is narrower or shorter than this size also receives the touch events that are
+    /// within an area enlarged equally on both sides to reach that size. When the enlarged
+    /// areas of several items overlap, the item in front receives the event. Mouse events always
+    /// use the exact geometry of the items. The default is 0.
+    pub fn set_minimum_touch_target_size(&self, size: f32) {
+        self.0.set_minimum_touch_target_size(size as _)
+    }
+
+    /// Sets the area of the window, in logical pixels, that is covered by the virtual keyboard, or
+    /// `None` when the virtual keyboard is hidden.
+    ///
+    /// When the item that has the keyboard focus is covered by the virtual keyboard, the `Flickable`
+    /// elements containing it are scrolled so that it becomes visible above the keyboard.
+    ///
+    /// Note: This function is usually called by the Slint backend. You should only call this function
+    /// if implementing your own backend or for testing purposes.
+    pub fn set_virtual_keyboard_rect(&self, rect: Option<euclid::Rect<f32, LogicalPx>>) {
+        self.0.set_virtual_keyboard_rect(rect.map(|rect| rect.to_untyped().cast()))
+    }
+
+    /// Moves the keyboard focus to the next item that accepts it, in the same order as with the
+    /// Tab key. The item that loses the focus receives a focus out event, and the newly focused
+    /// item a focus in event.
+    ///
+    /// If no item has the focus, the first item that accepts it is focused. After the last
+    /// such item, the focus wraps around to the first one.
+    pub fn focus_next(&self) {
+        self.0.focus_next_item()
+    }
+
+    /// Moves the keyboard focus to the previous item that accepts it, in the same order as with
+    /// Shift+Tab. The item that loses the focus receives a focus out event, and the newly focused
+    /// item a focus in event.
+    ///
+    /// If no item has the focus, the last item that accepts it is focused. Before the first
+    /// such item, the focus wraps around to the last one.
+    pub fn focus_previous(&self) {
+        self.0.focus_previous_item()
+    }
+
+    /// Removes the input mask set with `set-input-mask()` in `.slint`, so that all the items of
+    /// the window receive the mouse and keyboard input again.
+    pub fn clear_input_mask(&self) {
+        self.0.set_input_mask(None)
+    }
+
+    /// Returns true if an input mask set with `set-input-mask()` in `.slint` restricts the input
+    /// to the subtree of an item, for example while a modal dialog is shown.
+    pub fn has_input_mask(&self) -> bool {
+        self.0.input_mask().is_some()
     }
 
     /// Returns true if there is an animation currently running
@@ -0,0 +1,63 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Compares reading the rows of a model with a large row type by value, with `Model::row_data`,
+//! and by reference, with `ModelExt::with_row_data`.
+//!
+//! Run with `cargo bench -p i-slint-core --bench model`.
+
+use i_slint_core::model::{Model, ModelExt, ModelRc, SharedVectorModel};
+use i_slint_core::{SharedString, SharedVector};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Default)]
+struct LargeRow {
+    values: [f64; 64],
+    label: SharedString,
+    tags: Vec<SharedString>,
+}
+
+const ROWS: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+fn measure(name: &str, mut f: impl FnMut() -> f64) {
+    let mut total = Duration::ZERO;
+    let mut checksum = 0.;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        checksum += f();
+        total += start.elapsed();
+    }
+    println!("{:<28} {:>10.3?} per pass (checksum {})", name, total / ITERATIONS, checksum);
+}
+
+fn main() {
+    let rows = (0..ROWS)
+        .map(|i| LargeRow {
+            values: [i as f64; 64],
+            label: format!("Row {}", i).into(),
+            tags: vec!["a".into(), "b".into(), "c".into()],
+        })
+        .collect::<Vec<_>>();
+    let model = ModelRc::new(SharedVectorModel::from(SharedVector::from_slice(&rows)));
+
+    measure("row_data", || {
+        (0..model.row_count())
+            .map(|row| {
+                let data = model.row_data(row).unwrap();
+                data.values[0] + data.label.len() as f64 + data.tags.len() as f64
+            })
+            .sum()
+    });
+    measure("with_row_data", || {
+        (0..model.row_count())
+            .map(|row| {
+                model
+                    .with_row_data(row, |data| {
+                        data.values[0] + data.label.len() as f64 + data.tags.len() as f64
+                    })
+                    .unwrap()
+            })
+            .sum()
+    });
+}
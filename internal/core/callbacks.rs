@@ -41,6 +41,14 @@ pub fn call(&self, a: &Arg) -> Ret {
         r
     }
 
+    /// Returns true if a handler was set with [`Self::set_handler`]
+    pub fn has_handler(&self) -> bool {
+        let handler = self.handler.take();
+        let result = handler.is_some();
+        self.handler.set(handler);
+        result
+    }
+
     /// Set an handler to be called when the callback is called
     ///
     /// There can only be one single handler per callback.
@@ -143,6 +143,49 @@ pub struct FontRequest {
     pub letter_spacing: Option<Coord>,
 }
 
+/// The pixel size of the fonts that the renderers use when neither the element nor the window
+/// sets a font size.
+pub const DEFAULT_FONT_SIZE: Coord = 12 as Coord;
+
+impl FontRequest {
+    /// Returns this request with the fields that are not set taken from `defaults`.
+    pub fn merge(self, defaults: &FontRequest) -> FontRequest {
+        FontRequest {
+            family: self.family.or_else(|| defaults.family.clone()),
+            weight: self.weight.or(defaults.weight),
+            pixel_size: self.pixel_size.or(defaults.pixel_size),
+            letter_spacing: self.letter_spacing.or(defaults.letter_spacing),
+        }
+    }
+}
+
+/// TextMeasureRules describes how the line breaks count in the size of a text. The renderers
+/// apply the same rules when measuring and when drawing a text, so that the text fits in the
+/// measured size and the text cursor is placed consistently. Tabs are not configurable: each
+/// renderer measures a tab with the advance it draws it with.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextMeasureRules {
+    /// When true, a newline at the end of the text starts an empty line that counts in the height
+    /// of the text. This is the case in a multi-line text input, where the cursor can be placed
+    /// on that line.
+    pub trailing_newline_adds_line: bool,
+    /// When true, a text measured with a maximum width wraps at any character, and not only at
+    /// word boundaries. This is the case with [`TextWrap::CharWrap`](crate::items::TextWrap::CharWrap).
+    pub break_anywhere: bool,
+}
+
+impl TextMeasureRules {
+    /// The rules for the text of a multi-line text input.
+    pub fn for_text_input() -> Self {
+        Self { trailing_newline_adds_line: true, ..Self::default() }
+    }
+
+    /// Returns true if `text` ends with a newline that adds an empty line to its height.
+    pub fn adds_trailing_line(&self, text: &str) -> bool {
+        self.trailing_newline_adds_line && text.ends_with('\n')
+    }
+}
+
 #[cfg(feature = "ffi")]
 pub(crate) mod ffi {
     #![allow(unsafe_code)]
@@ -553,6 +553,23 @@ pub fn path(&self) -> Option<&std::path::Path> {
             _ => None,
         }
     }
+
+    /// Copies this image into the system clipboard, if the platform supports images in the
+    /// clipboard.
+    pub fn copy_to_clipboard(&self) {
+        crate::platform::PLAFTORM_ABSTRACTION_INSTANCE.with(|p| {
+            if let Some(backend) = p.get() {
+                backend.set_clipboard_image(self);
+            }
+        });
+    }
+
+    /// Returns the image stored in the system clipboard, if any and if the platform supports images
+    /// in the clipboard.
+    pub fn from_clipboard() -> Option<Self> {
+        crate::platform::PLAFTORM_ABSTRACTION_INSTANCE
+            .with(|p| p.get().and_then(|p| p.clipboard_image()))
+    }
 }
 
 /// Load an image from an image embedded in the binary.
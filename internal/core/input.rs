@@ -9,7 +9,7 @@
 use crate::item_tree::{ItemRc, ItemVisitorResult, ItemWeak, VisitChildrenResult};
 pub use crate::items::PointerEventButton;
 use crate::items::{ItemRef, TextCursorDirection};
-use crate::window::PlatformWindow;
+use crate::window::{PlatformWindow, WindowHandleAccess};
 use crate::{component::ComponentRc, SharedString};
 use crate::{Coord, Property};
 use alloc::rc::Rc;
@@ -17,6 +17,9 @@
 use const_field_offset::FieldOffsets;
 use core::pin::Pin;
 use euclid::default::Vector2D;
+#[cfg(not(feature = "std"))]
+#[allow(unused)]
+use num_traits::Float;
 
 /// A mouse or touch event
 ///
@@ -35,9 +38,24 @@ pub enum MouseEvent {
     /// Wheel was operated.
     /// `pos` is the position of the mouse when the event happens.
     /// `delta` is the amount of pixel to scroll.
-    Wheel { position: Point, delta: Point },
+    /// `modifiers` are the keyboard modifiers held while the wheel was operated.
+    Wheel { position: Point, delta: Point, modifiers: KeyboardModifiers },
     /// The mouse exited the item or component
     Exit,
+    /// The mouse entered the item. Items receive it, with their own input function, after the
+    /// event that brought the mouse over them was dispatched, and always receive an [`Self::Exit`]
+    /// before the next one.
+    Enter { position: Point },
+    /// The left button or the finger was held down without moving for the
+    /// [long press duration](crate::platform::PlatformAbstraction::long_press_duration). It is
+    /// sent between the [`Self::Pressed`] and [`Self::Released`] events, to the item that grabbed
+    /// the mouse, or else to the item under the pointer.
+    LongPress { position: Point },
+    /// Two fingers moved on a touch screen. `center` is the point between them, `scale` is the
+    /// ratio of the distance between them to the distance at the previous event, and `rotation`
+    /// is the angle in degrees by which the line between them turned clockwise since the previous
+    /// event. See [`MultiTouchState`].
+    Pinch { center: Point, scale: f32, rotation: f32 },
 }
 
 impl MouseEvent {
@@ -48,6 +66,9 @@ pub fn position(&self) -> Option<Point> {
             MouseEvent::Released { position, .. } => Some(*position),
             MouseEvent::Moved { position } => Some(*position),
             MouseEvent::Wheel { position, .. } => Some(*position),
+            MouseEvent::Enter { position } => Some(*position),
+            MouseEvent::LongPress { position } => Some(*position),
+            MouseEvent::Pinch { center, .. } => Some(*center),
             MouseEvent::Exit => None,
         }
     }
@@ -59,6 +80,9 @@ pub fn translate(&mut self, vec: Vector2D<Coord>) {
             MouseEvent::Released { position, .. } => Some(position),
             MouseEvent::Moved { position } => Some(position),
             MouseEvent::Wheel { position, .. } => Some(position),
+            MouseEvent::Enter { position } => Some(position),
+            MouseEvent::LongPress { position } => Some(position),
+            MouseEvent::Pinch { center, .. } => Some(center),
             MouseEvent::Exit => None,
         };
         if let Some(pos) = pos {
@@ -82,12 +106,121 @@ fn from(event: crate::api::PointerEvent) -> Self {
             crate::api::PointerEvent::Wheel { position, delta } => MouseEvent::Wheel {
                 position: position.to_untyped().cast(),
                 delta: delta.to_untyped().cast().to_point(),
+                // The api event has no modifiers, `Window::dispatch_pointer_event` sets them
+                modifiers: Default::default(),
             },
             crate::api::PointerEvent::Exit => MouseEvent::Exit,
         }
     }
 }
 
+/// The phase of a touch point reported by the backend, see [`MultiTouchState::touch_event`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    /// A finger touched the screen
+    Started,
+    /// The finger moved
+    Moved,
+    /// The finger was lifted from the screen
+    Ended,
+    /// The system cancelled the tracking of the finger
+    Cancelled,
+}
+
+/// The state which a window should hold for the touch points of a touch screen
+///
+/// A single touch point behaves like the left mouse button. When a second finger touches the
+/// screen, the press of the first one is cancelled with a [`MouseEvent::Exit`], and the moves of
+/// the two fingers are reported as [`MouseEvent::Pinch`] until all the fingers are lifted.
+/// Fingers beyond the second one are ignored.
+#[derive(Default)]
+pub struct MultiTouchState {
+    /// The identifier and last position of the fingers on the screen, in the order they touched it
+    points: Vec<(u64, Point)>,
+    /// true from the moment a second finger touches the screen until all the fingers are lifted
+    pinching: bool,
+}
+
+impl MultiTouchState {
+    /// Records that the touch point `id` reached `position` with the given `phase`, and returns
+    /// the event to dispatch to the items, if any.
+    pub fn touch_event(
+        &mut self,
+        id: u64,
+        position: Point,
+        phase: TouchPhase,
+    ) -> Option<MouseEvent> {
+        let index = self.points.iter().position(|(point_id, _)| *point_id == id);
+        match (phase, index) {
+            (TouchPhase::Started, None) => {
+                self.points.push((id, position));
+                if self.points.len() == 1 && !self.pinching {
+                    Some(MouseEvent::Pressed { position, button: PointerEventButton::Left })
+                } else if self.points.len() == 2 {
+                    self.pinching = true;
+                    // Cancel the press of the first finger
+                    Some(MouseEvent::Exit)
+                } else {
+                    None
+                }
+            }
+            (TouchPhase::Moved, Some(index)) => {
+                let (first, second) = self.pinch_points();
+                self.points[index].1 = position;
+                if !self.pinching {
+                    return Some(MouseEvent::Moved { position });
+                }
+                // Only the first two fingers pinch, and a finger left alone doesn't do anything
+                if index > 1 || self.points.len() < 2 {
+                    return None;
+                }
+                let (new_first, new_second) = self.pinch_points();
+                let (old_line, new_line) = (second - first, new_second - new_first);
+                let (old_length, new_length) = (line_length(old_line), line_length(new_line));
+                let rotation = (line_angle(new_line) - line_angle(old_line) + 540.) % 360. - 180.;
+                Some(MouseEvent::Pinch {
+                    center: ((new_first.to_vector() + new_second.to_vector()) / 2 as Coord)
+                        .to_point(),
+                    scale: if old_length > 0. { new_length / old_length } else { 1. },
+                    rotation,
+                })
+            }
+            (TouchPhase::Ended | TouchPhase::Cancelled, Some(index)) => {
+                self.points.remove(index);
+                if !self.pinching {
+                    return Some(MouseEvent::Released {
+                        position,
+                        button: PointerEventButton::Left,
+                    });
+                }
+                if self.points.is_empty() {
+                    self.pinching = false;
+                }
+                None
+            }
+            // A touch point that started twice or that was never started
+            _ => None,
+        }
+    }
+
+    /// Returns the position of the first two fingers, or twice the position of the first one if
+    /// there is only one.
+    fn pinch_points(&self) -> (Point, Point) {
+        let first = self.points.first().map_or_else(Point::default, |(_, position)| *position);
+        let second = self.points.get(1).map_or(first, |(_, position)| *position);
+        (first, second)
+    }
+}
+
+fn line_length(line: Vector2D<Coord>) -> f32 {
+    (line.x as f32).hypot(line.y as f32)
+}
+
+/// The angle of the line with the x axis, in degrees, clockwise as the y axis goes down
+fn line_angle(line: Vector2D<Coord>) -> f32 {
+    (line.y as f32).atan2(line.x as f32).to_degrees()
+}
+
 /// This value is returned by the `input_event` function of an Item
 /// to notify the run-time about how the event was handled and
 /// what the next steps are.
@@ -102,6 +235,14 @@ pub enum InputEventResult {
     EventIgnored,
     /// All further mouse event need to be sent to this item or component
     GrabMouse,
+    /// The event was ignored and continues to propagate to the items below, but handling it
+    /// changed the visual state of the item (for example a hover highlight), so the window
+    /// must be repainted.
+    ///
+    /// The event processing requests the repaint through
+    /// [`crate::window::PlatformWindow::request_redraw`], the backend then schedules it as
+    /// for any other change.
+    EventIgnoredNeedsRepaint,
 }
 
 impl Default for InputEventResult {
@@ -201,119 +342,248 @@ pub struct KeyEvent {
 
 impl KeyEvent {
     /// If a shortcut was pressed, this function returns `Some(StandardShortcut)`.
-    /// Otherwise it returns None.
+    /// Otherwise it returns None. The shortcuts are looked up in the [`shortcut_map()`].
     pub fn shortcut(&self) -> Option<StandardShortcut> {
-        if self.modifiers.control && !self.modifiers.shift {
-            match self.text.as_str() {
-                "c" => Some(StandardShortcut::Copy),
-                "x" => Some(StandardShortcut::Cut),
-                "v" => Some(StandardShortcut::Paste),
-                "a" => Some(StandardShortcut::SelectAll),
-                "f" => Some(StandardShortcut::Find),
-                "s" => Some(StandardShortcut::Save),
-                "p" => Some(StandardShortcut::Print),
-                "z" => Some(StandardShortcut::Undo),
-                #[cfg(target_os = "windows")]
-                "y" => Some(StandardShortcut::Redo),
-                "r" => Some(StandardShortcut::Refresh),
-                _ => None,
-            }
-        } else if self.modifiers.control && self.modifiers.shift {
-            match self.text.as_str() {
-                #[cfg(not(target_os = "windows"))]
-                "z" => Some(StandardShortcut::Redo),
-                _ => None,
-            }
-        } else {
-            None
-        }
+        SHORTCUT_MAP.with(|map| map.borrow().standard_shortcut(self))
     }
 
     /// If a shortcut concerning text editing was pressed, this function
-    /// returns `Some(TextShortcut)`. Otherwise it returns None.
+    /// returns `Some(TextShortcut)`. Otherwise it returns None. The shortcuts are looked up in
+    /// the [`shortcut_map()`].
     pub fn text_shortcut(&self) -> Option<TextShortcut> {
-        let keycode = self.text.chars().next()?;
+        SHORTCUT_MAP.with(|map| map.borrow().text_shortcut(self))
+    }
+}
 
-        let move_mod = if cfg!(target_os = "macos") {
-            self.modifiers.alt && !self.modifiers.control && !self.modifiers.meta
-        } else {
-            self.modifiers.control && !self.modifiers.alt && !self.modifiers.meta
+/// Represents an event of the input method of the windowing system, which composes text before
+/// it is inserted in the focused `TextInput`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputMethodEvent {
+    /// The text being composed changed. `cursor` is the byte offset of the cursor within `text`, or
+    /// None to put it at the end. An empty `text` ends the composition without inserting anything.
+    Preedit { text: SharedString, cursor: Option<usize> },
+    /// The composition ends with `text`, which is inserted instead of the text being composed.
+    Commit(SharedString),
+}
+
+/// A key combination of a [`ShortcutMap`]: the text of a key, as in [`KeyEvent::text`], with the
+/// modifiers that must be held.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeyCombination {
+    /// The text of the key, for example `"c"` or [`key_codes::LeftArrow`]
+    pub text: SharedString,
+    /// The modifiers that must be held, and no other one
+    pub modifiers: KeyboardModifiers,
+}
+
+impl KeyCombination {
+    /// Creates the combination of the key `text` with the `modifiers`
+    pub fn new(text: impl Into<SharedString>, modifiers: KeyboardModifiers) -> Self {
+        Self { text: text.into(), modifiers }
+    }
+
+    /// Returns true if the `event` is made with this combination. Shift is not compared if
+    /// `ignore_shift` is true.
+    fn matches(&self, event: &KeyEvent, ignore_shift: bool) -> bool {
+        let modifiers = KeyboardModifiers {
+            shift: if ignore_shift { self.modifiers.shift } else { event.modifiers.shift },
+            ..event.modifiers
         };
+        modifiers == self.modifiers && self.matches_key(event)
+    }
 
-        if move_mod {
-            match keycode {
-                key_codes::LeftArrow => {
-                    return Some(TextShortcut::Move(TextCursorDirection::BackwardByWord))
-                }
-                key_codes::RightArrow => {
-                    return Some(TextShortcut::Move(TextCursorDirection::ForwardByWord))
-                }
-                key_codes::UpArrow => {
-                    return Some(TextShortcut::Move(TextCursorDirection::StartOfParagraph))
-                }
-                key_codes::DownArrow => {
-                    return Some(TextShortcut::Move(TextCursorDirection::EndOfParagraph))
-                }
-                key_codes::Backspace => {
-                    return Some(TextShortcut::DeleteWordBackward);
-                }
-                key_codes::Delete => {
-                    return Some(TextShortcut::DeleteWordForward);
-                }
-                _ => (),
-            };
+    /// Returns true if the `event` is made with the key of this combination, whatever the
+    /// modifiers are.
+    fn matches_key(&self, event: &KeyEvent) -> bool {
+        if self.text == event.text {
+            return true;
         }
-
-        #[cfg(not(target_os = "macos"))]
-        {
-            if self.modifiers.control && !self.modifiers.alt && !self.modifiers.meta {
-                match keycode {
-                    key_codes::Home => {
-                        return Some(TextShortcut::Move(TextCursorDirection::StartOfText))
-                    }
-                    key_codes::End => {
-                        return Some(TextShortcut::Move(TextCursorDirection::EndOfText))
-                    }
-                    _ => (),
-                };
+        match event.text.as_bytes() {
+            // Some platforms report the control character that the key produces with Control, for
+            // example "\u{1}" for Ctrl+A, rather than the letter. The special keys that have one of
+            // these codes, like Backspace, are not letters.
+            [code @ 1..=26] if event.modifiers.control => {
+                !matches!(
+                    char::from(*code),
+                    key_codes::Backspace | key_codes::Tab | key_codes::Return | key_codes::Backtab
+                ) && self.text.as_bytes() == [b'a' + code - 1]
             }
+            _ => false,
         }
+    }
+}
 
-        #[cfg(target_os = "macos")]
-        {
-            if self.modifiers.control {
-                match keycode {
-                    key_codes::LeftArrow => {
-                        return Some(TextShortcut::Move(TextCursorDirection::StartOfLine))
-                    }
-                    key_codes::RightArrow => {
-                        return Some(TextShortcut::Move(TextCursorDirection::EndOfLine))
-                    }
-                    key_codes::UpArrow => {
-                        return Some(TextShortcut::Move(TextCursorDirection::StartOfText))
-                    }
-                    key_codes::DownArrow => {
-                        return Some(TextShortcut::Move(TextCursorDirection::EndOfText))
-                    }
-                    _ => (),
-                };
-            }
+/// The key combinations of the [`StandardShortcut`]s and the [`TextShortcut`]s.
+///
+/// The map in use is returned by [`shortcut_map()`] and replaced with [`set_shortcut_map()`],
+/// for example to use Emacs key bindings, or combinations that are easier to type with the
+/// keyboard layout of a language:
+///
+/// ```
+/// # use i_slint_core::platform::*;
+/// let control = KeyboardModifiers { control: true, ..Default::default() };
+/// let mut map = shortcut_map();
+/// map.bind_text_shortcut(
+///     KeyCombination::new("f", control),
+///     TextShortcut::Move(TextCursorDirection::Forward),
+/// );
+/// map.unbind(&KeyCombination::new("r", control));
+/// set_shortcut_map(map);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutMap {
+    standard_shortcuts: Vec<(KeyCombination, StandardShortcut)>,
+    text_shortcuts: Vec<(KeyCombination, TextShortcut)>,
+}
+
+impl ShortcutMap {
+    /// Returns the map of the usual shortcuts of the platform, which is in use until
+    /// [`set_shortcut_map()`] is called.
+    pub fn platform_default() -> Self {
+        use TextCursorDirection::*;
+        let none = KeyboardModifiers::default();
+        let control = KeyboardModifiers { control: true, ..none };
+        let control_shift = KeyboardModifiers { shift: true, ..control };
+        let mut map = Self::default();
+
+        for (text, shortcut) in [
+            ("c", StandardShortcut::Copy),
+            ("x", StandardShortcut::Cut),
+            ("v", StandardShortcut::Paste),
+            ("a", StandardShortcut::SelectAll),
+            ("f", StandardShortcut::Find),
+            ("s", StandardShortcut::Save),
+            ("p", StandardShortcut::Print),
+            ("z", StandardShortcut::Undo),
+            ("r", StandardShortcut::Refresh),
+        ] {
+            map.bind_standard_shortcut(KeyCombination::new(text, control), shortcut);
+        }
+        if cfg!(target_os = "windows") {
+            map.bind_standard_shortcut(KeyCombination::new("y", control), StandardShortcut::Redo);
+        } else {
+            map.bind_standard_shortcut(
+                KeyCombination::new("z", control_shift),
+                StandardShortcut::Redo,
+            );
         }
 
-        match TextCursorDirection::try_from(keycode) {
-            Ok(direction) => return Some(TextShortcut::Move(direction)),
-            _ => (),
+        // On macOS, Alt moves by words and Control to the ends of the lines
+        let word_modifiers = if cfg!(target_os = "macos") {
+            KeyboardModifiers { alt: true, ..none }
+        } else {
+            control
         };
-
-        match keycode {
-            key_codes::Backspace => Some(TextShortcut::DeleteBackward),
-            key_codes::Delete => Some(TextShortcut::DeleteForward),
-            _ => None,
+        let mut text_shortcuts = alloc::vec![
+            (key_codes::LeftArrow, word_modifiers, TextShortcut::Move(BackwardByWord)),
+            (key_codes::RightArrow, word_modifiers, TextShortcut::Move(ForwardByWord)),
+            (key_codes::UpArrow, word_modifiers, TextShortcut::Move(StartOfParagraph)),
+            (key_codes::DownArrow, word_modifiers, TextShortcut::Move(EndOfParagraph)),
+            (key_codes::Backspace, word_modifiers, TextShortcut::DeleteWordBackward),
+            (key_codes::Delete, word_modifiers, TextShortcut::DeleteWordForward),
+            (key_codes::LeftArrow, none, TextShortcut::Move(Backward)),
+            (key_codes::RightArrow, none, TextShortcut::Move(Forward)),
+            (key_codes::UpArrow, none, TextShortcut::Move(PreviousLine)),
+            (key_codes::DownArrow, none, TextShortcut::Move(NextLine)),
+            (key_codes::Backspace, none, TextShortcut::DeleteBackward),
+            (key_codes::Delete, none, TextShortcut::DeleteForward),
+        ];
+        if cfg!(target_os = "macos") {
+            // Home and End scroll to the top or the bottom of the page
+            text_shortcuts.extend([
+                (key_codes::LeftArrow, control, TextShortcut::Move(StartOfLine)),
+                (key_codes::RightArrow, control, TextShortcut::Move(EndOfLine)),
+                (key_codes::UpArrow, control, TextShortcut::Move(StartOfText)),
+                (key_codes::DownArrow, control, TextShortcut::Move(EndOfText)),
+            ]);
+        } else {
+            text_shortcuts.extend([
+                (key_codes::Home, control, TextShortcut::Move(StartOfText)),
+                (key_codes::End, control, TextShortcut::Move(EndOfText)),
+                (key_codes::Home, none, TextShortcut::Move(StartOfLine)),
+                (key_codes::End, none, TextShortcut::Move(EndOfLine)),
+            ]);
         }
+        for (key_code, modifiers, shortcut) in text_shortcuts {
+            let mut text = [0; 4];
+            map.bind_text_shortcut(
+                KeyCombination::new(&*key_code.encode_utf8(&mut text), modifiers),
+                shortcut,
+            );
+        }
+        map
+    }
+
+    /// Makes the `key` combination trigger the standard `shortcut`, instead of the standard
+    /// shortcut it triggered before, if any. A shortcut can be triggered by several combinations.
+    pub fn bind_standard_shortcut(&mut self, key: KeyCombination, shortcut: StandardShortcut) {
+        self.standard_shortcuts.retain(|(bound_key, _)| *bound_key != key);
+        self.standard_shortcuts.push((key, shortcut));
+    }
+
+    /// Makes the `key` combination trigger the text editing `shortcut`, instead of the text
+    /// editing shortcut it triggered before, if any. The shift modifier of `key` is ignored: it
+    /// is always allowed, and extends the selection when moving the cursor.
+    ///
+    /// A key bound without modifiers also triggers its shortcut when it is pressed with modifiers
+    /// that are bound to no other text editing shortcut, so that for example Alt+Left moves the
+    /// cursor like Left.
+    pub fn bind_text_shortcut(&mut self, mut key: KeyCombination, shortcut: TextShortcut) {
+        key.modifiers.shift = false;
+        self.text_shortcuts.retain(|(bound_key, _)| *bound_key != key);
+        self.text_shortcuts.push((key, shortcut));
+    }
+
+    /// Makes the `key` combination trigger no shortcut anymore.
+    pub fn unbind(&mut self, key: &KeyCombination) {
+        self.standard_shortcuts.retain(|(bound_key, _)| bound_key != key);
+        let text_key = KeyCombination {
+            modifiers: KeyboardModifiers { shift: false, ..key.modifiers },
+            ..key.clone()
+        };
+        self.text_shortcuts.retain(|(bound_key, _)| *bound_key != text_key);
+    }
+
+    /// Returns the standard shortcut that the `event` triggers, if any
+    pub fn standard_shortcut(&self, event: &KeyEvent) -> Option<StandardShortcut> {
+        self.standard_shortcuts
+            .iter()
+            .find_map(|(key, shortcut)| key.matches(event, false).then(|| *shortcut))
+    }
+
+    /// Returns the text editing shortcut that the `event` triggers, if any
+    pub fn text_shortcut(&self, event: &KeyEvent) -> Option<TextShortcut> {
+        self.text_shortcuts
+            .iter()
+            .find_map(|(key, shortcut)| key.matches(event, true).then(|| *shortcut))
+            .or_else(|| {
+                self.text_shortcuts.iter().find_map(|(key, shortcut)| {
+                    (key.modifiers == KeyboardModifiers::default() && key.matches_key(event))
+                        .then(|| *shortcut)
+                })
+            })
     }
 }
 
+use crate::unsafe_single_threaded::thread_local;
+
+thread_local!(static SHORTCUT_MAP: core::cell::RefCell<ShortcutMap> =
+    core::cell::RefCell::new(ShortcutMap::platform_default()));
+
+/// Returns a copy of the map of the shortcuts that [`KeyEvent::shortcut()`] and
+/// [`KeyEvent::text_shortcut()`] look up, for example to change it and pass it to
+/// [`set_shortcut_map()`].
+pub fn shortcut_map() -> ShortcutMap {
+    SHORTCUT_MAP.with(|map| map.borrow().clone())
+}
+
+/// Replaces the map of the shortcuts of the current thread, see [`ShortcutMap`].
+pub fn set_shortcut_map(map: ShortcutMap) {
+    SHORTCUT_MAP.with(|m| *m.borrow_mut() = map);
+}
+
 /// Represents a non context specific shortcut.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum StandardShortcut {
     /// Copy Something
     Copy,
@@ -338,6 +608,7 @@ pub enum StandardShortcut {
 }
 
 /// Shortcuts that are used when editing text
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TextShortcut {
     /// Move the cursor
     Move(TextCursorDirection),
@@ -371,6 +642,9 @@ pub enum FocusEventResult {
     FocusAccepted,
     /// The event was not handled and should be sent to other items.
     FocusIgnored,
+    /// The item refuses to lose the focus. Only returned for [`FocusEvent::FocusOut`], in which
+    /// case the item keeps the focus.
+    FocusOutRejected,
 }
 
 /// This event is sent to a component and items when they receive or loose
@@ -396,6 +670,38 @@ pub struct MouseInputState {
     item_stack: Vec<(ItemWeak, InputEventFilterResult)>,
     /// true if the top item of the stack has the mouse grab
     grabbed: bool,
+    /// The items that received `MouseEvent::Enter`, and no `MouseEvent::Exit` since then
+    hovered: Vec<ItemWeak>,
+}
+
+impl MouseInputState {
+    /// Returns the items that contain the mouse cursor, from the outermost to the innermost.
+    /// When the mouse is grabbed, the last item is the one that has the grab, and the other ones are
+    /// the items on the way to it that may still intercept the events. Items that were destroyed
+    /// are not returned.
+    pub fn item_stack(&self) -> Vec<ItemRc> {
+        self.item_stack.iter().map_while(|(item, _)| item.upgrade()).collect()
+    }
+
+    /// Returns true if the last item of the [`Self::item_stack`] has the mouse grab.
+    pub fn is_grabbed(&self) -> bool {
+        self.grabbed && !self.item_stack.is_empty()
+    }
+
+    /// Gives the mouse grab to `grabber`, as if it returned [`InputEventResult::GrabMouse`] from the
+    /// last mouse event. Its parents may intercept the following events.
+    #[cfg(feature = "testing")]
+    pub fn set_grab(&mut self, grabber: &ItemRc) {
+        let mut item_stack = Vec::new();
+        let mut item = Some(grabber.clone());
+        while let Some(it) = item {
+            item = it.parent_item();
+            item_stack.push((it.downgrade(), InputEventFilterResult::ForwardAndInterceptGrab));
+        }
+        item_stack.reverse();
+        self.item_stack = item_stack;
+        self.grabbed = true;
+    }
 }
 
 /// Try to handle the mouse grabber. Return true if the event has handled, or false otherwise
@@ -446,6 +752,9 @@ fn handle_mouse_grab(
 
     let grabber = mouse_input_state.item_stack.last().unwrap().0.upgrade().unwrap();
     let input_result = grabber.borrow().as_ref().input_event(event, platform_window, &grabber);
+    if input_result == InputEventResult::EventIgnoredNeedsRepaint {
+        platform_window.request_redraw();
+    }
     if input_result != InputEventResult::GrabMouse {
         mouse_input_state.grabbed = false;
         send_exit_events(mouse_input_state, mouse_event.position(), platform_window);
@@ -454,8 +763,8 @@ fn handle_mouse_grab(
     true
 }
 
-fn send_exit_events(
-    mouse_input_state: &MouseInputState,
+pub(crate) fn send_exit_events(
+    mouse_input_state: &mut MouseInputState,
     mut pos: Option<Point>,
     platform_window: &Rc<dyn PlatformWindow>,
 ) {
@@ -468,6 +777,107 @@ fn send_exit_events(
         }
         if !contains {
             item.borrow().as_ref().input_event(MouseEvent::Exit, platform_window, &item);
+            mouse_input_state.hovered.retain(|hovered| *hovered != it.0);
+        }
+    }
+}
+
+/// Sends `MouseEvent::Exit` to the items that were hovered in the `old_state` and are not in the
+/// `new_state` anymore, and then `MouseEvent::Enter` to the items of the `new_state` that contain
+/// the mouse at `pos` and were not hovered, from the outermost to the innermost. Records the
+/// hovered items in the `new_state`.
+fn send_enter_events(
+    old_state: &MouseInputState,
+    new_state: &mut MouseInputState,
+    pos: Option<Point>,
+    platform_window: &Rc<dyn PlatformWindow>,
+) {
+    let mut hovered = Vec::new();
+    let mut entered = Vec::new();
+    if let Some(mut pos) = pos {
+        for (item, _) in new_state.item_stack.iter() {
+            let item_rc = if let Some(item_rc) = item.upgrade() { item_rc } else { break };
+            let g = item_rc.geometry();
+            let contains = g.contains(pos);
+            pos -= g.origin.to_vector();
+            if contains {
+                if !old_state.hovered.contains(item) {
+                    entered.push((item_rc, pos));
+                }
+                hovered.push(item.clone());
+            }
+        }
+    }
+    for item in old_state.hovered.iter().filter(|item| !hovered.contains(item)) {
+        if let Some(item_rc) = item.upgrade() {
+            item_rc.borrow().as_ref().input_event(MouseEvent::Exit, platform_window, &item_rc);
+        }
+    }
+    for (item_rc, position) in entered {
+        let input_result = item_rc.borrow().as_ref().input_event(
+            MouseEvent::Enter { position },
+            platform_window,
+            &item_rc,
+        );
+        if input_result == InputEventResult::EventIgnoredNeedsRepaint {
+            platform_window.request_redraw();
+        }
+    }
+    new_state.hovered = hovered;
+}
+
+/// How the input mask set with [`WindowInner::set_input_mask`](crate::window::WindowInner::set_input_mask)
+/// applies to an item, and to its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InputMaskState {
+    /// The item receives the input: there is no mask, or the item is in the subtree of the mask
+    /// or in an open popup.
+    Unmasked,
+    /// The item is an ancestor of the mask. It still filters and clips the events on their way to
+    /// the mask, but only receives them itself when it intercepts them.
+    Ancestor,
+    /// Neither the item nor its children receive the input.
+    Masked,
+}
+
+/// The items that the input mask lets through, computed once per event, see
+/// [`WindowInner::input_mask_filter`](crate::window::WindowInner::input_mask_filter).
+pub(crate) struct InputMaskFilter {
+    /// The mask item followed by its ancestors, or None without mask.
+    pub(crate) mask_path: Option<Vec<ItemRc>>,
+    /// The root item of the open popup, which receives the input regardless of the mask.
+    pub(crate) popup_root: Option<ItemRc>,
+}
+
+impl InputMaskFilter {
+    /// The state of the root item of the tree, before knowing which item it is.
+    pub(crate) fn initial_state(&self) -> InputMaskState {
+        if self.mask_path.is_some() {
+            InputMaskState::Ancestor
+        } else {
+            InputMaskState::Unmasked
+        }
+    }
+
+    /// Returns true if `item` is the mask or the root of the open popup, so that its whole
+    /// subtree receives the input.
+    pub(crate) fn is_unmasked_root(&self, item: &ItemRc) -> bool {
+        self.mask_path.as_ref().map_or(true, |path| path[0] == *item)
+            || self.popup_root.as_ref() == Some(item)
+    }
+
+    /// Returns the state of `item`, given the state of its parent. Only the children of the
+    /// ancestors of the mask are compared with these ancestors.
+    pub(crate) fn state(&self, item: &ItemRc, parent_state: InputMaskState) -> InputMaskState {
+        match parent_state {
+            InputMaskState::Unmasked => InputMaskState::Unmasked,
+            _ if self.is_unmasked_root(item) => InputMaskState::Unmasked,
+            InputMaskState::Ancestor
+                if self.mask_path.as_ref().map_or(false, |path| path.contains(item)) =>
+            {
+                InputMaskState::Ancestor
+            }
+            _ => InputMaskState::Masked,
         }
     }
 }
@@ -481,20 +891,28 @@ pub fn process_mouse_input(
     platform_window: &Rc<dyn PlatformWindow>,
     mut mouse_input_state: MouseInputState,
 ) -> MouseInputState {
+    let window = platform_window.window().window_handle();
+    if let MouseEvent::Pressed { position, button } = mouse_event {
+        window.register_press(position, button);
+    }
+
     if handle_mouse_grab(&mouse_event, platform_window, &mut mouse_input_state) {
         return mouse_input_state;
     }
 
     let mut result = MouseInputState::default();
-    type State = (Vector2D<Coord>, Vec<(ItemWeak, InputEventFilterResult)>, MouseEvent);
+    let input_mask_filter = window.input_mask_filter();
+    type State =
+        (Vector2D<Coord>, Vec<(ItemWeak, InputEventFilterResult)>, MouseEvent, InputMaskState);
     crate::item_tree::visit_items_with_post_visit(
         &component,
         crate::item_tree::TraversalOrder::FrontToBack,
         |comp_rc: &ComponentRc,
          item: core::pin::Pin<ItemRef>,
          item_index: usize,
-         (offset, mouse_grabber_stack, mouse_event): &State| {
+         (offset, mouse_grabber_stack, mouse_event, mask_state): &State| {
             let item_rc = ItemRc::new(comp_rc.clone(), item_index);
+            let mask_state = input_mask_filter.state(&item_rc, *mask_state);
 
             let mut mouse_event = *mouse_event;
 
@@ -503,8 +921,12 @@ pub fn process_mouse_input(
 
             let mut mouse_grabber_stack = mouse_grabber_stack.clone();
 
-            let post_visit_state = if mouse_event.position().map_or(false, |p| geom.contains(p))
-                || crate::item_rendering::is_clipping_item(item)
+            // The items outside of the input mask are still visited, to reach the items inside it
+            let post_visit_state = if mask_state != InputMaskState::Masked
+                && (mouse_event
+                    .position()
+                    .map_or(false, |p| window.hit_test_rect(geom).contains(p))
+                    || crate::item_rendering::is_clipping_item(item))
             {
                 let mut event2 = mouse_event;
                 event2.translate(-geom.origin.to_vector());
@@ -514,7 +936,7 @@ pub fn process_mouse_input(
                     &item_rc,
                 );
                 mouse_grabber_stack.push((item_rc.downgrade(), filter_result));
-                match filter_result {
+                let post_visit_state = match filter_result {
                     InputEventFilterResult::ForwardAndIgnore => None,
                     InputEventFilterResult::ForwardEvent => {
                         Some((event2, mouse_grabber_stack.clone(), item_rc, false))
@@ -533,7 +955,11 @@ pub fn process_mouse_input(
                             Some((event2, mouse_grabber_stack, item_rc, true)),
                         )
                     }
-                }
+                };
+                // The ancestors of the mask only receive the events that they intercept
+                post_visit_state.filter(|(_, _, _, intercept)| {
+                    *intercept || mask_state == InputMaskState::Unmasked
+                })
             } else {
                 mouse_grabber_stack
                     .push((item_rc.downgrade(), InputEventFilterResult::ForwardAndIgnore));
@@ -545,6 +971,7 @@ pub fn process_mouse_input(
                     geom.origin.to_vector(),
                     mouse_grabber_stack,
                     mouse_event,
+                    mask_state,
                 )),
                 post_visit_state,
             )
@@ -568,6 +995,10 @@ pub fn process_mouse_input(
                     InputEventResult::EventIgnored => {
                         return VisitChildrenResult::CONTINUE;
                     }
+                    InputEventResult::EventIgnoredNeedsRepaint => {
+                        platform_window.request_redraw();
+                        return VisitChildrenResult::CONTINUE;
+                    }
                     InputEventResult::GrabMouse => {
                         result.item_stack = mouse_grabber_stack;
                         result.item_stack.last_mut().unwrap().1 =
@@ -579,10 +1010,16 @@ pub fn process_mouse_input(
             }
             r
         },
-        (Vector2D::new(0 as Coord, 0 as Coord), Vec::new(), mouse_event),
+        (
+            Vector2D::new(0 as Coord, 0 as Coord),
+            Vec::new(),
+            mouse_event,
+            input_mask_filter.initial_state(),
+        ),
     );
 
-    send_exit_events(&mouse_input_state, mouse_event.position(), platform_window);
+    send_exit_events(&mut mouse_input_state, mouse_event.position(), platform_window);
+    send_enter_events(&mouse_input_state, &mut result, mouse_event.position(), platform_window);
 
     result
 }
@@ -613,12 +1050,24 @@ pub fn new() -> Pin<Rc<Self>> {
 
     /// Sets a binding on the provided property that will ensure that the property value
     /// is true when the cursor should be shown and false if not.
-    pub fn set_binding(instance: Pin<Rc<TextCursorBlinker>>, prop: &Property<bool>) {
+    ///
+    /// The cursor is hidden while `enabled` returns false. The properties read by `enabled`
+    /// are tracked, so the cursor is shown again when they change and it returns true.
+    pub fn set_binding(
+        instance: Pin<Rc<TextCursorBlinker>>,
+        prop: &Property<bool>,
+        enabled: impl Fn() -> bool + 'static,
+    ) {
         instance.as_ref().cursor_visible.set(true);
         // Re-start timer, in case.
         Self::start(&instance);
         prop.set_binding(move || {
-            TextCursorBlinker::FIELD_OFFSETS.cursor_visible.apply_pin(instance.as_ref()).get()
+            // While disabled, the binding doesn't depend on the blinking
+            enabled()
+                && TextCursorBlinker::FIELD_OFFSETS
+                    .cursor_visible
+                    .apply_pin(instance.as_ref())
+                    .get()
         });
     }
 
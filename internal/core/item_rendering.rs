@@ -268,6 +268,9 @@ pub trait ItemRenderer {
     fn draw_clipped_image(&mut self, image: Pin<&ClippedImage>, _self_rc: &ItemRc);
     fn draw_text(&mut self, text: Pin<&Text>, _self_rc: &ItemRc);
     fn draw_text_input(&mut self, text_input: Pin<&TextInput>, _self_rc: &ItemRc);
+    /// Draw the `placeholder_text` of the text input with the `placeholder_color`, instead of
+    /// the text. Called instead of draw_text_input when [`TextInput::placeholder_visible`].
+    fn draw_text_input_placeholder(&mut self, text_input: Pin<&TextInput>, _self_rc: &ItemRc);
     #[cfg(feature = "std")]
     fn draw_path(&mut self, path: Pin<&Path>, _self_rc: &ItemRc);
     fn draw_box_shadow(&mut self, box_shadow: Pin<&BoxShadow>, _self_rc: &ItemRc);
@@ -499,6 +502,7 @@ fn filter_item(&mut self, item: Pin<ItemRef>) -> (bool, Rect) {
     forward_rendering_call!(fn draw_clipped_image(ClippedImage));
     forward_rendering_call!(fn draw_text(Text));
     forward_rendering_call!(fn draw_text_input(TextInput));
+    forward_rendering_call!(fn draw_text_input_placeholder(TextInput));
     #[cfg(feature = "std")]
     forward_rendering_call!(fn draw_path(Path));
     forward_rendering_call!(fn draw_box_shadow(BoxShadow));
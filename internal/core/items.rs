@@ -20,7 +20,7 @@
 #![allow(non_upper_case_globals)]
 #![allow(missing_docs)] // because documenting each property of items is redundant
 
-use crate::graphics::{Brush, Color, Point, Rect};
+use crate::graphics::{Brush, Color, FontRequest, Point, Rect};
 use crate::input::{
     FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
     KeyEventResult, KeyEventType, MouseEvent,
@@ -58,7 +58,9 @@
 pub type VoidArg = ();
 pub type KeyEventArg = (KeyEvent,);
 type PointerEventArg = (PointerEvent,);
+type PinchEventArg = (PinchEvent,);
 type PointArg = (Point,);
+type StringArg = (SharedString,);
 
 #[cfg(all(feature = "ffi", windows))]
 #[macro_export]
@@ -361,11 +363,15 @@ pub struct TouchArea {
     pub mouse_cursor: Property<MouseCursor>,
     pub clicked: Callback<VoidArg>,
     pub moved: Callback<VoidArg>,
+    pub long_pressed: Callback<VoidArg>,
     pub pointer_event: Callback<PointerEventArg>,
+    pub pinched: Callback<PinchEventArg>,
     /// FIXME: remove this
     pub cached_rendering_data: CachedRenderingData,
     /// true when we are currently grabbing the mouse
     grabbed: Cell<bool>,
+    /// true when the current press was handled as a long press, so its release doesn't click
+    long_press_handled: Cell<bool>,
 }
 
 impl Item for TouchArea {
@@ -399,7 +405,7 @@ fn input_event_filter_before_children(
         let hovering = !matches!(event, MouseEvent::Exit);
         Self::FIELD_OFFSETS.has_hover.apply_pin(self).set(hovering);
         if hovering {
-            platform_window.set_mouse_cursor(self.mouse_cursor());
+            platform_window.window().window_handle().set_mouse_cursor(self.mouse_cursor());
         }
         InputEventFilterResult::ForwardAndInterceptGrab
     }
@@ -412,14 +418,18 @@ fn input_event(
     ) -> InputEventResult {
         if matches!(event, MouseEvent::Exit) {
             Self::FIELD_OFFSETS.has_hover.apply_pin(self).set(false);
-            platform_window.set_mouse_cursor(MouseCursor::Default);
+            platform_window.window().window_handle().set_mouse_cursor(MouseCursor::Default);
         }
         if !self.enabled() {
             return InputEventResult::EventIgnored;
         }
         let result = if let MouseEvent::Released { position, button } = event {
             if button == PointerEventButton::Left
-                && euclid::rect(0 as Coord, 0 as Coord, self.width(), self.height())
+                && !self.long_press_handled.replace(false)
+                && platform_window
+                    .window()
+                    .window_handle()
+                    .hit_test_rect(euclid::rect(0 as Coord, 0 as Coord, self.width(), self.height()))
                     .contains(position)
             {
                 Self::FIELD_OFFSETS.clicked.apply_pin(self).call(&());
@@ -433,6 +443,7 @@ fn input_event(
             MouseEvent::Pressed { position, button } => {
                 self.grabbed.set(true);
                 if button == PointerEventButton::Left {
+                    self.long_press_handled.set(false);
                     Self::FIELD_OFFSETS.pressed_x.apply_pin(self).set(position.x);
                     Self::FIELD_OFFSETS.pressed_y.apply_pin(self).set(position.y);
                     Self::FIELD_OFFSETS.pressed.apply_pin(self).set(true);
@@ -442,6 +453,28 @@ fn input_event(
                     .apply_pin(self)
                     .call(&(PointerEvent { button, kind: PointerEventKind::Down },));
             }
+            MouseEvent::Enter { .. } => {
+                Self::FIELD_OFFSETS.has_hover.apply_pin(self).set(true);
+                return InputEventResult::EventIgnoredNeedsRepaint;
+            }
+            MouseEvent::LongPress { .. } => {
+                if !self.pressed() {
+                    return InputEventResult::EventIgnored;
+                }
+                // Like in other toolkits, the release only clicks if nobody handles the long press
+                let long_pressed = Self::FIELD_OFFSETS.long_pressed.apply_pin(self);
+                if long_pressed.has_handler() {
+                    self.long_press_handled.set(true);
+                    long_pressed.call(&());
+                }
+            }
+            MouseEvent::Pinch { scale, rotation, .. } => {
+                Self::FIELD_OFFSETS
+                    .pinched
+                    .apply_pin(self)
+                    .call(&(PinchEvent { scale, rotation },));
+                return InputEventResult::EventAccepted;
+            }
             MouseEvent::Exit => {
                 Self::FIELD_OFFSETS.pressed.apply_pin(self).set(false);
                 if self.grabbed.replace(false) {
@@ -527,6 +560,7 @@ pub struct FocusScope {
     pub height: Property<Coord>,
     pub enabled: Property<bool>,
     pub has_focus: Property<bool>,
+    pub keep_focus: Property<bool>,
     pub key_pressed: Callback<KeyEventArg, EventResult>,
     pub key_released: Callback<KeyEventArg, EventResult>,
     /// FIXME: remove this
@@ -591,7 +625,7 @@ fn key_event(
     fn focus_event(
         self: Pin<&Self>,
         event: &FocusEvent,
-        _platform_window: &Rc<dyn PlatformWindow>,
+        platform_window: &Rc<dyn PlatformWindow>,
     ) -> FocusEventResult {
         if !self.enabled() {
             return FocusEventResult::FocusIgnored;
@@ -601,6 +635,12 @@ fn focus_event(
             FocusEvent::FocusIn | FocusEvent::WindowReceivedFocus => {
                 self.has_focus.set(true);
             }
+            FocusEvent::FocusOut
+                if self.keep_focus()
+                    && !platform_window.window().window_handle().is_focus_out_forced() =>
+            {
+                return FocusEventResult::FocusOutRejected;
+            }
             FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
                 self.has_focus.set(false);
             }
@@ -1127,6 +1167,17 @@ pub fn font_weight(self: Pin<&Self>) -> Option<i32> {
             Some(font_weight)
         }
     }
+
+    /// Returns the default font properties of the window, used for the text elements that
+    /// don't set them.
+    pub fn default_font_request(self: Pin<&Self>) -> FontRequest {
+        FontRequest {
+            family: self.font_family(),
+            weight: self.font_weight(),
+            pixel_size: self.font_size(),
+            letter_spacing: None,
+        }
+    }
 }
 
 impl ItemConsts for WindowItem {
@@ -1276,3 +1327,11 @@ pub struct PointerEvent {
     pub button: PointerEventButton,
     pub kind: PointerEventKind,
 }
+
+/// Represents the move of two fingers on a touch screen, see [`MouseEvent::Pinch`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[repr(C)]
+pub struct PinchEvent {
+    pub scale: f32,
+    pub rotation: f32,
+}
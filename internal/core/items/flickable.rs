@@ -11,13 +11,13 @@
 use super::{
     Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, PointerEventButton, RenderingResult,
 };
-use crate::animations::{EasingCurve, Instant};
+use crate::animations::Instant;
 use crate::graphics::{Point, Rect};
 use crate::input::{
     FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
 };
 use crate::item_rendering::CachedRenderingData;
-use crate::items::{PropertyAnimation, Rectangle};
+use crate::items::Rectangle;
 use crate::layout::{LayoutInfo, Orientation};
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
@@ -73,7 +73,7 @@ fn input_event_filter_before_children(
         self: Pin<&Self>,
         event: MouseEvent,
         _platform_window: &Rc<dyn PlatformWindow>,
-        _self_rc: &ItemRc,
+        self_rc: &ItemRc,
     ) -> InputEventFilterResult {
         if let Some(pos) = event.position() {
             if pos.x < 0 as _ || pos.y < 0 as _ || pos.x > self.width() || pos.y > self.height() {
@@ -83,14 +83,14 @@ fn input_event_filter_before_children(
         if !self.interactive() && !matches!(event, MouseEvent::Wheel { .. }) {
             return InputEventFilterResult::ForwardAndIgnore;
         }
-        self.data.handle_mouse_filter(self, event)
+        self.data.handle_mouse_filter(self, event, self_rc)
     }
 
     fn input_event(
         self: Pin<&Self>,
         event: MouseEvent,
         _platform_window: &Rc<dyn PlatformWindow>,
-        _self_rc: &ItemRc,
+        self_rc: &ItemRc,
     ) -> InputEventResult {
         if !self.interactive() && !matches!(event, MouseEvent::Wheel { .. }) {
             return InputEventResult::EventIgnored;
@@ -106,7 +106,7 @@ fn input_event(
             }
         }
 
-        self.data.handle_mouse(self, event)
+        self.data.handle_mouse(self, event, self_rc)
     }
 
     fn key_event(
@@ -145,6 +145,25 @@ impl ItemConsts for Flickable {
         Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
 
+impl Flickable {
+    /// Moves the viewport by `delta`, without leaving its bounds, and returns by how much it
+    /// actually moved.
+    pub fn scroll_viewport_by(
+        self: Pin<&Self>,
+        delta: euclid::default::Vector2D<Coord>,
+    ) -> euclid::default::Vector2D<Coord> {
+        let viewport_x =
+            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(self);
+        let viewport_y =
+            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(self);
+        let old_pos = Point::new(viewport_x.get(), viewport_y.get());
+        let new_pos = ensure_in_bound(self, old_pos + delta);
+        viewport_x.set(new_pos.x);
+        viewport_y.set(new_pos.y);
+        new_pos - old_pos
+    }
+}
+
 #[repr(C)]
 /// Wraps the internal data structure for the Flickable
 pub struct FlickableDataBox(core::ptr::NonNull<FlickableData>);
@@ -173,6 +192,20 @@ fn deref(&self) -> &Self::Target {
 const DISTANCE_THRESHOLD: Coord = 8 as _;
 /// Time required before we stop caring about child event if the mouse hasn't been moved
 const DURATION_THRESHOLD: Duration = Duration::from_millis(500);
+/// The viewport doesn't keep moving after the release if the mouse stood still for that long before
+const RELEASE_VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+/// The interval at which the viewport moves while it keeps scrolling after a flick
+const KINETIC_INTERVAL: Duration = Duration::from_millis(16);
+/// The time, in milliseconds, after which the velocity of a flick decreased by a factor e
+const KINETIC_TIME_CONSTANT: f32 = 325.;
+/// Same as `KINETIC_TIME_CONSTANT`, when the viewport is scrolled past its bounds
+const OVERSCROLL_TIME_CONSTANT: f32 = 40.;
+/// The velocity, in pixels per millisecond, below which the viewport stops moving after a flick
+const MIN_KINETIC_VELOCITY: f32 = 0.05;
+/// How far the viewport can be scrolled past its bounds by a flick, before bouncing back
+const MAX_OVERSCROLL: Coord = 40 as _;
+/// How long the viewport takes to spring back within its bounds
+const BOUNCE_DURATION: Duration = Duration::from_millis(200);
 
 #[derive(Default, Debug)]
 struct FlickableDataInner {
@@ -182,11 +215,24 @@ struct FlickableDataInner {
     pressed_viewport_pos: Point,
     /// Set to true if the flickable is flicking and capturing all mouse event, not forwarding back to the children
     capture_events: bool,
+    /// The position and the time of the mouse from which the velocity is computed
+    last_move: Option<(Point, Instant)>,
+    /// The velocity of the mouse while flicking, in pixels per millisecond
+    velocity: euclid::default::Vector2D<f32>,
+    /// The time of the last step of the scrolling after a flick
+    last_kinetic_step: Option<Instant>,
+    /// The position of the viewport set by the last step of the scrolling after a flick
+    last_kinetic_pos: Option<Point>,
+    /// Once the flick stopped past the bounds: the time and the position from which the viewport
+    /// springs back
+    bounce: Option<(Instant, Point)>,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default)]
 pub struct FlickableData {
     inner: RefCell<FlickableDataInner>,
+    /// Keeps moving the viewport after a flick, see [`Self::kinetic_step`]
+    kinetic_timer: crate::timers::Timer,
 }
 
 impl FlickableData {
@@ -194,20 +240,18 @@ pub fn handle_mouse_filter(
         &self,
         flick: Pin<&Flickable>,
         event: MouseEvent,
+        flick_rc: &ItemRc,
     ) -> InputEventFilterResult {
         let mut inner = self.inner.borrow_mut();
         match event {
             MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                // A press catches the viewport that is still moving after a flick
+                self.stop_kinetic_scrolling(&mut inner);
                 inner.pressed_pos = position;
                 inner.pressed_time = Some(crate::animations::current_tick());
-                inner.pressed_viewport_pos = Point::new(
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
+                inner.last_move = Some((position, crate::animations::current_tick()));
+                inner.velocity = Default::default();
+                inner.pressed_viewport_pos = viewport_pos(flick);
                 if inner.capture_events {
                     InputEventFilterResult::Intercept
                 } else {
@@ -216,7 +260,7 @@ pub fn handle_mouse_filter(
             }
             MouseEvent::Exit | MouseEvent::Released { button: PointerEventButton::Left, .. } => {
                 let was_capturing = inner.capture_events;
-                Self::mouse_released(&mut inner, flick, event);
+                self.mouse_released(flick, &mut inner, event, flick_rc);
                 if was_capturing {
                     InputEventFilterResult::Intercept
                 } else {
@@ -255,27 +299,51 @@ pub fn handle_mouse_filter(
                 InputEventFilterResult::InterceptAndDispatch(MouseEvent::Moved { position })
             }
             // Not the left button
-            MouseEvent::Pressed { .. } | MouseEvent::Released { .. } => {
-                InputEventFilterResult::ForwardAndIgnore
-            }
+            MouseEvent::Pressed { .. }
+            | MouseEvent::Released { .. }
+            | MouseEvent::Enter { .. }
+            | MouseEvent::LongPress { .. }
+            | MouseEvent::Pinch { .. } => InputEventFilterResult::ForwardAndIgnore,
         }
     }
 
-    pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEventResult {
+    pub fn handle_mouse(
+        &self,
+        flick: Pin<&Flickable>,
+        event: MouseEvent,
+        flick_rc: &ItemRc,
+    ) -> InputEventResult {
         let mut inner = self.inner.borrow_mut();
         match event {
             MouseEvent::Pressed { .. } => {
                 inner.capture_events = true;
                 InputEventResult::GrabMouse
             }
+            MouseEvent::Enter { .. } | MouseEvent::Pinch { .. } => InputEventResult::EventIgnored,
+            MouseEvent::LongPress { .. } => {
+                if inner.pressed_time.is_some() {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
             MouseEvent::Exit | MouseEvent::Released { .. } => {
-                Self::mouse_released(&mut inner, flick, event);
+                self.mouse_released(flick, &mut inner, event, flick_rc);
                 InputEventResult::EventAccepted
             }
             MouseEvent::Moved { position } => {
                 if inner.pressed_time.is_some() {
                     inner.capture_events = true;
-                    let new_pos = ensure_in_bound(
+                    let now = crate::animations::current_tick();
+                    if let Some((last_pos, last_time)) = inner.last_move {
+                        // Several moves within the same tick are accumulated into the next velocity
+                        let elapsed = (now - last_time).as_millis() as f32;
+                        if elapsed > 0. {
+                            inner.velocity = (position - last_pos).cast::<f32>() / elapsed;
+                            inner.last_move = Some((position, now));
+                        }
+                    }
+                    let new_pos = drag_in_bound(
                         flick,
                         inner.pressed_viewport_pos + (position - inner.pressed_pos),
                     );
@@ -291,7 +359,12 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
                     InputEventResult::EventIgnored
                 }
             }
-            MouseEvent::Wheel { delta, .. } => {
+            MouseEvent::Wheel { mut delta, modifiers, .. } => {
+                self.stop_kinetic_scrolling(&mut inner);
+                // Shift turns the vertical scrolling of a wheel into horizontal scrolling
+                if modifiers.shift && delta.x == 0 as Coord {
+                    delta = Point::new(delta.y, delta.x);
+                }
                 let old_pos = Point::new(
                     (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
                         .apply_pin(flick)
@@ -312,36 +385,148 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
         }
     }
 
-    fn mouse_released(inner: &mut FlickableDataInner, flick: Pin<&Flickable>, event: MouseEvent) {
-        if let (Some(pressed_time), Some(pos)) = (inner.pressed_time, event.position()) {
-            let dist = (pos - inner.pressed_pos).cast::<f32>();
-            let speed =
-                dist / ((crate::animations::current_tick() - pressed_time).as_millis() as f32);
-
-            let duration = 250;
-            let final_pos = ensure_in_bound(
-                flick,
-                (inner.pressed_viewport_pos.cast() + dist + speed * (duration as f32)).cast(),
-            );
-            let anim = PropertyAnimation {
-                duration,
-                easing: EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]),
-                ..PropertyAnimation::default()
-            };
-            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                .apply_pin(flick)
-                .set_animated_value(final_pos.x, anim.clone());
-            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                .apply_pin(flick)
-                .set_animated_value(final_pos.y, anim);
+    fn mouse_released(
+        &self,
+        flick: Pin<&Flickable>,
+        inner: &mut FlickableDataInner,
+        event: MouseEvent,
+        flick_rc: &ItemRc,
+    ) {
+        if let Some((_, last_time)) = inner.last_move {
+            let now = crate::animations::current_tick();
+            if now - last_time > RELEASE_VELOCITY_TIMEOUT
+                || !matches!(event, MouseEvent::Released { .. })
+                || inner.velocity.length() < MIN_KINETIC_VELOCITY
+            {
+                inner.velocity = Default::default();
+                // A viewport dragged past its bounds springs back
+                let pos = viewport_pos(flick);
+                if ensure_in_bound(flick, pos) != pos {
+                    inner.bounce = Some((now, pos));
+                }
+            }
+            if inner.velocity != euclid::default::Vector2D::zero() || inner.bounce.is_some() {
+                inner.last_kinetic_step = Some(now);
+                let flick_weak = flick_rc.downgrade();
+                self.kinetic_timer.start(
+                    crate::timers::TimerMode::Repeated,
+                    KINETIC_INTERVAL,
+                    move || {
+                        if let Some(flick_rc) = flick_weak.upgrade() {
+                            if let Some(flick) = flick_rc.downcast::<Flickable>() {
+                                let flick = flick.as_pin_ref();
+                                flick.data.kinetic_step(flick);
+                            }
+                        }
+                    },
+                );
+            }
         }
         inner.capture_events = false; // FIXME: should only be set to false once the flick animation is over
         inner.pressed_time = None;
+        inner.last_move = None;
+    }
+
+    /// Moves the viewport with the velocity of the flick, which decreases over time, and makes it
+    /// spring back when it is scrolled past its bounds.
+    ///
+    /// The flick stops when something else moves the viewport that it scrolled past its bounds,
+    /// like a ListView that clamps it, or that springs it back itself.
+    fn kinetic_step(&self, flick: Pin<&Flickable>) {
+        let mut inner = self.inner.borrow_mut();
+        let viewport_x =
+            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(flick);
+        let viewport_y =
+            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(flick);
+        let pos = Point::new(viewport_x.get(), viewport_y.get());
+        let (lower, upper) = viewport_bounds(flick);
+        let in_bounds = |p: Point| p.max(lower).min(upper);
+        if let Some(last_pos) = inner.last_kinetic_pos {
+            if pos != last_pos && in_bounds(last_pos) != last_pos {
+                self.stop_kinetic_scrolling(&mut inner);
+                return;
+            }
+        }
+
+        let now = crate::animations::current_tick();
+        let new_pos = match inner.bounce {
+            Some((start, from)) => {
+                let bound = in_bounds(from);
+                bound + (from - bound) * springback_factor(now - start, BOUNCE_DURATION) as Coord
+            }
+            None => {
+                let elapsed = inner
+                    .last_kinetic_step
+                    .map_or(0., |last_step| (now - last_step).as_millis() as f32);
+                inner.last_kinetic_step = Some(now);
+                let mut velocity = inner.velocity;
+                let mut new_pos = pos;
+                for (v, p, lower, upper) in [
+                    (&mut velocity.x, &mut new_pos.x, lower.x, upper.x),
+                    (&mut velocity.y, &mut new_pos.y, lower.y, upper.y),
+                ] {
+                    if lower == upper {
+                        // The viewport can't scroll in that direction
+                        *v = 0.;
+                    }
+                    if *v == 0. {
+                        continue;
+                    }
+                    // The velocity decreases exponentially, much faster past the bounds. Integrating
+                    // it over the elapsed time keeps the same trajectory when the timer fires late.
+                    let time_constant = if *p < lower || *p > upper {
+                        OVERSCROLL_TIME_CONSTANT
+                    } else {
+                        KINETIC_TIME_CONSTANT
+                    };
+                    let decay = (-elapsed / time_constant).exp();
+                    *p += (*v * time_constant * (1. - decay)) as Coord;
+                    *v *= decay;
+                    if *p < lower - MAX_OVERSCROLL || *p > upper + MAX_OVERSCROLL {
+                        *p = p.max(lower - MAX_OVERSCROLL).min(upper + MAX_OVERSCROLL);
+                        *v = 0.;
+                    }
+                    if v.abs() < MIN_KINETIC_VELOCITY {
+                        *v = 0.;
+                    }
+                }
+                inner.velocity = velocity;
+                if velocity == euclid::default::Vector2D::zero() && in_bounds(new_pos) != new_pos {
+                    inner.bounce = Some((now, new_pos));
+                }
+                new_pos
+            }
+        };
+        viewport_x.set(new_pos.x);
+        viewport_y.set(new_pos.y);
+        inner.last_kinetic_pos = Some(new_pos);
+        if inner.velocity == euclid::default::Vector2D::zero() && in_bounds(new_pos) == new_pos {
+            self.stop_kinetic_scrolling(&mut inner);
+        }
+    }
+
+    fn stop_kinetic_scrolling(&self, inner: &mut FlickableDataInner) {
+        self.kinetic_timer.stop();
+        inner.last_kinetic_step = None;
+        inner.last_kinetic_pos = None;
+        inner.bounce = None;
+        inner.velocity = Default::default();
     }
 }
 
-/// Make sure that the point is within the bounds
-fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+/// Returns the fraction of the distance past the bounds that remains `elapsed` after a viewport
+/// started springing back within `duration`. It decelerates, and is 0 once `duration` elapsed.
+pub(crate) fn springback_factor(elapsed: Duration, duration: Duration) -> f32 {
+    let progress = if duration.is_zero() {
+        1.
+    } else {
+        (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.)
+    };
+    (1. - progress) * (1. - progress)
+}
+
+/// Returns the lowest and the highest position of the viewport within the bounds
+fn viewport_bounds(flick: Pin<&Flickable>) -> (Point, Point) {
     let w = flick.width();
     let h = flick.height();
     let vw =
@@ -350,11 +535,43 @@ fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
         .apply_pin(flick)
         .get();
 
-    let min = Point::new(w - vw, h - vh);
     let max = Point::new(0 as _, 0 as _);
+    (Point::new(w - vw, h - vh).min(max), max)
+}
+
+/// Make sure that the point is within the bounds
+fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+    let (min, max) = viewport_bounds(flick);
     p.max(min).min(max)
 }
 
+/// Returns the position of the viewport dragged to `p`: past the bounds, in the directions in which
+/// it can scroll, it follows half of the distance, up to `MAX_OVERSCROLL`.
+fn drag_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+    let (min, max) = viewport_bounds(flick);
+    let bounded = p.max(min).min(max);
+    let overscroll = |p: Coord, bounded: Coord, min: Coord, max: Coord| {
+        if min == max {
+            0 as Coord
+        } else {
+            ((p - bounded) / 2 as Coord).max(-MAX_OVERSCROLL).min(MAX_OVERSCROLL)
+        }
+    };
+    bounded
+        + euclid::vec2(
+            overscroll(p.x, bounded.x, min.x, max.x),
+            overscroll(p.y, bounded.y, min.y, max.y),
+        )
+}
+
+/// Returns the position of the viewport
+fn viewport_pos(flick: Pin<&Flickable>) -> Point {
+    Point::new(
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(flick).get(),
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(flick).get(),
+    )
+}
+
 /// # Safety
 /// This must be called using a non-null pointer pointing to a chunk of memory big enough to
 /// hold a FlickableDataBox
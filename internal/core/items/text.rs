@@ -9,24 +9,31 @@
 */
 
 use super::{
-    InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, PointArg,
-    PointerEventButton, RenderingResult, TextHorizontalAlignment, TextOverflow,
-    TextVerticalAlignment, TextWrap, VoidArg,
+    CaretStyle, InputType, Item, ItemConsts, ItemRc, ItemRef, KeyEventResult, KeyEventType,
+    PointArg, PointerEventButton, RenderingResult, StringArg, SuggestionAcceptKey, TextClipAxis,
+    TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap, VoidArg,
+};
+use crate::graphics::{
+    Brush, Color, FontRequest, Point, Rect, Size, TextMeasureRules, DEFAULT_FONT_SIZE,
 };
-use crate::graphics::{Brush, Color, FontRequest, Rect};
 use crate::input::{
     key_codes, FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
     KeyboardModifiers, MouseEvent, StandardShortcut, TextShortcut,
 };
 use crate::item_rendering::{CachedRenderingData, ItemRenderer};
 use crate::layout::{LayoutInfo, Orientation};
+use crate::platform::PlatformAbstraction;
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
 use crate::window::{PlatformWindow, WindowHandleAccess, WindowInner};
-use crate::{Callback, Coord, Property, SharedString};
+use crate::{Callback, Coord, Property, SharedString, SharedVector};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
+use core::cell::RefCell;
 use core::pin::Pin;
 #[allow(unused)]
 use euclid::num::Ceil;
@@ -43,16 +50,23 @@ pub struct Text {
     pub font_size: Property<Coord>,
     pub font_weight: Property<i32>,
     pub color: Property<Brush>,
+    pub text_stroke_color: Property<Brush>,
+    pub text_stroke_width: Property<Coord>,
     pub horizontal_alignment: Property<TextHorizontalAlignment>,
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
     pub overflow: Property<TextOverflow>,
+    pub clip_axis: Property<TextClipAxis>,
+    pub elide_string: Property<SharedString>,
     pub letter_spacing: Property<Coord>,
+    pub auto_shrink: Property<bool>,
+    pub min_font_size: Property<Coord>,
     pub x: Property<Coord>,
     pub y: Property<Coord>,
     pub width: Property<Coord>,
     pub height: Property<Coord>,
     pub cached_rendering_data: CachedRenderingData,
+    data: TextDataBox,
 }
 
 impl Item for Text {
@@ -68,14 +82,24 @@ fn layout_info(
         platform_window: &Rc<dyn PlatformWindow>,
     ) -> LayoutInfo {
         let window = platform_window.window().window_handle();
-        let implicit_size = |max_width| {
+        // The preferred size is the size of the text at the full font size, which doesn't depend on
+        // the size of this element, unlike the one it is rendered with when it shrinks.
+        let font_request = self.unshrunk_font_request(window);
+        let text_size = |font_request, max_width| {
             platform_window.renderer().text_size(
-                self.font_request(window),
+                font_request,
                 self.text().as_str(),
                 max_width,
                 platform_window.window().scale_factor().get(),
+                self.measure_rules(),
             )
         };
+        let implicit_size = |max_width| text_size(font_request.clone(), max_width);
+        // When shrinking, the text can be as small as with the minimum font size
+        let min_font_request = || FontRequest {
+            pixel_size: Some(self.min_font_size().max(1 as Coord)),
+            ..font_request.clone()
+        };
 
         // Stretch uses `round_layout` to explicitly align the top left and bottom right of layout nodes
         // to pixel boundaries. To avoid rounding down causing the minimum width to become so little that
@@ -84,15 +108,26 @@ fn layout_info(
             Orientation::Horizontal => {
                 let implicit_size = implicit_size(None);
                 let min = match self.overflow() {
+                    // The text can be drawn past the right edge of the element
+                    _ if !self.clips_horizontally() => 0 as Coord,
                     TextOverflow::Elide => implicit_size.width.min(
                         platform_window
                             .renderer()
-                            .text_size(self.font_request(window), "…", None, window.scale_factor())
+                            .text_size(
+                                font_request.clone(),
+                                self.ellipsis().as_str(),
+                                None,
+                                window.scale_factor(),
+                                TextMeasureRules::default(),
+                            )
                             .width,
                     ),
                     TextOverflow::Clip => match self.wrap() {
+                        TextWrap::NoWrap if self.auto_shrink() => {
+                            text_size(min_font_request(), None).width
+                        }
                         TextWrap::NoWrap => implicit_size.width,
-                        TextWrap::WordWrap => 0 as Coord,
+                        TextWrap::WordWrap | TextWrap::CharWrap => 0 as Coord,
                     },
                 };
                 LayoutInfo {
@@ -102,12 +137,19 @@ fn layout_info(
                 }
             }
             Orientation::Vertical => {
-                let h = match self.wrap() {
-                    TextWrap::NoWrap => implicit_size(None).height,
-                    TextWrap::WordWrap => implicit_size(Some(self.width())).height,
-                }
-                .ceil();
-                LayoutInfo { min: h, preferred: h, ..LayoutInfo::default() }
+                let max_width = match self.wrap() {
+                    TextWrap::NoWrap => None,
+                    TextWrap::WordWrap | TextWrap::CharWrap => Some(self.width()),
+                };
+                let h = implicit_size(max_width).height.ceil();
+                let min = if !self.clips_vertically() {
+                    0 as Coord
+                } else if self.auto_shrink() {
+                    text_size(min_font_request(), max_width).height.ceil()
+                } else {
+                    h
+                };
+                LayoutInfo { min, preferred: h, ..LayoutInfo::default() }
             }
         }
     }
@@ -162,34 +204,140 @@ impl ItemConsts for Text {
 }
 
 impl Text {
+    /// Returns the font request for this text, with the font properties that are not set on
+    /// this element resolved from the default font properties of the window.
+    ///
+    /// With `auto_shrink`, the pixel size is reduced, down to `min_font_size`, until the text
+    /// fits in the width and height of this element. It starts from [`DEFAULT_FONT_SIZE`] when
+    /// no font size is set. Wrapped text is wrapped at the width first, and shrinks only if it
+    /// is still too high.
     pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
-        let window_item = window.window_item();
+        let request = self.unshrunk_font_request(window);
+        if !self.auto_shrink() {
+            return request;
+        }
+        // The search measures the text several times, so its result is kept until the text, the
+        // font or the size of this element change
+        let tracker = self.data.shrunk_font_size_tracker.as_ref();
+        if let Some(pixel_size) = tracker.evaluate_if_dirty(|| self.shrunk_font_size(window)) {
+            self.data.shrunk_font_size.set(pixel_size);
+        }
+        FontRequest { pixel_size: Some(self.data.shrunk_font_size.get()), ..request }
+    }
+
+    /// Returns the pixel size of the font with `auto_shrink`, see [`Self::font_request`].
+    fn shrunk_font_size(self: Pin<&Self>, window: &WindowInner) -> Coord {
+        let request = self.unshrunk_font_request(window);
+        let font_size = request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE);
+        let width = self.width();
+        let height = self.height();
+        let max_width = match self.wrap() {
+            TextWrap::NoWrap => None,
+            TextWrap::WordWrap | TextWrap::CharWrap => Some(width),
+        };
+        let platform_window = window.platform_window();
+        let text = self.text();
+        let fits = |font_size| {
+            let size = platform_window.renderer().text_size(
+                FontRequest { pixel_size: Some(font_size), ..request.clone() },
+                text.as_str(),
+                max_width,
+                window.scale_factor(),
+                self.measure_rules(),
+            );
+            size.width <= width && size.height <= height
+        };
+        shrunk_font_size(font_size, self.min_font_size(), fits)
+    }
+
+    /// Returns the string that replaces the end of the text when it is elided: `elide_string`,
+    /// or "…" if it is empty.
+    pub fn ellipsis(self: Pin<&Self>) -> SharedString {
+        let elide_string = self.elide_string();
+        if elide_string.is_empty() {
+            "…".into()
+        } else {
+            elide_string
+        }
+    }
+
+    /// Returns true if the text is clipped at the left and right edges of this element.
+    pub fn clips_horizontally(self: Pin<&Self>) -> bool {
+        self.clip_axis() != TextClipAxis::Vertical
+    }
+
+    /// Returns true if the text is clipped at the top and bottom edges of this element.
+    pub fn clips_vertically(self: Pin<&Self>) -> bool {
+        self.clip_axis() != TextClipAxis::Horizontal
+    }
+
+    /// Returns the rectangle, relative to this element, in which the renderers lay out the text
+    /// and clip it. It is the geometry of this element, extended in the directions that
+    /// `clip_axis` doesn't clip so that the whole text fits in it, keeping the alignment.
+    pub fn layout_rect(self: Pin<&Self>, window: &WindowInner) -> Rect {
+        let size = Size::new(self.width(), self.height());
+        if self.clips_horizontally() && self.clips_vertically() {
+            return Rect::from_size(size);
+        }
+        let max_width = match self.wrap() {
+            TextWrap::NoWrap => None,
+            TextWrap::WordWrap | TextWrap::CharWrap => Some(size.width),
+        };
+        let text_size = window.platform_window().renderer().text_size(
+            self.font_request(window),
+            self.text().as_str(),
+            max_width,
+            window.scale_factor(),
+            self.measure_rules(),
+        );
+        overflowing_text_rect(
+            size,
+            text_size,
+            self.clips_horizontally(),
+            self.clips_vertically(),
+            self.horizontal_alignment(),
+            self.vertical_alignment(),
+        )
+    }
 
+    /// Returns the rules the renderers use to measure and lay out this text. With `char-wrap`,
+    /// the text breaks at any character.
+    pub fn measure_rules(self: Pin<&Self>) -> TextMeasureRules {
+        TextMeasureRules {
+            break_anywhere: self.wrap() == TextWrap::CharWrap,
+            ..TextMeasureRules::default()
+        }
+    }
+
+    /// Returns the font request for this text at its full font size, see [`Self::font_request`].
+    fn unshrunk_font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
+        let request = self.unresolved_font_request();
+        match window.window_item() {
+            Some(window_item) => request.merge(&window_item.as_pin_ref().default_font_request()),
+            None => request,
+        }
+    }
+
+    /// Returns the (UTF-8) byte ranges of the characters of the text that the renderer of the
+    /// window has no glyph for, and would be rendered as a placeholder box. This can be used to
+    /// choose a fallback font, or to warn about text that can't be displayed.
+    pub fn missing_glyphs(
+        self: Pin<&Self>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> Vec<core::ops::Range<usize>> {
+        let window = platform_window.window().window_handle();
+        platform_window.renderer().missing_glyphs(
+            self.font_request(window),
+            self.text().as_str(),
+            window.scale_factor(),
+        )
+    }
+
+    fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
         FontRequest {
-            family: {
-                let maybe_family = self.font_family();
-                if !maybe_family.is_empty() {
-                    Some(maybe_family)
-                } else {
-                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_family())
-                }
-            },
-            weight: {
-                let weight = self.font_weight();
-                if weight == 0 {
-                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_weight())
-                } else {
-                    Some(weight)
-                }
-            },
-            pixel_size: {
-                let font_size = self.font_size();
-                if font_size == 0 as Coord {
-                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_size())
-                } else {
-                    Some(font_size)
-                }
-            },
+            family: Some(self.font_family()).filter(|family| !family.is_empty()),
+            weight: Some(self.font_weight()).filter(|weight| *weight != 0),
+            pixel_size: Some(self.font_size()).filter(|size| *size != 0 as Coord),
             letter_spacing: Some(self.letter_spacing()),
         }
     }
@@ -219,19 +367,54 @@ pub struct TextInput {
     pub cursor_position: Property<i32>, // byte offset,
     pub anchor_position: Property<i32>, // byte offset
     pub text_cursor_width: Property<Coord>,
+    pub caret_style: Property<CaretStyle>,
+    pub highlight_current_line: Property<bool>,
+    pub highlight_current_line_when_unfocused: Property<bool>,
+    pub current_line_color: Property<Brush>,
     pub cursor_visible: Property<bool>,
     pub has_focus: Property<bool>,
+    pub keep_focus: Property<bool>,
     pub enabled: Property<bool>,
     pub accepted: Callback<VoidArg>,
     pub cursor_position_changed: Callback<PointArg>,
     pub edited: Callback<VoidArg>,
+    pub context_menu_requested: Callback<PointArg>,
+    pub copied: Callback<StringArg>,
     pub pressed: core::cell::Cell<bool>,
     pub single_line: Property<bool>,
     pub read_only: Property<bool>,
+    pub allowed_characters: Property<SharedString>,
+    pub trim_on_commit: Property<bool>,
+    pub trim_trailing_only: Property<bool>,
+    pub autoscroll_margin: Property<Coord>,
+    pub autoscroll_speed: Property<f32>,
+    pub max_length: Property<i32>,
+    pub placeholder_text: Property<SharedString>,
+    pub placeholder_color: Property<Brush>,
+    pub preedit_text: Property<SharedString>,
+    pub suggestion_text: Property<SharedString>,
+    pub suggestion_color: Property<Brush>,
+    pub suggestion_accept_key: Property<SuggestionAcceptKey>,
+    pub sync_primary_selection: Property<bool>,
+    pub accepts_tab: Property<bool>,
+    pub tab_spaces: Property<i32>,
+    pub background: Property<Brush>,
+    pub border_color: Property<Brush>,
+    pub border_width: Property<Coord>,
+    pub border_radius: Property<Coord>,
     pub cached_rendering_data: CachedRenderingData,
     // The x position where the cursor wants to be.
     // It is not updated when moving up and down even when the line is shorter.
     preferred_x_pos: core::cell::Cell<Coord>,
+    // Bit set of the StandardShortcut that are not handled, indexed by their discriminant.
+    ignored_shortcuts: core::cell::Cell<u16>,
+    // Byte offsets of the additional cursors, where the inserted text is also inserted.
+    secondary_cursor_positions: Property<SharedVector<i32>>,
+    // Set when the text input was last pressed with a touch screen, which shows the selection handles.
+    selection_handles_shown: Property<bool>,
+    // Byte offset of the cursor within the pre-edit text.
+    preedit_cursor: Property<i32>,
+    data: TextInputDataBox,
 }
 
 impl Item for TextInput {
@@ -247,7 +430,8 @@ fn layout_info(
         orientation: Orientation,
         platform_window: &Rc<dyn PlatformWindow>,
     ) -> LayoutInfo {
-        let text = self.text();
+        let (text, _) = self.text_with_preedit();
+        let borders = self.text_rect().origin.x * 2 as Coord;
         let implicit_size = |max_width| {
             platform_window.renderer().text_size(
                 self.font_request(platform_window),
@@ -260,6 +444,7 @@ fn layout_info(
                 },
                 max_width,
                 platform_window.window().scale_factor().get(),
+                self.measure_rules(),
             )
         };
 
@@ -271,20 +456,22 @@ fn layout_info(
                 let implicit_size = implicit_size(None);
                 let min = match self.wrap() {
                     TextWrap::NoWrap => implicit_size.width,
-                    TextWrap::WordWrap => 0 as Coord,
+                    TextWrap::WordWrap | TextWrap::CharWrap => 0 as Coord,
                 };
                 LayoutInfo {
-                    min: min.ceil(),
-                    preferred: implicit_size.width.ceil(),
+                    min: (min + borders).ceil(),
+                    preferred: (implicit_size.width + borders).ceil(),
                     ..LayoutInfo::default()
                 }
             }
             Orientation::Vertical => {
                 let h = match self.wrap() {
                     TextWrap::NoWrap => implicit_size(None).height,
-                    TextWrap::WordWrap => implicit_size(Some(self.width())).height,
-                }
-                .ceil();
+                    TextWrap::WordWrap | TextWrap::CharWrap => {
+                        implicit_size(Some(self.width() - borders)).height
+                    }
+                };
+                let h = (h + borders).ceil();
                 LayoutInfo { min: h, preferred: h, ..LayoutInfo::default() }
             }
         }
@@ -308,30 +495,194 @@ fn input_event(
         if !self.enabled() {
             return InputEventResult::EventIgnored;
         }
+        self.revalidate_cursor_and_anchor();
         match event {
             MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                let touch_input = platform_window.window().window_handle().is_touch_input();
+                self.selection_handles_shown.set(touch_input);
+                if touch_input {
+                    if let Some((handle, _)) =
+                        self.selection_handles(platform_window).into_iter().find(|(_, rect)| {
+                            platform_window
+                                .window()
+                                .window_handle()
+                                .hit_test_rect(*rect)
+                                .contains(position)
+                        })
+                    {
+                        // Drag the middle of the caret above the handle, rather than the handle
+                        let end_point = match handle {
+                            SelectionHandle::Anchor => self.anchor_position(),
+                            SelectionHandle::Cursor => self.cursor_position(),
+                        };
+                        let caret_rect = self.cursor_rect_for_byte_offset(
+                            end_point.max(0) as usize,
+                            platform_window,
+                        );
+                        self.data
+                            .dragged_selection_handle
+                            .set(Some((handle, caret_rect.center() - position)));
+                        return InputEventResult::GrabMouse;
+                    }
+                }
+                let window = platform_window.window().window_handle();
+                if !touch_input && window.keyboard_modifiers().shift {
+                    // Shift+Click keeps the anchor and extends the selection to the clicked position
+                    window.reset_click_count();
+                    self.data.selection_drag_unit.set(None);
+                    self.as_ref().pressed.set(true);
+                    let clicked_offset = self.byte_offset_for_position(position, platform_window);
+                    self.extend_selection_to(clicked_offset, platform_window);
+                    if !self.has_focus() {
+                        window.set_focus_item(self_rc);
+                    }
+                    return InputEventResult::GrabMouse;
+                }
+                // A fourth click selects from a single click again
+                let click_count = (window.click_count().max(1) - 1) % 3 + 1;
                 let clicked_offset =
-                    platform_window.renderer().text_input_byte_offset_for_position(self, position)
-                        as i32;
+                    self.byte_offset_for_position(position, platform_window) as i32;
+                let selection = self.selection();
+                // The text of a password input can't be dragged out of it
+                if click_count == 1
+                    && !touch_input
+                    && self.input_type() != InputType::Password
+                    && !selection.is_empty()
+                    && selection.contains(&(clicked_offset.max(0) as usize))
+                {
+                    // A press within the selection drags the selected text once the mouse moves,
+                    // and only moves the cursor if the button is released before
+                    *self.data.text_drag.borrow_mut() = Some(TextDrag {
+                        text: self.text()[selection.clone()].into(),
+                        range: selection,
+                        press_position: position,
+                        press_offset: clicked_offset.max(0) as usize,
+                        dragging: false,
+                    });
+                    if !self.has_focus() {
+                        window.set_focus_item(self_rc);
+                    }
+                    return InputEventResult::GrabMouse;
+                }
                 self.as_ref().pressed.set(true);
+                self.clear_secondary_cursors();
+                // A double click selects a word and a triple click a line, and dragging then
+                // extends the selection by whole words or lines
+                let granularity = match click_count {
+                    2 => Some(SelectionGranularity::Word),
+                    3 => Some(SelectionGranularity::Line),
+                    _ => None,
+                };
+                match granularity {
+                    Some(granularity) => {
+                        let unit = selection_unit_range(
+                            &self.text(),
+                            clicked_offset.max(0) as usize,
+                            granularity,
+                        );
+                        self.data.selection_drag_unit.set(Some((
+                            granularity,
+                            unit.start,
+                            unit.end,
+                        )));
+                        self.as_ref().anchor_position.set(unit.start as i32);
+                        self.set_cursor_position(unit.end as i32, true, platform_window);
+                    }
+                    None => {
+                        self.data.selection_drag_unit.set(None);
+                        self.as_ref().anchor_position.set(clicked_offset);
+                        self.set_cursor_position(clicked_offset, true, platform_window);
+                    }
+                }
+                if !self.has_focus() {
+                    platform_window.window().window_handle().set_focus_item(self_rc);
+                }
+                // Keep receiving the mouse events when selecting past the edges
+                return InputEventResult::GrabMouse;
+            }
+            MouseEvent::Pressed { position, button: PointerEventButton::Right } => {
+                let clicked_offset = self.byte_offset_for_position(position, platform_window);
+                // Keep the selection when the click is within it, so that the menu can act on it
+                let (anchor, cursor) = self.selection_anchor_and_cursor();
+                if anchor == cursor || !(anchor..=cursor).contains(&clicked_offset) {
+                    self.clear_secondary_cursors();
+                    self.as_ref().anchor_position.set(clicked_offset as i32);
+                    self.set_cursor_position(clicked_offset as i32, true, platform_window);
+                }
+                if !self.has_focus() {
+                    platform_window.window().window_handle().set_focus_item(self_rc);
+                }
+                Self::FIELD_OFFSETS.context_menu_requested.apply_pin(self).call(&(position,));
+            }
+            MouseEvent::Pressed { position, button: PointerEventButton::Middle } => {
+                if self.read_only() {
+                    return InputEventResult::EventIgnored;
+                }
+                // Paste the primary selection where the text was clicked. Platforms without a
+                // primary selection have no text for it, so nothing is pasted.
+                let clicked_offset =
+                    self.byte_offset_for_position(position, platform_window) as i32;
+                self.clear_secondary_cursors();
                 self.as_ref().anchor_position.set(clicked_offset);
                 self.set_cursor_position(clicked_offset, true, platform_window);
+                self.paste(|p| p.primary_selection_text(), platform_window);
                 if !self.has_focus() {
                     platform_window.window().window_handle().set_focus_item(self_rc);
                 }
             }
+            MouseEvent::Released { position, button: PointerEventButton::Left }
+                if self.data.text_drag.borrow().is_some() =>
+            {
+                let drag = self.data.text_drag.take().unwrap();
+                if drag.dragging {
+                    let window_position =
+                        self_rc.map_to_window(self_rc.geometry().origin + position.to_vector());
+                    self.drop_dragged_text(drag, window_position, self_rc, platform_window);
+                } else {
+                    self.clear_secondary_cursors();
+                    self.as_ref().anchor_position.set(drag.press_offset as i32);
+                    self.set_cursor_position(drag.press_offset as i32, true, platform_window);
+                }
+            }
             MouseEvent::Released { button: PointerEventButton::Left, .. } | MouseEvent::Exit => {
-                self.as_ref().pressed.set(false)
+                if matches!(event, MouseEvent::Released { .. })
+                    && (self.as_ref().pressed.get()
+                        || self.data.dragged_selection_handle.get().is_some())
+                {
+                    self.update_primary_selection();
+                }
+                self.as_ref().pressed.set(false);
+                self.data.dragged_selection_handle.set(None);
+                self.data.text_drag.take();
+                platform_window.window().window_handle().stop_selection_autoscroll();
             }
             MouseEvent::Moved { position } => {
+                if let Some(drag) = self.data.text_drag.borrow_mut().as_mut() {
+                    drag.dragging |= (position - drag.press_position).square_length()
+                        > TEXT_DRAG_DISTANCE * TEXT_DRAG_DISTANCE;
+                    return InputEventResult::GrabMouse;
+                }
+                if let Some((handle, offset)) = self.data.dragged_selection_handle.get() {
+                    self.drag_selection_handle(handle, position + offset, platform_window);
+                    return InputEventResult::GrabMouse;
+                }
                 if self.as_ref().pressed.get() {
-                    let clicked_offset = platform_window
-                        .renderer()
-                        .text_input_byte_offset_for_position(self, position)
-                        as i32;
-                    self.set_cursor_position(clicked_offset, true, platform_window);
+                    let clicked_offset = self.byte_offset_for_position(position, platform_window);
+                    self.drag_selection_to(clicked_offset, platform_window);
+                    self.update_selection_autoscroll(position, self_rc, platform_window);
+                    return InputEventResult::GrabMouse;
                 }
             }
+            MouseEvent::LongPress { position }
+                if self.as_ref().pressed.get()
+                    && platform_window.window().window_handle().is_touch_input() =>
+            {
+                // There is no right button on touch screens, so a long press opens the menu instead
+                self.as_ref().pressed.set(false);
+                platform_window.window().window_handle().stop_selection_autoscroll();
+                Self::FIELD_OFFSETS.context_menu_requested.apply_pin(self).call(&(position,));
+                return InputEventResult::GrabMouse;
+            }
             _ => return InputEventResult::EventIgnored,
         }
         InputEventResult::EventAccepted
@@ -348,7 +699,15 @@ fn key_event(
 
         match event.event_type {
             KeyEventType::KeyPressed => {
+                self.revalidate_cursor_and_anchor();
+                if self.is_suggestion_accept_key(event) && self.accept_suggestion(platform_window) {
+                    return KeyEventResult::EventAccepted;
+                }
                 match event.text_shortcut() {
+                    Some(text_shortcut) if !self.preedit_text().is_empty() => {
+                        self.apply_preedit_shortcut(text_shortcut, platform_window);
+                        return KeyEventResult::EventAccepted;
+                    }
                     Some(text_shortcut) if !self.read_only() => match text_shortcut {
                         TextShortcut::Move(direction) => {
                             TextInput::move_cursor(
@@ -357,6 +716,8 @@ fn key_event(
                                 event.modifiers.into(),
                                 platform_window,
                             );
+                            self.update_primary_selection();
+                            self.hold_key(event, platform_window);
                             return KeyEventResult::EventAccepted;
                         }
                         TextShortcut::DeleteForward => {
@@ -399,23 +760,36 @@ fn key_event(
                     None => (),
                 };
 
+                if self.accepts_tab() && !self.read_only() && !event.modifiers.control {
+                    if event.text.starts_with(key_codes::Tab) {
+                        let indentation = match self.tab_spaces() {
+                            spaces if spaces > 0 => " ".repeat(spaces as usize),
+                            _ => "\t".into(),
+                        };
+                        self.edit_with_undo(None, || self.insert(&indentation, platform_window));
+                        self.show_cursor(platform_window);
+                        return KeyEventResult::EventAccepted;
+                    }
+                    if event.text.starts_with(key_codes::Backtab) {
+                        self.dedent_selected_lines(platform_window);
+                        return KeyEventResult::EventAccepted;
+                    }
+                }
+
                 if let Some(keycode) = event.text.chars().next() {
                     if keycode == key_codes::Return && !self.read_only() && self.single_line() {
+                        self.apply_trim_on_commit(platform_window);
                         Self::FIELD_OFFSETS.accepted.apply_pin(self).call(&());
                         return KeyEventResult::EventAccepted;
                     }
                 }
 
-                // Only insert/interpreter non-control character strings
-                if event.text.is_empty()
-                    || event.text.as_str().chars().any(|ch| {
-                        // exclude the private use area as we encode special keys into it
-                        ('\u{f700}'..='\u{f7ff}').contains(&ch) || (ch.is_control() && ch != '\n')
-                    })
-                {
-                    return KeyEventResult::EventIgnored;
-                }
+                // The shortcuts are matched first, as some platforms report the control character
+                // that the key produces with Control, which is not inserted
                 match event.shortcut() {
+                    Some(shortcut) if self.is_shortcut_ignored(shortcut) => {
+                        return KeyEventResult::EventIgnored;
+                    }
                     Some(shortcut) => match shortcut {
                         StandardShortcut::SelectAll => {
                             self.select_all(platform_window);
@@ -426,46 +800,71 @@ fn key_event(
                             return KeyEventResult::EventAccepted;
                         }
                         StandardShortcut::Paste if !self.read_only() => {
-                            self.paste(platform_window);
+                            self.paste(|p| p.clipboard_text(), platform_window);
                             return KeyEventResult::EventAccepted;
                         }
                         StandardShortcut::Cut if !self.read_only() => {
                             self.copy();
-                            self.delete_selection(platform_window);
+                            self.edit_with_undo(None, || self.delete_selection(platform_window));
+                            return KeyEventResult::EventAccepted;
+                        }
+                        StandardShortcut::Undo if !self.read_only() => {
+                            self.undo(platform_window);
+                            return KeyEventResult::EventAccepted;
+                        }
+                        StandardShortcut::Redo if !self.read_only() => {
+                            self.redo(platform_window);
                             return KeyEventResult::EventAccepted;
                         }
-                        StandardShortcut::Paste | StandardShortcut::Cut => {
+                        StandardShortcut::Paste
+                        | StandardShortcut::Cut
+                        | StandardShortcut::Undo
+                        | StandardShortcut::Redo => {
                             return KeyEventResult::EventIgnored;
                         }
                         _ => (),
                     },
                     None => (),
                 }
+                // Only insert/interpreter non-control character strings
+                if event.text.is_empty()
+                    || event.text.as_str().chars().any(|ch| {
+                        // exclude the private use area as we encode special keys into it
+                        ('\u{f700}'..='\u{f7ff}').contains(&ch) || (ch.is_control() && ch != '\n')
+                    })
+                {
+                    return KeyEventResult::EventIgnored;
+                }
                 if self.read_only() || event.modifiers.control {
                     return KeyEventResult::EventIgnored;
                 }
-                self.delete_selection(platform_window);
-
-                let mut text: String = self.text().into();
 
-                // FIXME: respect grapheme boundaries
-                let insert_pos = self.selection_anchor_and_cursor().1;
-                text.insert_str(insert_pos, &event.text);
-
-                self.as_ref().text.set(text.into());
-                let new_cursor_pos = (insert_pos + event.text.len()) as i32;
-                self.as_ref().anchor_position.set(new_cursor_pos);
-                self.set_cursor_position(new_cursor_pos, true, platform_window);
+                self.edit_with_undo(Some(&event.text), || {
+                    self.insert(&event.text, platform_window)
+                });
 
                 // Keep the cursor visible when inserting text. Blinking should only occur when
                 // nothing is entered or the cursor isn't moved.
                 self.as_ref().show_cursor(platform_window);
 
-                Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
-
                 KeyEventResult::EventAccepted
             }
-            _ => KeyEventResult::EventIgnored,
+            KeyEventType::KeyReleased => {
+                let released_held_key = self
+                    .data
+                    .held_key
+                    .borrow()
+                    .as_ref()
+                    .map_or(false, |key| key.text == event.text);
+                if released_held_key {
+                    *self.data.held_key.borrow_mut() = None;
+                    platform_window.window().window_handle().stop_key_repeat();
+                    // The cursor stopped moving, start blinking again from the visible state
+                    self.show_cursor(platform_window);
+                }
+                // The parents still see the release, like the other keys this doesn't handle
+                KeyEventResult::EventIgnored
+            }
         }
     }
 
@@ -479,8 +878,26 @@ fn focus_event(
                 self.has_focus.set(true);
                 self.show_cursor(platform_window);
                 platform_window.show_virtual_keyboard(self.input_type());
+                self.report_ime_cursor_area(platform_window);
+            }
+            FocusEvent::FocusOut
+                if self.keep_focus()
+                    && !platform_window.window().window_handle().is_focus_out_forced() =>
+            {
+                return FocusEventResult::FocusOutRejected;
             }
             FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
+                *self.data.held_key.borrow_mut() = None;
+                platform_window.window().window_handle().stop_key_repeat();
+                if *event == FocusEvent::WindowLostFocus {
+                    // The click count is shared by the window: a press that moves the focus to
+                    // another input must still count for a double click there
+                    platform_window.window().window_handle().reset_click_count();
+                }
+                if *event == FocusEvent::FocusOut {
+                    self.cancel_preedit();
+                    self.apply_trim_on_commit(platform_window);
+                }
                 self.has_focus.set(false);
                 self.hide_cursor();
                 platform_window.hide_virtual_keyboard();
@@ -494,7 +911,11 @@ fn render(
         backend: &mut &mut dyn ItemRenderer,
         self_rc: &ItemRc,
     ) -> RenderingResult {
-        (*backend).draw_text_input(self, self_rc);
+        if self.placeholder_visible() {
+            (*backend).draw_text_input_placeholder(self, self_rc);
+        } else {
+            (*backend).draw_text_input(self, self_rc);
+        }
         RenderingResult::ContinueRenderingChildren
     }
 }
@@ -506,6 +927,7 @@ impl ItemConsts for TextInput {
     > = TextInput::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TextCursorDirection {
     Forward,
     Backward,
@@ -522,25 +944,6 @@ pub enum TextCursorDirection {
     EndOfText,
 }
 
-impl core::convert::TryFrom<char> for TextCursorDirection {
-    type Error = ();
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        Ok(match value {
-            key_codes::LeftArrow => Self::Backward,
-            key_codes::RightArrow => Self::Forward,
-            key_codes::UpArrow => Self::PreviousLine,
-            key_codes::DownArrow => Self::NextLine,
-            // On macos this scrolls to the top or the bottom of the page
-            #[cfg(not(target_os = "macos"))]
-            key_codes::Home => Self::StartOfLine,
-            #[cfg(not(target_os = "macos"))]
-            key_codes::End => Self::EndOfLine,
-            _ => return Err(()),
-        })
-    }
-}
-
 enum AnchorMode {
     KeepAnchor,
     MoveAnchor,
@@ -558,7 +961,22 @@ fn from(modifiers: KeyboardModifiers) -> Self {
 
 impl TextInput {
     fn show_cursor(&self, platform_window: &Rc<dyn PlatformWindow>) {
-        platform_window.window().window_handle().set_cursor_blink_binding(&self.cursor_visible);
+        let window = platform_window.window().window_handle();
+        // The caret is hidden while the focused TextInput is disabled, and comes back if it still
+        // has the focus when it's enabled again.
+        let focus_item = window
+            .focus_item()
+            .filter(|item| {
+                item.downcast::<TextInput>()
+                    .map_or(false, |input| core::ptr::eq(input.as_pin_ref().get_ref(), self))
+            })
+            .map(|item| item.downgrade())
+            .unwrap_or_default();
+        window.set_cursor_blink_binding(&self.cursor_visible, move || {
+            focus_item.upgrade().map_or(true, |item| {
+                item.downcast::<TextInput>().map_or(true, |input| input.as_pin_ref().enabled())
+            })
+        });
     }
 
     fn hide_cursor(&self) {
@@ -572,6 +990,7 @@ fn move_cursor(
         anchor_mode: AnchorMode,
         platform_window: &Rc<dyn PlatformWindow>,
     ) -> bool {
+        self.clear_secondary_cursors();
         let text = self.text();
         if text.is_empty() {
             return false;
@@ -590,6 +1009,7 @@ fn move_cursor(
                 " ",
                 None,
                 platform_window.window().scale_factor().get(),
+                TextMeasureRules::default(),
             )
             .height;
 
@@ -606,23 +1026,23 @@ fn move_cursor(
                 reset_preferred_x_pos = false;
 
                 let cursor_rect =
-                    renderer.text_input_cursor_rect_for_byte_offset(self, last_cursor_pos);
+                    self.cursor_rect_for_byte_offset(last_cursor_pos, platform_window);
                 let mut cursor_xy_pos = cursor_rect.center();
 
                 cursor_xy_pos.y += font_height;
                 cursor_xy_pos.x = self.preferred_x_pos.get();
-                renderer.text_input_byte_offset_for_position(self, cursor_xy_pos)
+                self.byte_offset_for_position(cursor_xy_pos, platform_window)
             }
             TextCursorDirection::PreviousLine => {
                 reset_preferred_x_pos = false;
 
                 let cursor_rect =
-                    renderer.text_input_cursor_rect_for_byte_offset(self, last_cursor_pos);
+                    self.cursor_rect_for_byte_offset(last_cursor_pos, platform_window);
                 let mut cursor_xy_pos = cursor_rect.center();
 
                 cursor_xy_pos.y -= font_height;
                 cursor_xy_pos.x = self.preferred_x_pos.get();
-                renderer.text_input_byte_offset_for_position(self, cursor_xy_pos)
+                self.byte_offset_for_position(cursor_xy_pos, platform_window)
             }
             TextCursorDirection::PreviousCharacter => {
                 let mut i = last_cursor_pos;
@@ -654,19 +1074,19 @@ fn move_cursor(
             }
             TextCursorDirection::StartOfLine => {
                 let cursor_rect =
-                    renderer.text_input_cursor_rect_for_byte_offset(self, last_cursor_pos);
+                    self.cursor_rect_for_byte_offset(last_cursor_pos, platform_window);
                 let mut cursor_xy_pos = cursor_rect.center();
 
                 cursor_xy_pos.x = 0 as Coord;
-                renderer.text_input_byte_offset_for_position(self, cursor_xy_pos)
+                self.byte_offset_for_position(cursor_xy_pos, platform_window)
             }
             TextCursorDirection::EndOfLine => {
                 let cursor_rect =
-                    renderer.text_input_cursor_rect_for_byte_offset(self, last_cursor_pos);
+                    self.cursor_rect_for_byte_offset(last_cursor_pos, platform_window);
                 let mut cursor_xy_pos = cursor_rect.center();
 
                 cursor_xy_pos.x = Coord::MAX;
-                renderer.text_input_byte_offset_for_position(self, cursor_xy_pos)
+                self.byte_offset_for_position(cursor_xy_pos, platform_window)
             }
             TextCursorDirection::StartOfParagraph => text
                 .as_bytes()
@@ -712,29 +1132,76 @@ fn set_cursor_position(
     ) {
         self.cursor_position.set(new_position);
         if new_position >= 0 {
-            let pos = platform_window
-                .renderer()
-                .text_input_cursor_rect_for_byte_offset(self, new_position as usize)
-                .origin;
+            let cursor_rect =
+                self.cursor_rect_for_byte_offset(new_position as usize, platform_window);
+            let pos = cursor_rect.origin;
             if reset_preferred_x_pos {
                 self.preferred_x_pos.set(pos.x);
             }
+            if self.has_focus() && self.preedit_text().is_empty() {
+                platform_window
+                    .window()
+                    .window_handle()
+                    .set_ime_cursor_area_of_focus_item(cursor_rect);
+            } else if self.has_focus() {
+                self.report_ime_cursor_area(platform_window);
+            }
             Self::FIELD_OFFSETS.cursor_position_changed.apply_pin(self).call(&(pos,));
         }
     }
 
+    /// Sets the selected text as the primary selection of the platform, if `sync_primary_selection`
+    /// is set. The text of a password field is never shared. This is called once a selection change
+    /// is complete, such as when the mouse button that selected the text is released, and not for
+    /// every cursor move while dragging.
+    fn update_primary_selection(self: Pin<&Self>) {
+        if !self.sync_primary_selection() || self.input_type() == InputType::Password {
+            return;
+        }
+        let (anchor, cursor) = self.selection_anchor_and_cursor();
+        if anchor == cursor {
+            return;
+        }
+        let text = self.text();
+        crate::platform::PLAFTORM_ABSTRACTION_INSTANCE.with(|p| {
+            if let Some(backend) = p.get() {
+                backend.set_primary_selection_text(&text[anchor..cursor]);
+            }
+        });
+    }
+
+    fn report_ime_cursor_area(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        let cursor_position = self.cursor_position();
+        if cursor_position >= 0 {
+            // While composing, the cursor is within the pre-edit text
+            let cursor_rect = match self.preedit_range() {
+                Some(preedit_range) => {
+                    platform_window.renderer().text_input_cursor_rect_for_byte_offset(
+                        self,
+                        preedit_range.start + self.preedit_cursor_position(),
+                    )
+                }
+                None => self.cursor_rect_for_byte_offset(cursor_position as usize, platform_window),
+            };
+            platform_window.window().window_handle().set_ime_cursor_area_of_focus_item(cursor_rect);
+        }
+    }
+
     fn select_and_delete(
         self: Pin<&Self>,
         step: TextCursorDirection,
         platform_window: &Rc<dyn PlatformWindow>,
     ) {
-        if !self.has_selection() {
-            self.move_cursor(step, AnchorMode::KeepAnchor, platform_window);
-        }
-        self.delete_selection(platform_window);
+        self.edit_with_undo(None, || {
+            if !self.has_selection() {
+                self.move_cursor(step, AnchorMode::KeepAnchor, platform_window);
+            }
+            self.delete_selection(platform_window);
+        });
     }
 
     fn delete_selection(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        self.clear_secondary_cursors();
         let text: String = self.text().into();
         if text.is_empty() {
             return;
@@ -755,14 +1222,34 @@ fn delete_selection(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>)
     // Avoid accessing self.cursor_position()/self.anchor_position() directly, always
     // use this bounds-checking function.
     pub fn selection_anchor_and_cursor(self: Pin<&Self>) -> (usize, usize) {
-        let max_pos = self.text().len() as i32;
-        let cursor_pos = self.cursor_position().max(0).min(max_pos);
-        let anchor_pos = self.anchor_position().max(0).min(max_pos);
+        let text = self.text();
+        let cursor_pos = previous_char_boundary(&text, self.cursor_position().max(0) as usize);
+        let anchor_pos = previous_char_boundary(&text, self.anchor_position().max(0) as usize);
 
         if anchor_pos > cursor_pos {
-            (cursor_pos as _, anchor_pos as _)
+            (cursor_pos, anchor_pos)
         } else {
-            (anchor_pos as _, cursor_pos as _)
+            (anchor_pos, cursor_pos)
+        }
+    }
+
+    /// The `text` property can be set programmatically, without going through the functions of
+    /// the TextInput, which doesn't emit `edited`. Then the cursor and the anchor may be past the
+    /// end of the new text, or within a character. This moves them back to a valid position
+    /// before they are used to edit the text.
+    fn revalidate_cursor_and_anchor(self: Pin<&Self>) {
+        let text = self.text();
+        let cursor_pos = self.cursor_position();
+        if cursor_pos >= 0 {
+            let valid_pos = previous_char_boundary(&text, cursor_pos as usize) as i32;
+            if valid_pos != cursor_pos {
+                self.cursor_position.set(valid_pos);
+            }
+        }
+        let anchor_pos = self.anchor_position();
+        let valid_pos = previous_char_boundary(&text, anchor_pos.max(0) as usize) as i32;
+        if valid_pos != anchor_pos {
+            self.anchor_position.set(valid_pos);
         }
     }
 
@@ -771,77 +1258,1821 @@ pub fn has_selection(self: Pin<&Self>) -> bool {
         anchor_pos != cursor_pos
     }
 
+    /// Returns `text` without the characters that are not allowed by the `allowed_characters`
+    /// property or by the `input_type`. A decimal input keeps a single decimal point in the text
+    /// once the selection is replaced.
+    fn filter_allowed_characters<'a>(self: Pin<&Self>, text: &'a str) -> Cow<'a, str> {
+        let allowed = self.allowed_characters();
+        let input_type = self.input_type();
+        if allowed.is_empty() && !matches!(input_type, InputType::Number | InputType::Decimal) {
+            return Cow::Borrowed(text);
+        }
+        let mut decimal_point_allowed = input_type == InputType::Decimal && {
+            let (selection_start, selection_end) = self.selection_anchor_and_cursor();
+            let current_text = self.text();
+            // With several cursors, the decimal point would be inserted more than once
+            self.secondary_cursors().is_empty()
+                && !current_text[..selection_start].contains('.')
+                && !current_text[selection_end..].contains('.')
+        };
+        let mut is_allowed = |ch: char| {
+            (allowed.is_empty() || is_character_allowed(&allowed, ch))
+                && match input_type {
+                    InputType::Number => ch.is_ascii_digit(),
+                    InputType::Decimal => {
+                        ch.is_ascii_digit()
+                            || (ch == '.' && core::mem::take(&mut decimal_point_allowed))
+                    }
+                    _ => true,
+                }
+        };
+        let filtered: String = text.chars().filter(|ch| is_allowed(*ch)).collect();
+        if filtered.len() == text.len() {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(filtered)
+        }
+    }
+
+    /// Returns the longest prefix of `text` that can be inserted at each cursor without the text
+    /// exceeding `max_length` characters, once the selection is replaced.
+    fn clamp_to_max_length<'a>(self: Pin<&Self>, text: Cow<'a, str>) -> Cow<'a, str> {
+        let max_length = self.max_length();
+        if max_length <= 0 {
+            return text;
+        }
+        let (selection_start, selection_end) = self.selection_anchor_and_cursor();
+        let current_text = self.text();
+        let remaining_length = current_text[..selection_start].chars().count()
+            + current_text[selection_end..].chars().count();
+        let cursor_count = self.secondary_cursors().len() + 1;
+        let fitting_length = (max_length as usize).saturating_sub(remaining_length) / cursor_count;
+        match text.char_indices().nth(fitting_length) {
+            None => text,
+            Some((end, _)) => match text {
+                Cow::Borrowed(text) => Cow::Borrowed(&text[..end]),
+                Cow::Owned(mut text) => {
+                    text.truncate(end);
+                    Cow::Owned(text)
+                }
+            },
+        }
+    }
+
     fn insert(self: Pin<&Self>, text_to_insert: &str, platform_window: &Rc<dyn PlatformWindow>) {
+        let text_to_insert = self.filter_allowed_characters(text_to_insert);
+        if text_to_insert.is_empty() {
+            return;
+        }
+        let text_to_insert = if text_to_insert.contains('\n') && self.single_line() {
+            Cow::Owned(text_to_insert.replace('\n', " "))
+        } else {
+            text_to_insert
+        };
+        let text_to_insert = self.clamp_to_max_length(text_to_insert);
+        if text_to_insert.is_empty() {
+            return;
+        }
+        // The selection is deleted first, which moves the secondary cursors after it
+        let (selection_start, selection_end) = self.selection_anchor_and_cursor();
+        let secondary_cursors: Vec<usize> = self
+            .secondary_cursors()
+            .into_iter()
+            .map(|pos| {
+                if pos >= selection_end {
+                    pos - (selection_end - selection_start)
+                } else {
+                    pos.min(selection_start)
+                }
+            })
+            .collect();
         self.delete_selection(platform_window);
         let mut text: String = self.text().into();
-        let cursor_pos = self.selection_anchor_and_cursor().1;
-        if text_to_insert.contains('\n') && self.single_line() {
-            text.insert_str(cursor_pos, &text_to_insert.replace('\n', " "));
-        } else {
-            text.insert_str(cursor_pos, text_to_insert);
+        // The cursor may have been set programmatically within a grapheme cluster
+        let cursor_pos = previous_grapheme_boundary(&text, self.selection_anchor_and_cursor().1);
+        let mut insert_positions = secondary_cursors.clone();
+        insert_positions.push(cursor_pos);
+        insert_positions.sort_unstable();
+        insert_positions.dedup();
+        // Insert from the last position, so that the positions before it stay valid
+        for pos in insert_positions.iter().rev() {
+            text.insert_str(*pos, &text_to_insert);
         }
-        let cursor_pos = cursor_pos + text_to_insert.len();
+        // Each cursor moves past the text inserted at it and at all the cursors before it. The
+        // inserted text may join a grapheme cluster after it, such as a letter followed by a
+        // combining mark, so the cursor moves past that cluster too.
+        let moved_position = |pos: usize| {
+            next_grapheme_boundary(
+                &text,
+                pos + insert_positions.iter().take_while(|p| **p <= pos).count()
+                    * text_to_insert.len(),
+            )
+        };
+        let cursor_pos = moved_position(cursor_pos);
+        let secondary_cursors: SharedVector<i32> =
+            secondary_cursors.into_iter().map(|pos| moved_position(pos) as i32).collect();
         self.text.set(text.into());
+        self.secondary_cursor_positions.set(secondary_cursors);
         self.anchor_position.set(cursor_pos as i32);
         self.set_cursor_position(cursor_pos as i32, true, platform_window);
         Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
-    fn select_all(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
-        self.move_cursor(TextCursorDirection::StartOfText, AnchorMode::MoveAnchor, platform_window);
-        self.move_cursor(TextCursorDirection::EndOfText, AnchorMode::KeepAnchor, platform_window);
-    }
-
-    fn copy(self: Pin<&Self>) {
-        let (anchor, cursor) = self.selection_anchor_and_cursor();
-        if anchor == cursor {
+    /// Removes the surrounding whitespace from the text if `trim_on_commit` is set. Called when
+    /// the text is committed, that is when the focus is lost or Enter is pressed.
+    fn apply_trim_on_commit(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        if !self.trim_on_commit() || self.read_only() {
             return;
         }
         let text = self.text();
-        crate::platform::PLAFTORM_ABSTRACTION_INSTANCE.with(|p| {
-            if let Some(backend) = p.get() {
-                backend.set_clipboard_text(&text[anchor..cursor]);
-            }
+        let trimmed_end = text.trim_end();
+        let (start, trimmed) = if self.trim_trailing_only() {
+            (0, trimmed_end)
+        } else {
+            let trimmed = trimmed_end.trim_start();
+            (trimmed_end.len() - trimmed.len(), trimmed)
+        };
+        if trimmed.len() == text.len() {
+            return;
+        }
+        let adjust_position =
+            |pos: i32| (pos.max(0) as usize).saturating_sub(start).min(trimmed.len()) as i32;
+        let anchor = adjust_position(self.anchor_position());
+        let cursor = adjust_position(self.cursor_position());
+        self.edit_with_undo(None, || {
+            self.clear_secondary_cursors();
+            self.text.set(trimmed.into());
+            self.anchor_position.set(anchor);
+            self.set_cursor_position(cursor, true, platform_window);
+            Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
         });
     }
 
-    fn paste(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
-        if let Some(text) = crate::platform::PLAFTORM_ABSTRACTION_INSTANCE
-            .with(|p| p.get().and_then(|p| p.clipboard_text()))
-        {
-            self.insert(&text, platform_window);
+    /// Gives the keyboard focus to this text input, if it doesn't have it yet, and selects the text
+    /// between the byte offsets `anchor` and `cursor`. The offsets are moved back to the closest
+    /// grapheme boundary.
+    pub fn focus_and_select(
+        self: Pin<&Self>,
+        self_rc: &ItemRc,
+        anchor: usize,
+        cursor: usize,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        if !self.has_focus() {
+            platform_window.window().window_handle().set_focus_item(self_rc);
         }
+        self.clear_secondary_cursors();
+        let text = self.text();
+        self.anchor_position.set(previous_grapheme_boundary(&text, anchor) as i32);
+        self.set_cursor_position(
+            previous_grapheme_boundary(&text, cursor) as i32,
+            true,
+            platform_window,
+        );
+        self.update_primary_selection();
+        self.show_cursor(platform_window);
     }
 
-    pub fn font_request(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) -> FontRequest {
-        let window_item = platform_window.window().window_handle().window_item();
+    /// Moves the cursor to the byte offset `offset`, moved back to the closest grapheme boundary,
+    /// and keeps the anchor, so that the selection is extended or shrunk up to that offset.
+    ///
+    /// The preferred horizontal position for moving to the next or previous line is the new
+    /// cursor position. Moving vertically with shift and the arrow keys uses `move_cursor`
+    /// instead, which keeps it.
+    pub fn extend_selection_to(
+        self: Pin<&Self>,
+        offset: usize,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        self.clear_secondary_cursors();
+        let text = self.text();
+        self.set_cursor_position(
+            previous_grapheme_boundary(&text, offset) as i32,
+            true,
+            platform_window,
+        );
+        self.update_primary_selection();
+        self.show_cursor(platform_window);
+    }
 
-        FontRequest {
-            family: {
-                let maybe_family = self.font_family();
-                if !maybe_family.is_empty() {
-                    Some(maybe_family)
-                } else {
-                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_family())
-                }
-            },
-            weight: {
-                let weight = self.font_weight();
-                if weight == 0 {
-                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_weight())
-                } else {
-                    Some(weight)
-                }
-            },
-            pixel_size: {
-                let font_size = self.font_size();
-                if font_size == 0 as Coord {
-                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_size())
+    /// Drops the text dragged from the selection at `position`, in window coordinates, into the
+    /// text input there. The text is moved, or copied if Control is held. Dropping it within the
+    /// dragged text does nothing.
+    fn drop_dragged_text(
+        self: Pin<&Self>,
+        drag: TextDrag,
+        position: Point,
+        self_rc: &ItemRc,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        // The text or the input type may have been changed programmatically while dragging
+        if self.text().get(drag.range.clone()) != Some(drag.text.as_str())
+            || self.input_type() == InputType::Password
+        {
+            return;
+        }
+        let window = platform_window.window().window_handle();
+        let target_rc = match text_input_at(window, position) {
+            Some(target_rc) => target_rc,
+            None => return,
+        };
+        let target_origin = target_rc.map_to_window(target_rc.geometry().origin);
+        let copy = window.keyboard_modifiers().control;
+        if target_rc == *self_rc {
+            let drop_offset = self
+                .byte_offset_for_position(position - target_origin.to_vector(), platform_window);
+            if self.read_only() || (drag.range.start..=drag.range.end).contains(&drop_offset) {
+                return;
+            }
+            self.edit_batch(platform_window, |editor| {
+                let insert_offset = if copy {
+                    drop_offset
                 } else {
-                    Some(font_size)
-                }
-            },
-            letter_spacing: Some(self.letter_spacing()),
+                    editor.delete(drag.range.clone());
+                    moved_text_offset(drag.range.clone(), drop_offset)
+                };
+                editor.insert(insert_offset, &drag.text);
+                editor.anchor_position = insert_offset;
+                editor.cursor_position = insert_offset + drag.text.len();
+            });
+        } else {
+            let target = target_rc.downcast::<TextInput>().unwrap();
+            let target = target.as_pin_ref();
+            if !target.enabled() || target.read_only() {
+                return;
+            }
+            let drop_offset = target
+                .byte_offset_for_position(position - target_origin.to_vector(), platform_window);
+            if target.insert_dropped_text(&drag.text, drop_offset, platform_window)
+                && !copy
+                && !self.read_only()
+            {
+                self.edit_batch(platform_window, |editor| editor.delete(drag.range));
+            }
+            window.set_focus_item(&target_rc);
         }
     }
+
+    /// Inserts the `text` dropped at the byte `offset`, and selects it. The text is filtered like
+    /// pasted text. Returns true if any text was inserted.
+    fn insert_dropped_text(
+        self: Pin<&Self>,
+        text: &str,
+        offset: usize,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> bool {
+        self.revalidate_cursor_and_anchor();
+        let offset = previous_char_boundary(&self.text(), offset);
+        self.clear_secondary_cursors();
+        self.as_ref().anchor_position.set(offset as i32);
+        self.set_cursor_position(offset as i32, true, platform_window);
+        let text_before = self.text();
+        let mut inserted = false;
+        self.edit_with_undo(None, || {
+            self.insert(text, platform_window);
+            inserted = self.text() != text_before;
+            if inserted {
+                self.as_ref().anchor_position.set(offset as i32);
+            }
+        });
+        inserted
+    }
+
+    /// Remembers the key press `event` that moved the cursor, and moves the cursor again at the
+    /// key repeat rate until the key is released, on the platforms that don't repeat the key
+    /// presses while a key is held down.
+    fn hold_key(self: Pin<&Self>, event: &KeyEvent, platform_window: &Rc<dyn PlatformWindow>) {
+        *self.data.held_key.borrow_mut() = Some(event.clone());
+        let window = platform_window.window().window_handle();
+        let focus_item = window
+            .focus_item()
+            .filter(|item| {
+                item.downcast::<TextInput>()
+                    .map_or(false, |input| core::ptr::eq(input.as_pin_ref().get_ref(), &*self))
+            })
+            .map(|item| item.downgrade())
+            .unwrap_or_default();
+        let platform_window_weak = Rc::downgrade(platform_window);
+        window.start_key_repeat(move || {
+            let platform_window = match platform_window_weak.upgrade() {
+                Some(platform_window) => platform_window,
+                None => return,
+            };
+            let held = focus_item.upgrade().map_or(false, |item| {
+                item.downcast::<TextInput>()
+                    .map_or(false, |input| input.as_pin_ref().repeat_held_key(&platform_window))
+            });
+            if !held {
+                platform_window.window().window_handle().stop_key_repeat();
+            }
+        });
+    }
+
+    /// Moves the cursor again like the key that is held down. Returns false if no key is held
+    /// down anymore, or if this text input lost the focus or is disabled.
+    fn repeat_held_key(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) -> bool {
+        let held_key = match self.data.held_key.borrow().clone() {
+            Some(key) if self.has_focus() && self.enabled() => key,
+            _ => return false,
+        };
+        if let Some(TextShortcut::Move(direction)) = held_key.text_shortcut() {
+            self.move_cursor(direction, held_key.modifiers.into(), platform_window);
+            self.update_primary_selection();
+        }
+        true
+    }
+
+    /// Returns the byte range of the selected text. It is empty, at the cursor, if nothing is selected.
+    pub fn selection(self: Pin<&Self>) -> core::ops::Range<usize> {
+        let (start, end) = self.selection_anchor_and_cursor();
+        start..end
+    }
+
+    /// Selects the text between the byte offsets `start` and `end`, which are moved back to the
+    /// closest character boundary. The anchor is placed at `start` and the cursor at `end`, which
+    /// may be before `start`. Unlike `focus_and_select`, this doesn't change the focus.
+    pub fn set_selection(
+        self: Pin<&Self>,
+        start: usize,
+        end: usize,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        self.clear_secondary_cursors();
+        let text = self.text();
+        self.anchor_position.set(previous_char_boundary(&text, start) as i32);
+        self.set_cursor_position(previous_char_boundary(&text, end) as i32, true, platform_window);
+        self.update_primary_selection();
+        if self.has_focus() {
+            self.show_cursor(platform_window);
+        }
+    }
+
+    /// Returns the area of this text input that is visible, in its own coordinates. The text
+    /// input is usually scrolled within its parent item, which clips it.
+    fn visible_rect(self: Pin<&Self>, self_rc: &ItemRc) -> Rect {
+        let size =
+            self_rc.parent_item().map_or_else(|| self.geometry().size, |p| p.geometry().size);
+        Rect::new(Point::new(-self.x(), -self.y()), size)
+    }
+
+    /// Starts or stops scrolling the selection, depending on whether the mouse, at `position`,
+    /// selects text past the visible area.
+    fn update_selection_autoscroll(
+        self: Pin<&Self>,
+        position: Point,
+        self_rc: &ItemRc,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        let window = platform_window.window().window_handle();
+        let step = autoscroll_step(
+            position,
+            self.visible_rect(self_rc),
+            self.autoscroll_margin(),
+            self.autoscroll_speed(),
+        );
+        if step == euclid::vec2(0 as Coord, 0 as Coord) {
+            window.stop_selection_autoscroll();
+            return;
+        }
+        // The text input moves within its parent while scrolling, but the mouse doesn't
+        let position_in_parent = position + euclid::vec2(self.x(), self.y());
+        let self_weak = self_rc.downgrade();
+        let platform_window_weak = Rc::downgrade(platform_window);
+        window.start_selection_autoscroll(move || {
+            if let (Some(self_rc), Some(platform_window)) =
+                (self_weak.upgrade(), platform_window_weak.upgrade())
+            {
+                if let Some(text_input) = ItemRef::downcast_pin::<TextInput>(self_rc.borrow()) {
+                    text_input.autoscroll_selection(position_in_parent, &self_rc, &platform_window);
+                }
+            }
+        });
+    }
+
+    /// Extends the selection past the cursor by one auto-scroll step, so that the scroll
+    /// container, which keeps the cursor visible, scrolls.
+    fn autoscroll_selection(
+        self: Pin<&Self>,
+        position_in_parent: Point,
+        self_rc: &ItemRc,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        let position = position_in_parent - euclid::vec2(self.x(), self.y());
+        let step = autoscroll_step(
+            position,
+            self.visible_rect(self_rc),
+            self.autoscroll_margin(),
+            self.autoscroll_speed(),
+        );
+        if !self.pressed.get() || step == euclid::vec2(0 as Coord, 0 as Coord) {
+            platform_window.window().window_handle().stop_selection_autoscroll();
+            return;
+        }
+        let cursor_pos =
+            previous_char_boundary(&self.text(), self.cursor_position().max(0) as usize);
+        let cursor_rect = self.cursor_rect_for_byte_offset(cursor_pos, platform_window);
+        let offset = self.byte_offset_for_position(cursor_rect.center() + step, platform_window);
+        self.drag_selection_to(offset, platform_window);
+    }
+
+    /// Extends the selection to the byte `offset` reached by dragging the mouse. After a double or
+    /// a triple click, the selection is extended by whole words or lines.
+    fn drag_selection_to(
+        self: Pin<&Self>,
+        offset: usize,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        match self.data.selection_drag_unit.get() {
+            Some((granularity, start, end)) => {
+                let (anchor, cursor) =
+                    extend_selection_by_units(&self.text(), start..end, offset, granularity);
+                self.as_ref().anchor_position.set(anchor as i32);
+                self.extend_selection_to(cursor, platform_window);
+            }
+            None => self.extend_selection_to(offset, platform_window),
+        }
+    }
+
+    /// Adds cursors at the byte offsets `positions`, besides the cursor at `cursor_position`.
+    /// The text that is typed or pasted is inserted at all the cursors. The offsets are moved
+    /// back to the closest grapheme boundary, and replace the previous secondary cursors.
+    ///
+    /// Moving the cursor, clicking or deleting text removes the secondary cursors.
+    ///
+    /// Only the Qt and FemtoVG renderers draw the secondary cursors: the Skia and software
+    /// renderers don't draw text inputs yet.
+    pub fn set_secondary_cursors(self: Pin<&Self>, positions: &[usize]) {
+        let text = self.text();
+        let cursor_pos = self.selection_anchor_and_cursor().1;
+        let mut positions: Vec<usize> = positions
+            .iter()
+            .map(|pos| previous_grapheme_boundary(&text, *pos))
+            .filter(|pos| *pos != cursor_pos)
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        self.secondary_cursor_positions.set(positions.into_iter().map(|pos| pos as i32).collect());
+    }
+
+    /// Returns the byte offsets of the secondary cursors, in increasing order, without the
+    /// offset of the primary cursor.
+    pub fn secondary_cursors(self: Pin<&Self>) -> Vec<usize> {
+        let text = self.text();
+        let cursor_pos = self.selection_anchor_and_cursor().1;
+        // The text may have been changed programmatically since the cursors were set
+        let mut positions: Vec<usize> = self
+            .secondary_cursor_positions()
+            .iter()
+            .map(|pos| previous_char_boundary(&text, (*pos).max(0) as usize))
+            .filter(|pos| *pos != cursor_pos)
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+
+    /// Removes the secondary cursors, leaving only the cursor at `cursor_position`.
+    pub fn clear_secondary_cursors(self: Pin<&Self>) {
+        self.secondary_cursor_positions.set(Default::default());
+    }
+
+    /// Returns the rectangles of the carets at the secondary cursors, which the renderers fill
+    /// with the text color. Returns an empty Vec when the cursor is not visible.
+    pub fn secondary_cursor_rects(
+        self: Pin<&Self>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> Vec<Rect> {
+        if !self.cursor_visible() || !self.enabled() || self.read_only() {
+            return Vec::new();
+        }
+        self.secondary_cursors()
+            .into_iter()
+            .map(|pos| {
+                let cursor_rect = self.cursor_rect_for_byte_offset(pos, platform_window);
+                Rect::new(
+                    cursor_rect.origin,
+                    Size::new(self.text_cursor_width(), cursor_rect.height()),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the selection handles, below the anchor and the cursor, when they are shown: while
+    /// the text input has the focus and a selection, after it was pressed with a touch screen.
+    fn selection_handles(
+        self: Pin<&Self>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> Vec<(SelectionHandle, Rect)> {
+        if !self.selection_handles_shown() || !self.has_focus() || !self.has_selection() {
+            return Vec::new();
+        }
+        let handle_rect = |pos: i32| {
+            let cursor_rect =
+                self.cursor_rect_for_byte_offset(pos.max(0) as usize, platform_window);
+            let size = SELECTION_HANDLE_SIZE as Coord;
+            Rect::new(
+                Point::new(cursor_rect.min_x() - size / 2 as Coord, cursor_rect.max_y()),
+                Size::new(size, size),
+            )
+        };
+        alloc::vec![
+            (SelectionHandle::Anchor, handle_rect(self.anchor_position())),
+            (SelectionHandle::Cursor, handle_rect(self.cursor_position())),
+        ]
+    }
+
+    /// Returns the rectangles of the selection handles, which the renderers fill with the
+    /// `selection_background_color`. Returns an empty Vec when the handles are not shown, which is
+    /// unless the text input was last pressed with a touch screen and has a selection.
+    pub fn selection_handle_rects(
+        self: Pin<&Self>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> Vec<Rect> {
+        self.selection_handles(platform_window).into_iter().map(|(_, rect)| rect).collect()
+    }
+
+    /// Moves the end of the selection that belongs to `handle` to the text at `position`. It snaps
+    /// to the start of the word under the position when it is the start of the selection, and to
+    /// the end of that word otherwise.
+    fn drag_selection_handle(
+        self: Pin<&Self>,
+        handle: SelectionHandle,
+        position: Point,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        let text = self.text();
+        let offset = self.byte_offset_for_position(position, platform_window);
+        let other_end = match handle {
+            SelectionHandle::Anchor => self.cursor_position(),
+            SelectionHandle::Cursor => self.anchor_position(),
+        };
+        let offset = snap_to_word_boundary(&text, offset, (offset as i32) < other_end.max(0))
+            .min(text.len());
+        let offset = previous_grapheme_boundary(&text, offset) as i32;
+        self.clear_secondary_cursors();
+        match handle {
+            SelectionHandle::Anchor => self.anchor_position.set(offset),
+            SelectionHandle::Cursor => self.set_cursor_position(offset, true, platform_window),
+        }
+        self.show_cursor(platform_window);
+    }
+
+    /// Sets whether this text input ignores the standard `shortcut`, so that the key event is
+    /// propagated to the parent items instead of being handled here. For example, ignoring
+    /// [`StandardShortcut::SelectAll`] lets an enclosing `FocusScope` handle Ctrl+A.
+    /// By default, no shortcut is ignored.
+    pub fn set_shortcut_ignored(&self, shortcut: StandardShortcut, ignored: bool) {
+        let bit = 1 << shortcut as u16;
+        let ignored_shortcuts = self.ignored_shortcuts.get();
+        self.ignored_shortcuts.set(if ignored {
+            ignored_shortcuts | bit
+        } else {
+            ignored_shortcuts & !bit
+        });
+    }
+
+    /// Returns true if the standard `shortcut` was ignored with [`Self::set_shortcut_ignored`].
+    pub fn is_shortcut_ignored(&self, shortcut: StandardShortcut) -> bool {
+        self.ignored_shortcuts.get() & (1 << shortcut as u16) != 0
+    }
+
+    /// Shows `text` as the pre-edit text of an input method, at the cursor, with the cursor at the
+    /// byte offset `cursor` within it, or at its end if None. The text itself doesn't change until
+    /// [`Self::commit_preedit`]; an empty `text` cancels the composition.
+    pub fn set_preedit(
+        self: Pin<&Self>,
+        text: &str,
+        cursor: Option<usize>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        if self.read_only() {
+            return;
+        }
+        let cursor = cursor.map_or(text.len(), |cursor| previous_grapheme_boundary(text, cursor));
+        self.preedit_text.set(text.into());
+        self.preedit_cursor.set(cursor as i32);
+        self.show_cursor(platform_window);
+        if self.has_focus() {
+            self.report_ime_cursor_area(platform_window);
+        }
+    }
+
+    /// Inserts the pre-edit text at the cursor, replacing the selection, as if it was typed, and
+    /// ends the composition.
+    pub fn commit_preedit(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        let preedit_text = self.preedit_text();
+        self.cancel_preedit();
+        if !preedit_text.is_empty() {
+            self.edit_with_undo(Some(&preedit_text), || {
+                self.insert(&preedit_text, platform_window)
+            });
+            self.show_cursor(platform_window);
+        }
+    }
+
+    /// Ends the composition and removes the pre-edit text, without changing the text.
+    pub fn cancel_preedit(self: Pin<&Self>) {
+        self.preedit_text.set(Default::default());
+        self.preedit_cursor.set(0);
+    }
+
+    /// Returns the byte offset of the cursor within the `preedit_text`.
+    pub fn preedit_cursor_position(self: Pin<&Self>) -> usize {
+        previous_char_boundary(&self.preedit_text(), self.preedit_cursor().max(0) as usize)
+    }
+
+    /// Returns the text that the renderers draw: the text with the `preedit_text` inserted at the
+    /// cursor, and the byte range of the pre-edit text, which is underlined, if there is one. While
+    /// composing, the selection is not drawn and the cursor is drawn at
+    /// [`Self::preedit_cursor_position`] within the pre-edit text.
+    pub fn text_with_preedit(self: Pin<&Self>) -> (SharedString, Option<core::ops::Range<usize>>) {
+        let text = self.text();
+        let preedit_range = match self.preedit_range() {
+            Some(preedit_range) => preedit_range,
+            None => return (text, None),
+        };
+        let mut displayed = String::from(&text[..preedit_range.start]);
+        displayed.push_str(&self.preedit_text());
+        displayed.push_str(&text[preedit_range.start..]);
+        (displayed.into(), Some(preedit_range))
+    }
+
+    /// Returns the byte range of the pre-edit text in [`Self::text_with_preedit`], if composing.
+    fn preedit_range(self: Pin<&Self>) -> Option<core::ops::Range<usize>> {
+        let preedit_len = self.preedit_text().len();
+        (preedit_len > 0).then(|| {
+            let cursor =
+                previous_char_boundary(&self.text(), self.cursor_position().max(0) as usize);
+            cursor..cursor + preedit_len
+        })
+    }
+
+    /// Returns the byte offset in the text at `position`. The renderers lay out the text with the
+    /// pre-edit text, so a position within the pre-edit text is at the cursor.
+    fn byte_offset_for_position(
+        self: Pin<&Self>,
+        position: Point,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> usize {
+        let offset = platform_window.renderer().text_input_byte_offset_for_position(self, position);
+        match self.preedit_range() {
+            Some(preedit_range) if offset >= preedit_range.end => offset - preedit_range.len(),
+            Some(preedit_range) if offset > preedit_range.start => preedit_range.start,
+            _ => offset,
+        }
+    }
+
+    /// Returns the rectangle of the text cursor at the byte offset `offset` in the text, which is
+    /// after the pre-edit text if it is after the cursor.
+    fn cursor_rect_for_byte_offset(
+        self: Pin<&Self>,
+        offset: usize,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> Rect {
+        let offset = match self.preedit_range() {
+            Some(preedit_range) if offset > preedit_range.start => offset + preedit_range.len(),
+            _ => offset,
+        };
+        platform_window.renderer().text_input_cursor_rect_for_byte_offset(self, offset)
+    }
+
+    /// Moves the cursor or deletes within the pre-edit text, instead of the text, while composing.
+    fn apply_preedit_shortcut(
+        self: Pin<&Self>,
+        shortcut: TextShortcut,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        let mut preedit_text: String = self.preedit_text().into();
+        let cursor = self.preedit_cursor_position();
+        let next_grapheme = |pos: usize| {
+            preedit_text[pos..].graphemes(true).next().map_or(pos, |grapheme| pos + grapheme.len())
+        };
+        let previous_grapheme = |pos: usize| {
+            if pos == 0 {
+                0
+            } else {
+                previous_grapheme_boundary(&preedit_text, pos - 1)
+            }
+        };
+        let next_word_end = |pos: usize| {
+            preedit_text
+                .unicode_word_indices()
+                .find(|(offset, word)| offset + word.len() > pos)
+                .map_or(preedit_text.len(), |(offset, word)| offset + word.len())
+        };
+        let previous_word_start = |pos: usize| {
+            preedit_text
+                .unicode_word_indices()
+                .map(|(offset, _)| offset)
+                .take_while(|offset| *offset < pos)
+                .last()
+                .unwrap_or(0)
+        };
+        let (deleted, new_cursor) = match shortcut {
+            TextShortcut::Move(direction) => {
+                let new_cursor = match direction {
+                    TextCursorDirection::Forward => next_grapheme(cursor),
+                    TextCursorDirection::Backward | TextCursorDirection::PreviousCharacter => {
+                        previous_grapheme(cursor)
+                    }
+                    TextCursorDirection::ForwardByWord => next_word_end(cursor),
+                    TextCursorDirection::BackwardByWord => previous_word_start(cursor),
+                    TextCursorDirection::StartOfLine
+                    | TextCursorDirection::StartOfParagraph
+                    | TextCursorDirection::StartOfText => 0,
+                    TextCursorDirection::EndOfLine
+                    | TextCursorDirection::EndOfParagraph
+                    | TextCursorDirection::EndOfText => preedit_text.len(),
+                    TextCursorDirection::NextLine | TextCursorDirection::PreviousLine => cursor,
+                };
+                (None, new_cursor)
+            }
+            TextShortcut::DeleteForward => (Some(cursor..next_grapheme(cursor)), cursor),
+            TextShortcut::DeleteBackward => {
+                let start = previous_char_boundary(&preedit_text, cursor.saturating_sub(1));
+                (Some(start..cursor), start)
+            }
+            TextShortcut::DeleteWordForward => (Some(cursor..next_word_end(cursor)), cursor),
+            TextShortcut::DeleteWordBackward => {
+                let start = previous_word_start(cursor);
+                (Some(start..cursor), start)
+            }
+        };
+        if let Some(deleted) = deleted {
+            preedit_text.replace_range(deleted, "");
+            self.preedit_text.set(preedit_text.into());
+        }
+        self.preedit_cursor.set(new_cursor as i32);
+        self.show_cursor(platform_window);
+        self.report_ime_cursor_area(platform_window);
+    }
+
+    /// Returns the area in which the text is laid out, in the coordinates of the text input: its
+    /// geometry inset by the `border_width`.
+    pub fn text_rect(self: Pin<&Self>) -> Rect {
+        let border_width = self.border_width().max(0 as Coord);
+        let width = self.width() - border_width * 2 as Coord;
+        let height = self.height() - border_width * 2 as Coord;
+        Rect::new(
+            Point::new(border_width, border_width),
+            Size::new(width.max(0 as Coord), height.max(0 as Coord)),
+        )
+    }
+
+    /// Returns true if the renderers draw the `placeholder_text` instead of the text, which is
+    /// when the text is empty and the text input doesn't have the focus.
+    pub fn placeholder_visible(self: Pin<&Self>) -> bool {
+        self.text().is_empty() && !self.has_focus() && !self.placeholder_text().is_empty()
+    }
+
+    /// Returns true if the renderers draw the `suggestion_text` after the end of the text, with the
+    /// `suggestion_color`. That is when the text input has the focus and can be edited, and the
+    /// cursor is at the end of the text, without selection, pre-edit text or secondary cursors.
+    /// The suggestion is drawn after the last line and is not taken into account to lay out the text.
+    /// The Skia and software renderers, which don't draw text inputs yet, don't draw it either.
+    pub fn suggestion_visible(self: Pin<&Self>) -> bool {
+        if self.suggestion_text().is_empty()
+            || !self.has_focus()
+            || !self.enabled()
+            || self.read_only()
+            || self.input_type() == InputType::Password
+            || !self.preedit_text().is_empty()
+        {
+            return false;
+        }
+        let (anchor, cursor) = self.selection_anchor_and_cursor();
+        anchor == cursor && cursor == self.text().len() && self.secondary_cursors().is_empty()
+    }
+
+    /// Inserts the `suggestion_text` at the cursor, as if it was typed, if it is visible. Returns
+    /// true if it was inserted. The `suggestion_text` is not cleared: the application is expected
+    /// to update it when the text is `edited`.
+    pub fn accept_suggestion(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) -> bool {
+        if !self.suggestion_visible() {
+            return false;
+        }
+        let suggestion_text = self.suggestion_text();
+        self.edit_with_undo(Some(&suggestion_text), || {
+            self.insert(&suggestion_text, platform_window)
+        });
+        self.show_cursor(platform_window);
+        true
+    }
+
+    /// Removes one level of indentation from the start of each line that the selection touches:
+    /// a tab, or up to `tab_spaces` spaces, four if it is not set.
+    fn dedent_selected_lines(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        let (start, end) = self.selection_anchor_and_cursor();
+        let indent_width = match self.tab_spaces() {
+            spaces if spaces > 0 => spaces as usize,
+            _ => 4,
+        };
+        self.edit_batch(platform_window, |editor| {
+            let text = String::from(editor.text());
+            let first_line_start = text[..start].rfind('\n').map_or(0, |pos| pos + 1);
+            let line_starts: Vec<usize> = core::iter::once(first_line_start)
+                .chain(text[start..end].match_indices('\n').map(|(pos, _)| start + pos + 1))
+                .filter(|line_start| *line_start == first_line_start || *line_start < end)
+                .collect();
+            // Delete from the last line, so that the offsets before it stay valid
+            for line_start in line_starts.into_iter().rev() {
+                let line = &text[line_start..];
+                let indent_len = if line.starts_with('\t') {
+                    1
+                } else {
+                    line.bytes().take(indent_width).take_while(|byte| *byte == b' ').count()
+                };
+                editor.delete(line_start..line_start + indent_len);
+            }
+        });
+    }
+
+    /// Returns true if the key event is one of the keys that accept the suggestion, according to
+    /// the `suggestion_accept_key`.
+    fn is_suggestion_accept_key(self: Pin<&Self>, event: &KeyEvent) -> bool {
+        let modifiers = &event.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.meta || modifiers.shift {
+            return false;
+        }
+        let tab = event.text.starts_with(key_codes::Tab);
+        let right_arrow = event.text.starts_with(key_codes::RightArrow);
+        match self.suggestion_accept_key() {
+            SuggestionAcceptKey::TabOrRightArrow => tab || right_arrow,
+            SuggestionAcceptKey::Tab => tab,
+            SuggestionAcceptKey::RightArrow => right_arrow,
+        }
+    }
+
+    /// Returns the rectangle of the line that contains the cursor, which the renderers fill with the
+    /// `current_line_color` below the text. Returns None if the current line is not highlighted.
+    pub fn current_line_rect(
+        self: Pin<&Self>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) -> Option<Rect> {
+        if !self.highlight_current_line()
+            || (!self.has_focus() && !self.highlight_current_line_when_unfocused())
+        {
+            return None;
+        }
+        let cursor_pos = self.cursor_position().max(0).min(self.text().len() as i32) as usize;
+        let cursor_rect = self.cursor_rect_for_byte_offset(cursor_pos, platform_window);
+        let text_rect = self.text_rect();
+        Some(Rect::new(
+            Point::new(text_rect.origin.x, cursor_rect.origin.y),
+            Size::new(text_rect.width(), cursor_rect.height()),
+        ))
+    }
+
+    fn select_all(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        self.move_cursor(TextCursorDirection::StartOfText, AnchorMode::MoveAnchor, platform_window);
+        self.move_cursor(TextCursorDirection::EndOfText, AnchorMode::KeepAnchor, platform_window);
+        self.update_primary_selection();
+    }
+
+    fn copy(self: Pin<&Self>) {
+        let (anchor, cursor) = self.selection_anchor_and_cursor();
+        if anchor == cursor {
+            return;
+        }
+        let text = self.text();
+        crate::platform::PLAFTORM_ABSTRACTION_INSTANCE.with(|p| {
+            if let Some(backend) = p.get() {
+                backend.set_clipboard_text(&text[anchor..cursor]);
+            }
+        });
+        Self::FIELD_OFFSETS.copied.apply_pin(self).call(&(text[anchor..cursor].into(),));
+    }
+
+    /// Inserts the text that `clipboard_text` returns from the platform, such as the clipboard or
+    /// the primary selection.
+    fn paste(
+        self: Pin<&Self>,
+        clipboard_text: impl FnOnce(&dyn PlatformAbstraction) -> Option<String>,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        if let Some(text) = crate::platform::PLAFTORM_ABSTRACTION_INSTANCE
+            .with(|p| p.get().and_then(|p| clipboard_text(p.as_ref())))
+        {
+            self.edit_with_undo(None, || self.insert(&text, platform_window));
+        }
+    }
+
+    fn edit_state(self: Pin<&Self>) -> EditState {
+        EditState {
+            text: self.text(),
+            anchor_position: self.anchor_position(),
+            cursor_position: self.cursor_position(),
+        }
+    }
+
+    /// Runs `edit`, which changes the text, and records the state before it in the undo history.
+    /// `typed_text` is the text typed with the keyboard, which the history merges with the text
+    /// typed just before it into a single undo step, up to the start of a word.
+    fn edit_with_undo(self: Pin<&Self>, typed_text: Option<&str>, edit: impl FnOnce()) {
+        let before = self.edit_state();
+        let pushed = self.data.history.borrow_mut().begin_edit(before, typed_text);
+        edit();
+        self.data.history.borrow_mut().end_edit(self.edit_state(), pushed, typed_text.is_some());
+    }
+
+    /// Restores the text, anchor and cursor as they were before the last edit that was not undone.
+    /// Does nothing if there is no such edit, or if the text was changed externally since then.
+    pub fn undo(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        let state = self.data.history.borrow_mut().undo(self.edit_state());
+        if let Some(state) = state {
+            self.restore_edit_state(state, platform_window);
+        }
+    }
+
+    /// Applies again the last edit that was undone. Does nothing if there is no such edit, or if
+    /// the text was edited or changed externally since then.
+    pub fn redo(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) {
+        let state = self.data.history.borrow_mut().redo(self.edit_state());
+        if let Some(state) = state {
+            self.restore_edit_state(state, platform_window);
+        }
+    }
+
+    /// Calls `edit` with a [`TextInputEditor`] on the current text, and applies all the edits it makes at
+    /// once, as a single undo step. `edited` is emitted once at the end if the text changed, and no
+    /// callback is emitted for the intermediate states. The anchor and the cursor follow the edits, see
+    /// [`TextInputEditor::replace`]. Returns the value returned by `edit`.
+    pub fn edit_batch<R>(
+        self: Pin<&Self>,
+        platform_window: &Rc<dyn PlatformWindow>,
+        edit: impl FnOnce(&mut TextInputEditor) -> R,
+    ) -> R {
+        self.revalidate_cursor_and_anchor();
+        let mut editor = TextInputEditor {
+            text: self.text().into(),
+            anchor_position: self.anchor_position().max(0) as usize,
+            cursor_position: self.cursor_position().max(0) as usize,
+        };
+        let mut result = None;
+        self.edit_with_undo(None, || {
+            result = Some(edit(&mut editor));
+            if editor.text.as_str() != self.text().as_str() {
+                self.clear_secondary_cursors();
+                self.text.set(editor.text.as_str().into());
+                self.anchor_position.set(editor.anchor_position as i32);
+                self.set_cursor_position(editor.cursor_position as i32, true, platform_window);
+                Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+            }
+        });
+        result.unwrap()
+    }
+
+    fn restore_edit_state(
+        self: Pin<&Self>,
+        state: EditState,
+        platform_window: &Rc<dyn PlatformWindow>,
+    ) {
+        self.clear_secondary_cursors();
+        self.text.set(state.text);
+        self.anchor_position.set(state.anchor_position);
+        self.set_cursor_position(state.cursor_position, true, platform_window);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
+    /// Returns the font request for this text input, with the font properties that are not set on
+    /// this element resolved from the default font properties of the window.
+    pub fn font_request(self: Pin<&Self>, platform_window: &Rc<dyn PlatformWindow>) -> FontRequest {
+        let request = self.unresolved_font_request();
+        match platform_window.window().window_handle().window_item() {
+            Some(window_item) => request.merge(&window_item.as_pin_ref().default_font_request()),
+            None => request,
+        }
+    }
+
+    /// Returns the rules the renderers use to measure and lay out the text of this text input.
+    /// In a multi-line text input, a trailing newline starts an empty line where the cursor can go.
+    /// With `char-wrap`, the text breaks at any character.
+    pub fn measure_rules(self: Pin<&Self>) -> TextMeasureRules {
+        let rules = if self.single_line() {
+            TextMeasureRules::default()
+        } else {
+            TextMeasureRules::for_text_input()
+        };
+        TextMeasureRules { break_anywhere: self.wrap() == TextWrap::CharWrap, ..rules }
+    }
+
+    fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
+        FontRequest {
+            family: Some(self.font_family()).filter(|family| !family.is_empty()),
+            weight: Some(self.font_weight()).filter(|weight| *weight != 0),
+            pixel_size: Some(self.font_size()).filter(|size| *size != 0 as Coord),
+            letter_spacing: Some(self.letter_spacing()),
+        }
+    }
+}
+
+/// The width and height of the selection handles, in logical pixels.
+const SELECTION_HANDLE_SIZE: f32 = 16.;
+
+/// The selection end point that a selection handle moves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SelectionHandle {
+    Anchor,
+    Cursor,
+}
+
+/// The unit by which the selection is extended while dragging after a double or a triple click.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SelectionGranularity {
+    Word,
+    Line,
+}
+
+/// The distance the mouse must move after a press within the selection to start dragging the
+/// selected text.
+const TEXT_DRAG_DISTANCE: Coord = 5 as Coord;
+
+/// The selected text being dragged with the mouse, from the press within the selection.
+#[derive(Clone, Debug)]
+struct TextDrag {
+    /// The dragged text, and its byte range
+    text: SharedString,
+    range: core::ops::Range<usize>,
+    /// Where the button was pressed, and the byte offset there
+    press_position: Point,
+    press_offset: usize,
+    /// True once the mouse moved far enough from the press position
+    dragging: bool,
+}
+
+/// Returns the topmost visible TextInput of the window at `position`, in window coordinates.
+fn text_input_at(window: &WindowInner, position: Point) -> Option<ItemRc> {
+    let mut found = None;
+    crate::item_tree::visit_items(
+        &window.component(),
+        crate::item_tree::TraversalOrder::FrontToBack,
+        |component, item, index, offset| {
+            let geometry = item.as_ref().geometry().translate(*offset);
+            let item_rc = ItemRc::new(component.clone(), index);
+            if item_rc.downcast::<TextInput>().is_some()
+                && window.hit_test_rect(geometry).contains(position)
+                && item_rc.is_visible()
+            {
+                found = Some(item_rc);
+                return crate::item_tree::ItemVisitorResult::Abort;
+            }
+            crate::item_tree::ItemVisitorResult::Continue(geometry.origin.to_vector())
+        },
+        euclid::default::Vector2D::new(0 as Coord, 0 as Coord),
+    );
+    found
+}
+
+/// The maximum number of edits that can be undone.
+const MAX_UNDO_STEPS: usize = 100;
+
+/// The text, anchor and cursor of a TextInput, which undo and redo restore.
+#[derive(Clone, Debug)]
+struct EditState {
+    text: SharedString,
+    anchor_position: i32,
+    cursor_position: i32,
+}
+
+/// The text of a TextInput being edited in [`TextInput::edit_batch`], with the anchor and the
+/// cursor that follow the edits.
+pub struct TextInputEditor {
+    text: String,
+    anchor_position: usize,
+    cursor_position: usize,
+}
+
+impl TextInputEditor {
+    /// Returns the text with the edits made so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the byte offsets of the anchor and the cursor in the text edited so far.
+    pub fn anchor_and_cursor(&self) -> (usize, usize) {
+        (self.anchor_position, self.cursor_position)
+    }
+
+    /// Replaces the text in the byte `range` with `text`. The ends of the range are moved back to the
+    /// closest character boundary. Like when setting the `text` property, the text is not filtered
+    /// by `allowed-characters` or `max-length`.
+    ///
+    /// The anchor and the cursor after the range keep their place relative to the text after it,
+    /// and the ones within the range move to the end of the new text. When inserting, with an empty
+    /// range, the ones at the insertion point move after the inserted text.
+    pub fn replace(&mut self, range: core::ops::Range<usize>, text: &str) {
+        let end = previous_char_boundary(&self.text, range.end);
+        let start = previous_char_boundary(&self.text, range.start).min(end);
+        self.text.replace_range(start..end, text);
+        let moved_position = |pos: usize| {
+            if pos >= end {
+                pos - (end - start) + text.len()
+            } else if pos > start {
+                start + text.len()
+            } else {
+                pos
+            }
+        };
+        self.anchor_position = moved_position(self.anchor_position);
+        self.cursor_position = moved_position(self.cursor_position);
+    }
+
+    /// Inserts `text` at the byte `offset`, see [`Self::replace`].
+    pub fn insert(&mut self, offset: usize, text: &str) {
+        self.replace(offset..offset, text)
+    }
+
+    /// Deletes the text in the byte `range`, see [`Self::replace`].
+    pub fn delete(&mut self, range: core::ops::Range<usize>) {
+        self.replace(range, "")
+    }
+}
+
+#[derive(Default, Debug)]
+struct UndoHistory {
+    /// The states before the edits, the last edit at the end
+    undo_stack: Vec<EditState>,
+    /// The states before the undos, the last undo at the end
+    redo_stack: Vec<EditState>,
+    /// The text after the last edit, undo or redo. When the current text is different, it was
+    /// set externally, for example through a binding, and the history doesn't apply anymore.
+    text: Option<SharedString>,
+    /// The cursor position after the last typed text. The text typed next at this position
+    /// extends the same undo step.
+    typing_cursor_position: Option<i32>,
+}
+
+impl UndoHistory {
+    fn clear_if_changed_externally(&mut self, text: &SharedString) {
+        if self.text.as_ref() != Some(text) {
+            self.undo_stack.clear();
+            self.redo_stack.clear();
+            self.typing_cursor_position = None;
+            self.text = Some(text.clone());
+        }
+    }
+
+    /// Records the state `before` an edit. Returns false if the edit extends the last undo step
+    /// instead.
+    fn begin_edit(&mut self, before: EditState, typed_text: Option<&str>) -> bool {
+        self.clear_if_changed_externally(&before.text);
+        if let Some(typed_text) = typed_text {
+            let cursor =
+                previous_char_boundary(&before.text, before.cursor_position.max(0) as usize);
+            let starts_word = before.text[..cursor].ends_with(char::is_whitespace)
+                && !typed_text.starts_with(char::is_whitespace);
+            if self.typing_cursor_position == Some(before.cursor_position)
+                && !starts_word
+                && !self.undo_stack.is_empty()
+            {
+                return false;
+            }
+        }
+        self.undo_stack.push(before);
+        if self.undo_stack.len() > MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+        true
+    }
+
+    /// Completes the edit started with `begin_edit`, which resulted in the state `after`.
+    fn end_edit(&mut self, after: EditState, pushed: bool, typing: bool) {
+        if self.text.as_ref() == Some(&after.text) {
+            // Nothing was edited, for example when deleting at the end of the text
+            if pushed {
+                self.undo_stack.pop();
+            }
+            return;
+        }
+        self.redo_stack.clear();
+        self.typing_cursor_position = typing.then(|| after.cursor_position);
+        self.text = Some(after.text);
+    }
+
+    /// Returns the state to restore to undo the last edit, from the `current` state.
+    fn undo(&mut self, current: EditState) -> Option<EditState> {
+        self.clear_if_changed_externally(&current.text);
+        let state = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        self.typing_cursor_position = None;
+        self.text = Some(state.text.clone());
+        Some(state)
+    }
+
+    /// Returns the state to restore to redo the last undone edit, from the `current` state.
+    fn redo(&mut self, current: EditState) -> Option<EditState> {
+        self.clear_if_changed_externally(&current.text);
+        let state = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        self.typing_cursor_position = None;
+        self.text = Some(state.text.clone());
+        Some(state)
+    }
+}
+
+pub struct TextData {
+    /// Tracks the properties that the font size found by `auto_shrink` depends on
+    shrunk_font_size_tracker: Pin<Box<crate::properties::PropertyTracker>>,
+    /// The font size found by `auto_shrink`, valid while the tracker is not dirty
+    shrunk_font_size: core::cell::Cell<Coord>,
+}
+
+impl Default for TextData {
+    fn default() -> Self {
+        Self {
+            shrunk_font_size_tracker: Box::pin(Default::default()),
+            shrunk_font_size: Default::default(),
+        }
+    }
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the Text
+pub struct TextDataBox(core::ptr::NonNull<TextData>);
+
+impl Default for TextDataBox {
+    fn default() -> Self {
+        TextDataBox(Box::leak(Box::new(TextData::default())).into())
+    }
+}
+impl Drop for TextDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TextDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for TextDataBox {
+    type Target = TextData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TextDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_data_init(data: *mut TextDataBox) {
+    core::ptr::write(data, TextDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_data_free(data: *mut TextDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+#[derive(Default, Debug)]
+pub struct TextInputData {
+    history: RefCell<UndoHistory>,
+    /// The selection handle being dragged with a touch screen, and the offset from the touch
+    /// point to the selection end point
+    dragged_selection_handle:
+        core::cell::Cell<Option<(SelectionHandle, euclid::default::Vector2D<Coord>)>>,
+    /// The last key press that moved the cursor, until the key is released
+    held_key: RefCell<Option<KeyEvent>>,
+    /// After a double or triple click, the unit by which dragging extends the selection, and the
+    /// byte range of the word or line that was clicked, until the button is pressed again
+    selection_drag_unit: core::cell::Cell<Option<(SelectionGranularity, usize, usize)>>,
+    /// The selected text pressed or dragged with the mouse, until the button is released
+    text_drag: RefCell<Option<TextDrag>>,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the TextInput
+pub struct TextInputDataBox(core::ptr::NonNull<TextInputData>);
+
+impl Default for TextInputDataBox {
+    fn default() -> Self {
+        TextInputDataBox(Box::leak(Box::new(TextInputData::default())).into())
+    }
+}
+impl Drop for TextInputDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TextInputDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for TextInputDataBox {
+    type Target = TextInputData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TextInputDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextInputDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_textinput_data_init(data: *mut TextInputDataBox) {
+    core::ptr::write(data, TextInputDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextInputDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_textinput_data_free(data: *mut TextInputDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+/// Returns the character boundary of `text` at or before the byte offset `pos`.
+fn previous_char_boundary(text: &str, pos: usize) -> usize {
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+/// Returns the grapheme boundary of `text` at or before the byte offset `pos`.
+fn previous_grapheme_boundary(text: &str, pos: usize) -> usize {
+    let pos = previous_char_boundary(text, pos);
+    let mut grapheme_cursor = unicode_segmentation::GraphemeCursor::new(pos, text.len(), true);
+    if grapheme_cursor.is_boundary(text, 0).unwrap_or(true) {
+        return pos;
+    }
+    grapheme_cursor.prev_boundary(text, 0).ok().flatten().unwrap_or(0)
+}
+
+/// Returns the grapheme boundary of `text` at or after the byte offset `pos`.
+fn next_grapheme_boundary(text: &str, pos: usize) -> usize {
+    let pos = previous_char_boundary(text, pos);
+    let mut grapheme_cursor = unicode_segmentation::GraphemeCursor::new(pos, text.len(), true);
+    if grapheme_cursor.is_boundary(text, 0).unwrap_or(true) {
+        return pos;
+    }
+    grapheme_cursor.next_boundary(text, 0).ok().flatten().unwrap_or(text.len())
+}
+
+/// Returns `pos` moved to the start of the word of `text` that contains it if `to_start`, or else to
+/// the end of that word. A position that is not within a word is returned unchanged.
+fn snap_to_word_boundary(text: &str, pos: usize, to_start: bool) -> usize {
+    text.unicode_word_indices()
+        .find(|(offset, word)| *offset < pos && pos < offset + word.len())
+        .map_or(pos, |(offset, word)| if to_start { offset } else { offset + word.len() })
+}
+
+/// Returns the byte range of the word or the line of `text` at the byte offset `pos`. The words are
+/// the segments between the word boundaries, so a position in the spaces between two words gives
+/// the range of the spaces. The lines are separated by newlines.
+fn selection_unit_range(
+    text: &str,
+    pos: usize,
+    granularity: SelectionGranularity,
+) -> core::ops::Range<usize> {
+    let pos = previous_char_boundary(text, pos);
+    match granularity {
+        SelectionGranularity::Word => text
+            .split_word_bound_indices()
+            .find(|(offset, segment)| pos < offset + segment.len())
+            .or_else(|| text.split_word_bound_indices().last())
+            .map_or(pos..pos, |(offset, segment)| offset..offset + segment.len()),
+        SelectionGranularity::Line => {
+            let start = text[..pos].rfind('\n').map_or(0, |newline| newline + 1);
+            let end = text[pos..].find('\n').map_or(text.len(), |newline| pos + newline);
+            start..end
+        }
+    }
+}
+
+/// Returns the anchor and the cursor of the selection when dragging to the byte offset `pos` after
+/// the word or line `clicked` was selected by a double or triple click. The selection always contains
+/// the clicked unit, and is extended to whole units in the direction of `pos`.
+fn extend_selection_by_units(
+    text: &str,
+    clicked: core::ops::Range<usize>,
+    pos: usize,
+    granularity: SelectionGranularity,
+) -> (usize, usize) {
+    let unit = selection_unit_range(text, pos, granularity);
+    if unit.start < clicked.start {
+        (clicked.end, unit.start)
+    } else {
+        (clicked.start, unit.end.max(clicked.end))
+    }
+}
+
+/// Returns the byte offset at which the text in `range` is inserted when it's moved to the byte
+/// `drop_offset` of the same text, once it was removed from `range`. `drop_offset` is outside of
+/// `range`.
+fn moved_text_offset(range: core::ops::Range<usize>, drop_offset: usize) -> usize {
+    if drop_offset > range.end {
+        drop_offset - range.len()
+    } else {
+        drop_offset
+    }
+}
+
+/// Returns the rectangle, relative to an element of size `size`, in which a text of size
+/// `text_size` is laid out: in the directions that aren't clipped, it is extended to fit the whole
+/// text, keeping the alignment.
+fn overflowing_text_rect(
+    size: Size,
+    text_size: Size,
+    clips_horizontally: bool,
+    clips_vertically: bool,
+    horizontal_alignment: TextHorizontalAlignment,
+    vertical_alignment: TextVerticalAlignment,
+) -> Rect {
+    let overflow = |available: Coord, needed: Coord, clipped: bool| {
+        if clipped {
+            0 as Coord
+        } else {
+            (needed.ceil() - available).max(0 as Coord)
+        }
+    };
+    let overflow_x = overflow(size.width, text_size.width, clips_horizontally);
+    let overflow_y = overflow(size.height, text_size.height, clips_vertically);
+    let x = match horizontal_alignment {
+        TextHorizontalAlignment::Left => 0 as Coord,
+        TextHorizontalAlignment::Center => -overflow_x / (2 as Coord),
+        TextHorizontalAlignment::Right => -overflow_x,
+    };
+    let y = match vertical_alignment {
+        TextVerticalAlignment::Top => 0 as Coord,
+        TextVerticalAlignment::Center => -overflow_y / (2 as Coord),
+        TextVerticalAlignment::Bottom => -overflow_y,
+    };
+    euclid::rect(x, y, size.width + overflow_x, size.height + overflow_y)
+}
+
+/// Returns the largest font size between `min_font_size` and `font_size` for which `fits` returns
+/// true, within a pixel. Returns `min_font_size` if the text doesn't fit at any of these sizes.
+fn shrunk_font_size(font_size: Coord, min_font_size: Coord, fits: impl Fn(Coord) -> bool) -> Coord {
+    if fits(font_size) {
+        return font_size;
+    }
+    // `high` never fits, while `low` fits, unless it is the minimum
+    let mut low = min_font_size.max(1 as Coord).min(font_size);
+    let mut high = font_size;
+    while high - low > 1 as Coord {
+        let middle = (low + high) / 2 as Coord;
+        if fits(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+/// Returns how far the selection scrolls at each tick while the mouse, at `position`, selects text
+/// in a text input whose visible area is `visible_rect`. The scroll starts when the mouse is
+/// closer than `margin` to an edge, and is `speed` pixels per tick for each pixel past that point.
+fn autoscroll_step(
+    position: Point,
+    visible_rect: Rect,
+    margin: Coord,
+    speed: f32,
+) -> euclid::default::Vector2D<Coord> {
+    let distance_past_margin = |pos: Coord, min: Coord, max: Coord| {
+        let distance = if pos < min + margin {
+            pos - (min + margin)
+        } else if pos > max - margin {
+            pos - (max - margin)
+        } else {
+            0 as Coord
+        };
+        (distance as f32 * speed) as Coord
+    };
+    euclid::vec2(
+        distance_past_margin(position.x, visible_rect.min_x(), visible_rect.max_x()),
+        distance_past_margin(position.y, visible_rect.min_y(), visible_rect.max_y()),
+    )
+}
+
+/// Returns true if `ch` is part of the set described by `allowed`: a list of characters
+/// where `a-z` denotes a range. A `-` that is first or last is taken literally.
+fn is_character_allowed(allowed: &str, ch: char) -> bool {
+    let mut chars = allowed.chars();
+    while let Some(start) = chars.next() {
+        let mut lookahead = chars.clone();
+        if lookahead.next() == Some('-') {
+            if let Some(end) = lookahead.next() {
+                chars = lookahead;
+                if (start..=end).contains(&ch) {
+                    return true;
+                }
+                continue;
+            }
+        }
+        if start == ch {
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns the rectangle covered by the text cursor when drawn with the given `style`.
+///
+/// `origin` is the top left corner of the cursor position on its line and `line_height` the height
+/// of that line. `cursor_width` is the width of the bar, and the thickness of the underline.
+/// `grapheme_width` is the width of the grapheme after the cursor; renderers should pass the width
+/// of a space when the cursor is at the end of a line.
+pub fn text_cursor_rect(
+    style: CaretStyle,
+    origin: Point,
+    line_height: Coord,
+    cursor_width: Coord,
+    grapheme_width: Coord,
+) -> Rect {
+    match style {
+        CaretStyle::Bar => Rect::new(origin, Size::new(cursor_width, line_height)),
+        CaretStyle::Block => Rect::new(origin, Size::new(grapheme_width, line_height)),
+        CaretStyle::Underline => Rect::new(
+            Point::new(origin.x, origin.y + line_height - cursor_width),
+            Size::new(grapheme_width, cursor_width),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_unit_ranges() {
+        let text = "Hello big\nWorld";
+        assert_eq!(selection_unit_range(text, 2, SelectionGranularity::Word), 0..5);
+        assert_eq!(selection_unit_range(text, 5, SelectionGranularity::Word), 5..6);
+        assert_eq!(selection_unit_range(text, 6, SelectionGranularity::Word), 6..9);
+        assert_eq!(selection_unit_range(text, 15, SelectionGranularity::Word), 10..15);
+        assert_eq!(selection_unit_range("", 0, SelectionGranularity::Word), 0..0);
+        assert_eq!(selection_unit_range(text, 2, SelectionGranularity::Line), 0..9);
+        assert_eq!(selection_unit_range(text, 12, SelectionGranularity::Line), 10..15);
+        assert_eq!(selection_unit_range(text, 15, SelectionGranularity::Line), 10..15);
+    }
+
+    #[test]
+    fn extend_selection_by_words_and_lines() {
+        let text = "one two three four five";
+        let word = SelectionGranularity::Word;
+        // "three" was double clicked
+        let clicked = 8..13;
+        // Dragging forward across several words extends to the end of the word under the mouse
+        assert_eq!(extend_selection_by_units(text, clicked.clone(), 15, word), (8, 18));
+        assert_eq!(extend_selection_by_units(text, clicked.clone(), 20, word), (8, 23));
+        // Within the clicked word, the selection is the word
+        assert_eq!(extend_selection_by_units(text, clicked.clone(), 10, word), (8, 13));
+        // Dragging backward keeps the clicked word selected, the cursor is at the start of the word
+        assert_eq!(extend_selection_by_units(text, clicked.clone(), 5, word), (13, 4));
+        assert_eq!(extend_selection_by_units(text, clicked, 1, word), (13, 0));
+
+        let text = "first\nsecond\nthird";
+        let line = SelectionGranularity::Line;
+        assert_eq!(extend_selection_by_units(text, 6..12, 15, line), (6, 18));
+        assert_eq!(extend_selection_by_units(text, 6..12, 2, line), (12, 0));
+    }
+
+    #[test]
+    fn moved_text_offset_after_removing_the_text() {
+        // "two " is moved in "one two three"
+        let range = 4..8;
+        // Before the moved text, the offset doesn't change
+        assert_eq!(moved_text_offset(range.clone(), 0), 0);
+        assert_eq!(moved_text_offset(range.clone(), 3), 3);
+        // After it, the offset moves back by its length
+        assert_eq!(moved_text_offset(range.clone(), 9), 5);
+        assert_eq!(moved_text_offset(range, 13), 9);
+    }
+
+    #[test]
+    fn overflowing_text_rect_per_clip_axis() {
+        use TextHorizontalAlignment as H;
+        use TextVerticalAlignment as V;
+
+        let size = Size::new(100 as Coord, 20 as Coord);
+        let text_size = Size::new(150 as Coord, 40 as Coord);
+        let rect = |clips_horizontally, clips_vertically, h_align, v_align| {
+            overflowing_text_rect(
+                size,
+                text_size,
+                clips_horizontally,
+                clips_vertically,
+                h_align,
+                v_align,
+            )
+        };
+
+        // Clipped in both directions, the text is laid out in the element
+        assert_eq!(rect(true, true, H::Center, V::Center), Rect::from_size(size));
+
+        // Only clipped horizontally, the text overflows at the bottom, or at both vertical edges
+        // when centered
+        assert_eq!(
+            rect(true, false, H::Left, V::Top),
+            euclid::rect(0 as Coord, 0 as Coord, 100 as Coord, 40 as Coord)
+        );
+        assert_eq!(
+            rect(true, false, H::Left, V::Center),
+            euclid::rect(0 as Coord, -10 as Coord, 100 as Coord, 40 as Coord)
+        );
+
+        // Only clipped vertically, the text overflows at the left when aligned to the right
+        assert_eq!(
+            rect(false, true, H::Right, V::Top),
+            euclid::rect(-50 as Coord, 0 as Coord, 150 as Coord, 20 as Coord)
+        );
+
+        // A text smaller than the element doesn't shrink the rectangle
+        assert_eq!(
+            overflowing_text_rect(
+                size,
+                Size::new(10 as Coord, 10 as Coord),
+                false,
+                false,
+                H::Right,
+                V::Bottom
+            ),
+            Rect::from_size(size)
+        );
+    }
+
+    #[test]
+    fn text_cursor_rect_per_style() {
+        let origin = Point::new(10 as Coord, 20 as Coord);
+        let rect = |style| text_cursor_rect(style, origin, 16 as Coord, 2 as Coord, 8 as Coord);
+
+        assert_eq!(rect(CaretStyle::Bar), Rect::new(origin, Size::new(2 as Coord, 16 as Coord)));
+        assert_eq!(rect(CaretStyle::Block), Rect::new(origin, Size::new(8 as Coord, 16 as Coord)));
+        assert_eq!(
+            rect(CaretStyle::Underline),
+            Rect::new(Point::new(10 as Coord, 34 as Coord), Size::new(8 as Coord, 2 as Coord))
+        );
+    }
+
+    #[test]
+    fn autoscroll_step_grows_past_the_edge() {
+        let visible_rect = Rect::new(Point::default(), Size::new(100 as Coord, 20 as Coord));
+        let step =
+            |x: Coord| autoscroll_step(Point::new(x, 10 as Coord), visible_rect, 10 as Coord, 2.);
+
+        // No scroll away from the edges
+        assert_eq!(step(50 as Coord), euclid::vec2(0 as Coord, 0 as Coord));
+        assert_eq!(step(90 as Coord), euclid::vec2(0 as Coord, 0 as Coord));
+
+        // Within the margin, and 5px and 50px past the right edge
+        assert_eq!(step(94 as Coord).x, 8 as Coord);
+        assert_eq!(step(105 as Coord).x, 30 as Coord);
+        assert_eq!(step(150 as Coord).x, 120 as Coord);
+
+        // Past the left edge, the selection scrolls backward
+        assert_eq!(step(-5 as Coord).x, -30 as Coord);
+        assert_eq!(step(-50 as Coord).x, -120 as Coord);
+        assert_eq!(step(-50 as Coord).y, 0 as Coord);
+    }
+
+    #[test]
+    fn shrunk_font_size_fits_long_text_in_narrow_box() {
+        // A text of 20 glyphs as wide as half the font size, in a 100px wide box
+        let fits = |font_size: Coord| 20 as Coord * font_size / 2 as Coord <= 100 as Coord;
+        let shrunk = shrunk_font_size(24 as Coord, 4 as Coord, fits);
+        assert!(fits(shrunk));
+        assert!(shrunk > 9 as Coord && shrunk <= 10 as Coord);
+
+        // Short enough text keeps its size, and the minimum size is a bound
+        assert_eq!(shrunk_font_size(8 as Coord, 4 as Coord, fits), 8 as Coord);
+        assert_eq!(shrunk_font_size(24 as Coord, 12 as Coord, fits), 12 as Coord);
+        assert_eq!(shrunk_font_size(24 as Coord, 4 as Coord, |_| false), 4 as Coord);
+    }
+
+    /// A window whose renderer measures every byte as wide as half the font size, and counts
+    /// the measures.
+    struct MeasuringWindow {
+        window: crate::api::Window,
+        measure_count: core::cell::Cell<usize>,
+    }
+
+    impl PlatformWindow for MeasuringWindow {
+        fn renderer(&self) -> &dyn crate::renderer::Renderer {
+            self
+        }
+
+        fn as_any(&self) -> &dyn core::any::Any {
+            self
+        }
+
+        fn window(&self) -> &crate::api::Window {
+            &self.window
+        }
+    }
+
+    impl crate::renderer::Renderer for MeasuringWindow {
+        fn text_size(
+            &self,
+            font_request: FontRequest,
+            text: &str,
+            _max_width: Option<Coord>,
+            _scale_factor: f32,
+            _rules: TextMeasureRules,
+        ) -> Size {
+            self.measure_count.set(self.measure_count.get() + 1);
+            let font_size = font_request.pixel_size.unwrap();
+            Size::new(text.len() as Coord * font_size / 2 as Coord, font_size)
+        }
+
+        fn text_input_byte_offset_for_position(&self, _: Pin<&TextInput>, _: Point) -> usize {
+            0
+        }
+
+        fn text_input_cursor_rect_for_byte_offset(&self, _: Pin<&TextInput>, _: usize) -> Rect {
+            Rect::default()
+        }
+    }
+
+    #[test]
+    fn auto_shrink_font_size_is_cached() {
+        let platform_window = Rc::new_cyclic(|self_weak| MeasuringWindow {
+            window: crate::api::Window::new(self_weak.clone() as _),
+            measure_count: Default::default(),
+        });
+        let window = platform_window.window.window_handle();
+        let text = Box::pin(Text::default());
+        text.text.set("Hello World".into());
+        text.auto_shrink.set(true);
+        text.width.set(33 as Coord);
+        text.height.set(100 as Coord);
+
+        // Without a font size, the text shrinks from the default font size
+        let pixel_size = text.as_ref().font_request(window).pixel_size.unwrap();
+        assert!(pixel_size > 5 as Coord && pixel_size <= 6 as Coord, "{}", pixel_size);
+        let measure_count = platform_window.measure_count.get();
+        assert!(measure_count > 0);
+
+        // The font size is only searched again when the text or its size change
+        assert_eq!(text.as_ref().font_request(window).pixel_size, Some(pixel_size));
+        assert_eq!(platform_window.measure_count.get(), measure_count);
+        text.width.set(66 as Coord);
+        assert_eq!(text.as_ref().font_request(window).pixel_size, Some(DEFAULT_FONT_SIZE));
+        assert!(platform_window.measure_count.get() > measure_count);
+    }
+
+    #[test]
+    fn snap_to_word_boundary_moves_within_words() {
+        let text = "one two  three";
+        assert_eq!(snap_to_word_boundary(text, 5, true), 4);
+        assert_eq!(snap_to_word_boundary(text, 5, false), 7);
+        // Word boundaries and positions between words don't move
+        assert_eq!(snap_to_word_boundary(text, 4, false), 4);
+        assert_eq!(snap_to_word_boundary(text, 8, true), 8);
+        assert_eq!(snap_to_word_boundary(text, 14, true), 14);
+    }
+
+    #[test]
+    fn font_request_inherits_window_defaults() {
+        let window_item = Box::pin(crate::items::WindowItem::default());
+        window_item.default_font_family.set("Window Font".into());
+        window_item.default_font_size.set(20 as Coord);
+        window_item.default_font_weight.set(700);
+
+        let text = Box::pin(Text::default());
+        let resolve = || {
+            text.as_ref()
+                .unresolved_font_request()
+                .merge(&window_item.as_ref().default_font_request())
+        };
+        assert_eq!(
+            resolve(),
+            FontRequest {
+                family: Some("Window Font".into()),
+                weight: Some(700),
+                pixel_size: Some(20 as Coord),
+                letter_spacing: Some(0 as Coord),
+            }
+        );
+
+        // The window defaults are read at call time
+        window_item.default_font_size.set(30 as Coord);
+        assert_eq!(resolve().pixel_size, Some(30 as Coord));
+
+        // Properties set on the element take precedence
+        text.font_family.set("Item Font".into());
+        text.font_weight.set(300);
+        assert_eq!(resolve().family, Some("Item Font".into()));
+        assert_eq!(resolve().weight, Some(300));
+
+        // Without defaults on the window, unset properties stay unset
+        let text_input = Box::pin(TextInput::default());
+        let request = text_input
+            .as_ref()
+            .unresolved_font_request()
+            .merge(&Box::pin(crate::items::WindowItem::default()).as_ref().default_font_request());
+        assert_eq!(request.family, None);
+        assert_eq!(request.weight, None);
+        assert_eq!(request.pixel_size, None);
+    }
+
+    #[test]
+    fn text_cursor_rect_is_default_bar() {
+        assert_eq!(CaretStyle::default(), CaretStyle::Bar);
+    }
 }
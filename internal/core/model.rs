@@ -10,7 +10,7 @@
 use crate::items::ItemRef;
 use crate::layout::Orientation;
 use crate::{Coord, Property, SharedString, SharedVector};
-pub use adapters::{FilterModel, MapModel};
+pub use adapters::{FilterModel, LazyMapModel, MapModel, SortModel};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
@@ -120,6 +120,26 @@ pub trait Model {
     /// This function does not register dependencies on the current binding. For an equivalent
     /// function that tracks dependencies, see [`ModelExt::row_data_tracked`]
     fn row_data(&self, row: usize) -> Option<Self::Data>;
+    /// Calls `visitor` with a reference to the data of a particular row, if that row exists.
+    ///
+    /// This allows reading the data without cloning it. The default implementation calls
+    /// `visitor` with the value returned by [`Self::row_data`]; models that store their rows
+    /// should re-implement this function to pass a reference to the stored row instead, as long
+    /// as the `visitor` can still modify the model, for example when it updates a property that
+    /// has a binding writing to the model. Use [`ModelExt::with_row_data`] to get a result back
+    /// from the visitor.
+    fn visit_row_data(&self, row: usize, visitor: &mut dyn FnMut(&Self::Data)) {
+        if let Some(data) = self.row_data(row) {
+            visitor(&data)
+        }
+    }
+    /// Hints that the rows in `range` are about to be read with [`Self::row_data`].
+    ///
+    /// The ListView calls this function with the rows it is about to show, before reading them
+    /// one by one. Models for which fetching a contiguous range of rows is cheaper than fetching
+    /// each row separately, for example when the rows come from a database, can re-implement this
+    /// function to load these rows into a cache. The default implementation does nothing.
+    fn prefetch(&self, _range: core::ops::Range<usize>) {}
     /// Sets the data for a particular row.
     ///
     /// This function should be called with `row < row_count()`, otherwise the implementation can panic.
@@ -188,6 +208,16 @@ fn row_data_tracked(&self, row: usize) -> Option<Self::Data> {
         self.row_data(row)
     }
 
+    /// Returns the result of `f` called with a reference to the data of the row, or None if
+    /// the row doesn't exist. Unlike [`Model::row_data`], this doesn't clone the data of the models
+    /// that implement [`Model::visit_row_data`].
+    fn with_row_data<R>(&self, row: usize, f: impl FnOnce(&Self::Data) -> R) -> Option<R> {
+        let mut f = Some(f);
+        let mut result = None;
+        self.visit_row_data(row, &mut |data| result = f.take().map(|f| f(data)));
+        result
+    }
+
     /// Returns a new Model where all elements are mapped by the function `map_function`.
     /// This is a shortcut for [`MapModel::new()`].
     fn map<F, U>(self, map_function: F) -> MapModel<Self, F>
@@ -198,6 +228,18 @@ fn map<F, U>(self, map_function: F) -> MapModel<Self, F>
         MapModel::new(self, map_function)
     }
 
+    /// Returns a new Model where all elements are mapped by the function `map_function`, which
+    /// is only called again for a row after that row has changed.
+    /// This is a shortcut for [`LazyMapModel::new()`].
+    fn lazy_map<F, U>(self, map_function: F) -> LazyMapModel<Self, F, U>
+    where
+        Self: Sized + 'static,
+        F: Fn(Self::Data) -> U + 'static,
+        U: Clone + 'static,
+    {
+        LazyMapModel::new(self, map_function)
+    }
+
     /// Returns a new Model where the elements are filtered by the function `filter_function`.
     /// This is a shortcut for [`FilterModel::new()`].
     fn filter<F>(self, filter_function: F) -> FilterModel<Self, F>
@@ -207,6 +249,16 @@ fn filter<F>(self, filter_function: F) -> FilterModel<Self, F>
     {
         FilterModel::new(self, filter_function)
     }
+
+    /// Returns a new Model where the elements are sorted by the function `sort_function`.
+    /// This is a shortcut for [`SortModel::new()`].
+    fn sort_by<F>(self, sort_function: F) -> SortModel<Self, F>
+    where
+        Self: Sized + 'static,
+        F: Fn(&Self::Data, &Self::Data) -> core::cmp::Ordering + 'static,
+    {
+        SortModel::new(self, sort_function)
+    }
 }
 
 impl<T: Model> ModelExt for T {}
@@ -261,6 +313,14 @@ fn row_data(&self, row: usize) -> Option<Self::Data> {
         (**self).row_data(row)
     }
 
+    fn visit_row_data(&self, row: usize, visitor: &mut dyn FnMut(&Self::Data)) {
+        (**self).visit_row_data(row, visitor)
+    }
+
+    fn prefetch(&self, range: core::ops::Range<usize>) {
+        (**self).prefetch(range)
+    }
+
     fn model_tracker(&self) -> &dyn ModelTracker {
         (**self).model_tracker()
     }
@@ -289,6 +349,27 @@ pub fn from_slice(slice: &[T]) -> ModelRc<T>
         ModelRc::new(Self::from(slice.to_vec()))
     }
 
+    /// Creates a new model with the rows of `vec`. The model isn't attached to any view yet.
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        VecModel { array: RefCell::new(vec), notify: Default::default() }
+    }
+
+    /// Returns a copy of the rows of the model
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.array.borrow().clone()
+    }
+
+    /// Calls `f` with the index and a reference to the data of every row, without copying them.
+    /// Modifying the model from `f` panics.
+    pub fn for_each_row(&self, mut f: impl FnMut(usize, &T)) {
+        for (row, data) in self.array.borrow().iter().enumerate() {
+            f(row, data)
+        }
+    }
+
     /// Add a row at the end of the model
     pub fn push(&self, value: T) {
         self.array.borrow_mut().push(value);
@@ -302,17 +383,73 @@ pub fn insert(&self, index: usize, value: T) {
         self.notify.row_added(index, 1)
     }
 
+    /// Swaps the rows at positions `a` and `b`, which are notified as changed.
+    /// This function panics if `a` or `b` is >= row_count().
+    pub fn swap(&self, a: usize, b: usize) {
+        self.array.borrow_mut().swap(a, b);
+        if a != b {
+            self.notify.row_changed(a);
+            self.notify.row_changed(b);
+        }
+    }
+
     /// Remove the row at the given index from the model
     pub fn remove(&self, index: usize) {
         self.array.borrow_mut().remove(index);
         self.notify.row_removed(index, 1)
     }
 
+    /// Removes all the rows of the model. The views are notified with a single removal of all the
+    /// rows, instead of one per row.
+    pub fn clear(&self) {
+        let mut array = self.array.borrow_mut();
+        let len = array.len();
+        array.clear();
+        drop(array);
+        if len > 0 {
+            self.notify.row_removed(0, len)
+        }
+    }
+
     /// Replace inner Vec with new data
+    ///
+    /// The views are notified with a single reset instead of one notification per row, so a
+    /// repeater rebuilds all its rows at once the next time it is updated.
     pub fn set_vec(&self, new: impl Into<Vec<T>>) {
         *self.array.borrow_mut() = new.into();
         self.notify.reset();
     }
+
+    /// Sets the data of several rows at once. `updates` yields pairs of row index and data, the
+    /// rows that are out of bounds are ignored.
+    ///
+    /// The views are notified after all the rows are set. If the updated rows make up at least
+    /// half of the range between the first and the last one, a single notification is sent for
+    /// that whole range. Otherwise, the updated rows are notified one by one.
+    pub fn set_rows(&self, updates: impl IntoIterator<Item = (usize, T)>) {
+        let mut rows = Vec::new();
+        {
+            let mut array = self.array.borrow_mut();
+            for (row, data) in updates {
+                if let Some(value) = array.get_mut(row) {
+                    *value = data;
+                    rows.push(row);
+                }
+            }
+        }
+        rows.sort_unstable();
+        rows.dedup();
+        match (rows.first(), rows.last()) {
+            (Some(&first), Some(&last)) if last - first < rows.len() * 2 => {
+                self.notify.row_changed_range(first..last + 1)
+            }
+            _ => {
+                for row in rows {
+                    self.notify.row_changed(row)
+                }
+            }
+        }
+    }
 }
 
 impl<T> From<Vec<T>> for VecModel<T> {
@@ -321,6 +458,29 @@ fn from(array: Vec<T>) -> Self {
     }
 }
 
+impl<T> core::iter::FromIterator<T> for VecModel<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+/// A `VecModel` is serialized as the sequence of its rows.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for VecModel<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.array.borrow().serialize(serializer)
+    }
+}
+
+/// A `VecModel` is deserialized from a sequence of rows. The resulting model isn't attached to
+/// any view.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for VecModel<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(Self::from)
+    }
+}
+
 impl<T: Clone + 'static> Model for VecModel<T> {
     type Data = T;
 
@@ -387,6 +547,15 @@ fn row_data(&self, row: usize) -> Option<Self::Data> {
         self.array.borrow().get(row).cloned()
     }
 
+    fn visit_row_data(&self, row: usize, visitor: &mut dyn FnMut(&Self::Data)) {
+        // Visit a copy of the shared vector, which doesn't clone the rows, so that the visitor
+        // can modify the model without conflicting with the borrow.
+        let array = self.array.borrow().clone();
+        if let Some(data) = array.get(row) {
+            visitor(data)
+        }
+    }
+
     fn set_row_data(&self, row: usize, data: Self::Data) {
         self.array.borrow_mut().make_mut_slice()[row] = data;
         self.notify.row_changed(row);
@@ -527,6 +696,18 @@ fn row_data(&self, row: usize) -> Option<Self::Data> {
         self.0.as_ref().and_then(|model| model.row_data(row))
     }
 
+    fn visit_row_data(&self, row: usize, visitor: &mut dyn FnMut(&Self::Data)) {
+        if let Some(model) = self.0.as_ref() {
+            model.visit_row_data(row, visitor)
+        }
+    }
+
+    fn prefetch(&self, range: core::ops::Range<usize>) {
+        if let Some(model) = self.0.as_ref() {
+            model.prefetch(range)
+        }
+    }
+
     fn set_row_data(&self, row: usize, data: Self::Data) {
         if let Some(model) = self.0.as_ref() {
             model.set_row_data(row, data);
@@ -550,7 +731,7 @@ pub trait RepeatedComponent:
     type Data: 'static;
 
     /// Update this component at the given index and the given data
-    fn update(&self, index: usize, data: Self::Data);
+    fn update(&self, index: usize, data: &Self::Data);
 
     /// Layout this item in the listview
     ///
@@ -563,6 +744,17 @@ fn listview_layout(
     ) {
     }
 
+    /// Layout this item in a horizontal listview
+    ///
+    /// offset_x is the `x` position where this item should be placed.
+    /// it should be updated to be to the x position of the next item.
+    fn listview_layout_horizontal(
+        self: Pin<&Self>,
+        _offset_x: &mut Coord,
+        _viewport_height: Pin<&Property<Coord>>,
+    ) {
+    }
+
     /// Returns what's needed to perform the layout if this component is in a box layout
     fn box_layout_data(
         self: Pin<&Self>,
@@ -592,6 +784,76 @@ struct RepeaterInner<C: RepeatedComponent> {
     /// the position of the item in the row `offset` (which corresponds to `components[0]`).
     /// We will try to keep this constant when re-layouting items
     anchor_y: Coord,
+    /// The first visible row and its fraction scrolled out of the view, as last reported to
+    /// the callback set with [`Repeater::set_listview_scroll_callback`]
+    listview_scroll_position: Option<(usize, f32)>,
+    /// The row count of the model when the callback set with
+    /// [`Repeater::set_listview_end_reached_callback`] was last called. Cleared when rows are
+    /// removed or the model is reset, so that the callback is called again for that row count.
+    listview_end_reached_row_count: Option<usize>,
+    /// The spring back of the viewport of the ListView after it was scrolled past its bounds
+    listview_springback: Option<Springback>,
+}
+
+/// See [`Repeater::set_listview_overscroll_springback`]
+#[derive(Clone, Copy)]
+struct Springback {
+    /// The time at which the viewport was scrolled past the bounds
+    start: crate::animations::Instant,
+    /// How far the viewport was scrolled past the bounds
+    overscroll: Coord,
+    /// The viewport_y that was set during the last layout, to know if the spring back was
+    /// interrupted by scrolling again
+    viewport_y: Coord,
+}
+
+impl<C: RepeatedComponent> RepeaterInner<C> {
+    /// Returns the row at the top of the view and the fraction of its height that is above `view_top`.
+    /// `first_row_y` is the position of the row `offset`, the components must be laid out.
+    /// For a horizontal ListView, this is the row at the left of the view and its width.
+    fn first_visible_row(
+        &self,
+        orientation: Orientation,
+        first_row_y: Coord,
+        view_top: Coord,
+    ) -> (usize, f32) {
+        let mut y = first_row_y;
+        for (i, c) in self.components.iter().enumerate() {
+            let h = c.1.as_ref().map_or(0 as Coord, |c| {
+                listview_item_size(c.as_pin_ref().get_item_ref(0), orientation)
+            });
+            if y + h > view_top || i + 1 == self.components.len() {
+                let fraction = if h > 0 as Coord {
+                    ((view_top - y) as f32 / h as f32).max(0.).min(1.)
+                } else {
+                    0.
+                };
+                return (self.offset + i, fraction);
+            }
+            y += h;
+        }
+        (self.offset, 0.)
+    }
+
+    /// Returns the row after the last one that is visible above `view_bottom`, with the same
+    /// arguments as [`Self::first_visible_row`].
+    fn visible_rows_end(
+        &self,
+        orientation: Orientation,
+        first_row_y: Coord,
+        view_bottom: Coord,
+    ) -> usize {
+        let mut y = first_row_y;
+        for (i, c) in self.components.iter().enumerate() {
+            if y >= view_bottom {
+                return self.offset + i;
+            }
+            y += c.1.as_ref().map_or(0 as Coord, |c| {
+                listview_item_size(c.as_pin_ref().get_item_ref(0), orientation)
+            });
+        }
+        self.offset + self.components.len()
+    }
 }
 
 impl<C: RepeatedComponent> Default for RepeaterInner<C> {
@@ -602,10 +864,46 @@ fn default() -> Self {
             cached_item_height: Default::default(),
             previous_viewport_y: Default::default(),
             anchor_y: Default::default(),
+            listview_scroll_position: None,
+            listview_end_reached_row_count: None,
+            listview_springback: None,
+        }
+    }
+}
+
+/// Returns the size of a row of a ListView along its `orientation`: the height of the row for a
+/// vertical ListView, and its width for a horizontal one.
+fn listview_item_size(item: Pin<ItemRef>, orientation: Orientation) -> Coord {
+    let geometry = item.as_ref().geometry();
+    match orientation {
+        Orientation::Horizontal => geometry.width(),
+        Orientation::Vertical => geometry.height(),
+    }
+}
+
+/// Lays out a row of a ListView along its `orientation`, see [`RepeatedComponent::listview_layout`]
+/// and [`RepeatedComponent::listview_layout_horizontal`].
+fn listview_layout_row<C: RepeatedComponent>(
+    component: &ComponentRc<C>,
+    orientation: Orientation,
+    offset: &mut Coord,
+    viewport_cross_size: Pin<&Property<Coord>>,
+) {
+    match orientation {
+        Orientation::Horizontal => {
+            component.as_pin_ref().listview_layout_horizontal(offset, viewport_cross_size)
+        }
+        Orientation::Vertical => {
+            component.as_pin_ref().listview_layout(offset, viewport_cross_size)
         }
     }
 }
 
+/// The default amount of rows from which a ListView only instantiates the visible rows. The
+/// `windowing-threshold` property of the ListView widget has the same default.
+/// See [`Repeater::set_listview_windowing_threshold`].
+pub const DEFAULT_LISTVIEW_WINDOWING_THRESHOLD: usize = 32;
+
 /// This field is put in a component when using the `for` syntax
 /// It helps instantiating the components `C`
 #[pin_project]
@@ -618,6 +916,16 @@ pub struct RepeaterTracker<C: RepeatedComponent> {
     /// Only used for the list view to track if the scrollbar has changed and item needs to be layed out again.
     #[pin]
     listview_geometry_tracker: crate::properties::PropertyTracker,
+    /// Only used for the list view: below this amount of rows, all the rows are instantiated.
+    listview_windowing_threshold: Cell<usize>,
+    /// Only used for the list view: see [`Repeater::set_listview_scroll_callback`].
+    listview_scroll_callback: RefCell<Option<Box<dyn Fn(usize, f32)>>>,
+    /// Only used for the list view: see [`Repeater::set_listview_end_reached_callback`].
+    listview_end_reached_callback: RefCell<Option<Box<dyn Fn()>>>,
+    /// Only used for the list view: see [`Repeater::set_listview_end_reached_threshold`].
+    listview_end_reached_threshold: Cell<usize>,
+    /// Only used for the list view: see [`Repeater::set_listview_overscroll_springback`].
+    listview_overscroll_springback: Cell<Option<core::time::Duration>>,
 }
 
 impl<C: RepeatedComponent> ModelChangeListener for RepeaterTracker<C> {
@@ -630,6 +938,18 @@ fn row_changed(&self, row: usize) {
             c.0 = RepeatedComponentState::Dirty;
         }
     }
+    /// Notify the peers that all the rows in a range were changed
+    fn row_changed_range(&self, range: core::ops::Range<usize>) {
+        self.is_dirty.set(true);
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+        let len = inner.components.len();
+        let start = range.start.saturating_sub(inner.offset).min(len);
+        let end = range.end.saturating_sub(inner.offset).min(len);
+        for c in &mut inner.components[start..end] {
+            c.0 = RepeatedComponentState::Dirty;
+        }
+    }
     /// Notify the peers that rows were added
     fn row_added(&self, mut index: usize, mut count: usize) {
         let mut inner = self.inner.borrow_mut();
@@ -658,6 +978,7 @@ fn row_added(&self, mut index: usize, mut count: usize) {
     /// Notify the peers that rows were removed
     fn row_removed(&self, mut index: usize, mut count: usize) {
         let mut inner = self.inner.borrow_mut();
+        inner.listview_end_reached_row_count = None;
         if index < inner.offset {
             if index + count < inner.offset {
                 return;
@@ -683,7 +1004,9 @@ fn row_removed(&self, mut index: usize, mut count: usize) {
 
     fn reset(&self) {
         self.is_dirty.set(true);
-        self.inner.borrow_mut().components.clear();
+        let mut inner = self.inner.borrow_mut();
+        inner.components.clear();
+        inner.listview_end_reached_row_count = None;
     }
 }
 
@@ -694,6 +1017,11 @@ fn default() -> Self {
             model: Property::new_named(ModelRc::default(), "i_slint_core::Repeater::model"),
             is_dirty: Property::new_named(false, "i_slint_core::Repeater::is_dirty"),
             listview_geometry_tracker: Default::default(),
+            listview_windowing_threshold: Cell::new(DEFAULT_LISTVIEW_WINDOWING_THRESHOLD),
+            listview_scroll_callback: Default::default(),
+            listview_end_reached_callback: Default::default(),
+            listview_end_reached_threshold: Cell::new(0),
+            listview_overscroll_springback: Cell::new(None),
         }
     }
 }
@@ -754,7 +1082,8 @@ fn ensure_updated_impl(
                     created = true;
                     c.1 = Some(init());
                 }
-                c.1.as_ref().unwrap().update(i + offset, model.row_data(i + offset).unwrap());
+                let component = c.1.as_ref().unwrap();
+                model.visit_row_data(i + offset, &mut |data| component.update(i + offset, data));
                 c.0 = RepeatedComponentState::Clean;
             }
         }
@@ -762,6 +1091,123 @@ fn ensure_updated_impl(
         created
     }
 
+    /// Sets the amount of rows from which a ListView only instantiates the rows that are visible
+    /// and recycles them while scrolling. Below that amount, all the rows are instantiated and
+    /// laid out like a normal repeater, which avoids the overhead of the windowing for small models.
+    ///
+    /// The repeater switches between both modes when the row count of the model crosses the
+    /// threshold. The components that are already instantiated are kept, so their state is
+    /// preserved, and the row that was at the top of the view stays at the same position on screen.
+    pub fn set_listview_windowing_threshold(&self, row_count: usize) {
+        self.0.listview_windowing_threshold.set(row_count);
+    }
+
+    /// Sets a callback that a ListView calls when its scroll position changes, with the row at the
+    /// top of the view and the fraction of the height of that row that is scrolled out of the view.
+    ///
+    /// The callback is called during the layout of the rows, and not as long as the model is empty.
+    pub fn set_listview_scroll_callback(&self, callback: impl Fn(usize, f32) + 'static) {
+        *self.0.listview_scroll_callback.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets a callback that a ListView calls when the rows that are visible come within `threshold`
+    /// rows of the end of the model, for example to fetch more rows from a server and append them
+    /// to the model.
+    ///
+    /// The callback is called during the layout of the rows, once for every row count of the model:
+    /// after rows were added, it is called again when the view approaches the new end. It is also
+    /// called when the model is empty, to load the first rows. The rows that it adds to the model
+    /// are laid out with the next update of the ListView.
+    pub fn set_listview_end_reached_callback(
+        &self,
+        threshold: usize,
+        callback: impl Fn() + 'static,
+    ) {
+        self.0.listview_end_reached_threshold.set(threshold);
+        *self.0.listview_end_reached_callback.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Changes the amount of rows from the end of the model at which the callback set with
+    /// [`Self::set_listview_end_reached_callback`] is called.
+    pub fn set_listview_end_reached_threshold(&self, threshold: usize) {
+        self.0.listview_end_reached_threshold.set(threshold);
+    }
+
+    /// When set, a ListView lets its viewport be scrolled past the top or the bottom of the rows,
+    /// and springs back to the bound within `duration`, decelerating. By default, or with `None`,
+    /// the viewport is clamped to the bounds.
+    pub fn set_listview_overscroll_springback(&self, duration: Option<core::time::Duration>) {
+        self.0.listview_overscroll_springback.set(duration);
+    }
+
+    /// Sets `viewport_y` at the end of the layout of a ListView. `requested` is the value of
+    /// `viewport_y` used for the layout, `clamped` is the position within the bounds, and
+    /// `overscroll` is how far `requested` was past the bounds.
+    fn set_listview_viewport_y(
+        self: Pin<&Self>,
+        viewport_y: Pin<&Property<Coord>>,
+        requested: Coord,
+        clamped: Coord,
+        overscroll: Coord,
+    ) {
+        let mut inner = self.0.inner.borrow_mut();
+        let springback =
+            match (self.0.listview_overscroll_springback.get(), inner.listview_springback) {
+                (Some(duration), Some(springback)) if springback.viewport_y == requested => {
+                    // Still springing back
+                    let elapsed =
+                        crate::animations::current_tick().duration_since(springback.start);
+                    let remaining = crate::items::springback_factor(elapsed, duration);
+                    Some(Springback {
+                        viewport_y: clamped + springback.overscroll * remaining as Coord,
+                        ..springback
+                    })
+                }
+                (Some(_), _) if overscroll != 0 as Coord => Some(Springback {
+                    start: crate::animations::current_tick(),
+                    overscroll,
+                    viewport_y: clamped + overscroll,
+                }),
+                _ => None,
+            }
+            .filter(|springback| springback.viewport_y != clamped);
+        inner.listview_springback = springback;
+        drop(inner);
+        viewport_y.set(springback.map_or(clamped, |springback| springback.viewport_y));
+        if springback.is_some() {
+            crate::animations::CURRENT_ANIMATION_DRIVER
+                .with(|driver| driver.set_has_active_animations());
+        }
+    }
+
+    fn notify_listview_scroll_position(self: Pin<&Self>, position: (usize, f32)) {
+        let previous = self.0.inner.borrow_mut().listview_scroll_position.replace(position);
+        if previous != Some(position) {
+            if let Some(callback) = self.0.listview_scroll_callback.borrow().as_ref() {
+                callback(position.0, position.1);
+            }
+        }
+    }
+
+    /// Calls the callback set with [`Self::set_listview_end_reached_callback`] if the rows up to
+    /// `visible_rows_end` are visible and close enough to the end of the model.
+    fn notify_listview_visible_rows_end(
+        self: Pin<&Self>,
+        visible_rows_end: usize,
+        row_count: usize,
+    ) {
+        let end_reached_callback = self.0.listview_end_reached_callback.borrow();
+        if let Some(callback) = end_reached_callback.as_ref() {
+            if visible_rows_end + self.0.listview_end_reached_threshold.get() >= row_count {
+                let previous =
+                    self.0.inner.borrow_mut().listview_end_reached_row_count.replace(row_count);
+                if previous != Some(row_count) {
+                    callback();
+                }
+            }
+        }
+    }
+
     /// Same as `Self::ensuer_updated` but for a ListView
     pub fn ensure_updated_listview(
         self: Pin<&Self>,
@@ -771,6 +1217,55 @@ pub fn ensure_updated_listview(
         viewport_y: Pin<&Property<Coord>>,
         listview_width: Coord,
         listview_height: Pin<&Property<Coord>>,
+    ) {
+        self.ensure_updated_listview_impl(
+            Orientation::Vertical,
+            init,
+            viewport_width,
+            viewport_height,
+            viewport_y,
+            listview_width,
+            listview_height,
+        );
+    }
+
+    /// Same as `Self::ensure_updated_listview` but for a ListView whose rows are laid out from left
+    /// to right with [`RepeatedComponent::listview_layout_horizontal`], and that scrolls
+    /// horizontally. Like for a vertical ListView, only the visible rows are instantiated.
+    pub fn ensure_updated_listview_horizontal(
+        self: Pin<&Self>,
+        init: impl Fn() -> ComponentRc<C>,
+        viewport_width: Pin<&Property<Coord>>,
+        viewport_height: Pin<&Property<Coord>>,
+        viewport_x: Pin<&Property<Coord>>,
+        listview_width: Pin<&Property<Coord>>,
+        listview_height: Coord,
+    ) {
+        self.ensure_updated_listview_impl(
+            Orientation::Horizontal,
+            init,
+            viewport_height,
+            viewport_width,
+            viewport_x,
+            listview_height,
+            listview_width,
+        );
+    }
+
+    /// The implementation of [`Self::ensure_updated_listview`] and
+    /// [`Self::ensure_updated_listview_horizontal`]. The names are the ones of a vertical ListView:
+    /// for a horizontal one, the `y` positions and the heights are the `x` positions and the widths,
+    /// and the other way around.
+    #[allow(clippy::too_many_arguments)]
+    fn ensure_updated_listview_impl(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        init: impl Fn() -> ComponentRc<C>,
+        viewport_width: Pin<&Property<Coord>>,
+        viewport_height: Pin<&Property<Coord>>,
+        viewport_y: Pin<&Property<Coord>>,
+        listview_width: Coord,
+        listview_height: Pin<&Property<Coord>>,
     ) {
         viewport_width.set(listview_width);
         let model = self.model();
@@ -779,12 +1274,27 @@ pub fn ensure_updated_listview(
             self.0.inner.borrow_mut().components.clear();
             viewport_height.set(0 as _);
             viewport_y.set(0 as _);
+            self.notify_listview_visible_rows_end(0, 0);
 
             return;
         }
 
+        if row_count < self.data().listview_windowing_threshold.get() {
+            self.ensure_updated_listview_all_rows(
+                orientation,
+                init,
+                &model,
+                viewport_width,
+                viewport_height,
+                viewport_y,
+                listview_height,
+            );
+            return;
+        }
+
         let listview_height = listview_height.get();
-        let mut vp_y = viewport_y.get().min(0 as _);
+        let requested_vp_y = viewport_y.get();
+        let mut vp_y = requested_vp_y.min(0 as _);
 
         // We need some sort of estimation of the element height
         let cached_item_height = self.data().inner.borrow_mut().cached_item_height;
@@ -795,7 +1305,7 @@ pub fn ensure_updated_listview(
             let count = Cell::new(0);
             let get_height_visitor = |item: Pin<ItemRef>| {
                 count.set(count.get() + 1);
-                let height = item.as_ref().geometry().height();
+                let height = listview_item_size(item, orientation);
                 total_height.set(total_height.get() + height);
             };
             for c in self.data().inner.borrow().components.iter() {
@@ -825,6 +1335,13 @@ pub fn ensure_updated_listview(
             }
         };
 
+        // Let the model load the rows that are estimated to be visible in one go
+        if element_height > 0 as Coord {
+            let first_row = ((-vp_y / element_height).floor() as usize).min(row_count - 1);
+            let visible_rows = (listview_height / element_height).ceil() as usize + 1;
+            model.prefetch(first_row..(first_row + visible_rows).min(row_count));
+        }
+
         let data = self.data();
         let mut inner = data.inner.borrow_mut();
         let one_and_a_half_screen = listview_height * 3 as Coord / 2 as Coord;
@@ -848,11 +1365,15 @@ pub fn ensure_updated_listview(
                     if c.1.is_none() {
                         c.1 = Some(init());
                     }
-                    c.1.as_ref().unwrap().update(new_offset, model.row_data(new_offset).unwrap());
+                    let component = c.1.as_ref().unwrap();
+                    model
+                        .visit_row_data(new_offset, &mut |data| component.update(new_offset, data));
                     c.0 = RepeatedComponentState::Clean;
                 }
-                let h =
-                    c.1.as_ref().unwrap().as_pin_ref().get_item_ref(0).as_ref().geometry().height();
+                let h = listview_item_size(
+                    c.1.as_ref().unwrap().as_pin_ref().get_item_ref(0),
+                    orientation,
+                );
                 if it_y + h >= -vp_y || new_offset + 1 >= row_count {
                     break;
                 }
@@ -865,30 +1386,31 @@ pub fn ensure_updated_listview(
             (inner.offset, first_item_y)
         };
 
-        loop {
+        let (position, visible_rows_end, new_viewport_y, overscroll) = loop {
             // If there is a gap before the new_offset and the beginning of the visible viewport,
             // try to fill it with items. First look at items that are before new_offset in the
             // inner.components, if any.
             while new_offset > inner.offset && new_offset_y > -vp_y {
                 new_offset -= 1;
-                new_offset_y -= inner.components[new_offset - inner.offset]
-                    .1
-                    .as_ref()
-                    .unwrap()
-                    .as_pin_ref()
-                    .get_item_ref(0)
-                    .as_ref()
-                    .geometry()
-                    .height();
+                new_offset_y -= listview_item_size(
+                    inner.components[new_offset - inner.offset]
+                        .1
+                        .as_ref()
+                        .unwrap()
+                        .as_pin_ref()
+                        .get_item_ref(0),
+                    orientation,
+                );
             }
             // If there is still a gap, fill it with new component before
             let mut new_components = Vec::new();
             while new_offset > 0 && new_offset_y > -vp_y {
                 new_offset -= 1;
                 let new_component = init();
-                new_component.update(new_offset, model.row_data(new_offset).unwrap());
+                model
+                    .visit_row_data(new_offset, &mut |data| new_component.update(new_offset, data));
                 new_offset_y -=
-                    new_component.as_pin_ref().get_item_ref(0).as_ref().geometry().height();
+                    listview_item_size(new_component.as_pin_ref().get_item_ref(0), orientation);
                 new_components.push(new_component);
             }
             if !new_components.is_empty() {
@@ -914,11 +1436,12 @@ pub fn ensure_updated_listview(
                     if c.1.is_none() {
                         c.1 = Some(init());
                     }
-                    c.1.as_ref().unwrap().update(idx, model.row_data(idx).unwrap());
+                    let component = c.1.as_ref().unwrap();
+                    model.visit_row_data(idx, &mut |data| component.update(idx, data));
                     c.0 = RepeatedComponentState::Clean;
                 }
                 if let Some(x) = c.1.as_ref() {
-                    x.as_pin_ref().listview_layout(&mut y, viewport_width);
+                    listview_layout_row(x, orientation, &mut y, viewport_width);
                 }
                 idx += 1;
                 if y >= -vp_y + listview_height {
@@ -929,8 +1452,8 @@ pub fn ensure_updated_listview(
             // create more items until there is no more room.
             while y < -vp_y + listview_height && idx < row_count {
                 let new_component = init();
-                new_component.update(idx, model.row_data(idx).unwrap());
-                new_component.as_pin_ref().listview_layout(&mut y, viewport_width);
+                model.visit_row_data(idx, &mut |data| new_component.update(idx, data));
+                listview_layout_row(&new_component, orientation, &mut y, viewport_width);
                 inner.components.push((RepeatedComponentState::Clean, Some(new_component)));
                 idx += 1;
             }
@@ -956,10 +1479,81 @@ pub fn ensure_updated_listview(
             inner.anchor_y = inner.offset as Coord * inner.cached_item_height;
             viewport_height.set(inner.cached_item_height * row_count as Coord);
             let new_viewport_y = -inner.anchor_y + vp_y + new_offset_y;
-            viewport_y.set(new_viewport_y);
             inner.previous_viewport_y = new_viewport_y;
-            break;
+            break (
+                inner.first_visible_row(orientation, new_offset_y, -vp_y),
+                inner.visible_rows_end(orientation, new_offset_y, -vp_y + listview_height),
+                new_viewport_y,
+                requested_vp_y - vp_y,
+            );
+        };
+        drop(inner);
+        self.set_listview_viewport_y(viewport_y, requested_vp_y, new_viewport_y, overscroll);
+        self.notify_listview_scroll_position(position);
+        self.notify_listview_visible_rows_end(visible_rows_end, row_count);
+    }
+
+    /// Part of `Self::ensure_updated_listview` when the model is smaller than the windowing threshold:
+    /// all the rows are instantiated and laid out.
+    fn ensure_updated_listview_all_rows(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        init: impl Fn() -> ComponentRc<C>,
+        model: &ModelRc<C::Data>,
+        viewport_width: Pin<&Property<Coord>>,
+        viewport_height: Pin<&Property<Coord>>,
+        viewport_y: Pin<&Property<Coord>>,
+        listview_height: Pin<&Property<Coord>>,
+    ) {
+        let row_count = model.row_count();
+        let (anchor_row, anchor_y) = {
+            let mut inner = self.0.inner.borrow_mut();
+            let anchor = (inner.offset, inner.anchor_y);
+            if inner.offset > 0 {
+                // We were only showing a window of the model: keep the existing components at their row
+                let offset = inner.offset;
+                inner.components.splice(
+                    0..0,
+                    core::iter::repeat((RepeatedComponentState::Dirty, None)).take(offset),
+                );
+                inner.offset = 0;
+            }
+            anchor
+        };
+        self.ensure_updated_impl(init, model, row_count);
+
+        let mut inner = self.0.inner.borrow_mut();
+        let mut y = 0 as Coord;
+        let mut anchor_actual_y = anchor_y;
+        for (row, c) in inner.components.iter().enumerate() {
+            if row == anchor_row {
+                anchor_actual_y = y;
+            }
+            if let Some(x) = c.1.as_ref() {
+                listview_layout_row(x, orientation, &mut y, viewport_width);
+            }
         }
+
+        // The anchor row was placed at an estimated position, move the viewport so that it
+        // stays at the same position on screen.
+        let requested_vp_y = viewport_y.get();
+        let min_vp_y = (listview_height.get() - y).min(0 as _);
+        let vp_y = (requested_vp_y.min(0 as _) - (anchor_actual_y - anchor_y)).max(min_vp_y);
+        let overscroll = requested_vp_y - requested_vp_y.min(0 as _).max(min_vp_y);
+        viewport_height.set(y);
+
+        // All the positions are exact, so when switching to the windowed mode, the first row is
+        // the anchor and the visible rows are searched as if we scrolled down from the top.
+        inner.cached_item_height = y / row_count as Coord;
+        inner.anchor_y = 0 as Coord;
+        inner.previous_viewport_y = 0 as Coord;
+        let position = inner.first_visible_row(orientation, 0 as Coord, -vp_y);
+        let visible_rows_end =
+            inner.visible_rows_end(orientation, 0 as Coord, -vp_y + listview_height.get());
+        drop(inner);
+        self.set_listview_viewport_y(viewport_y, requested_vp_y, vp_y, overscroll);
+        self.notify_listview_scroll_position(position);
+        self.notify_listview_visible_rows_end(visible_rows_end, row_count);
     }
 
     /// Sets the data directly in the model
@@ -969,7 +1563,7 @@ pub fn model_set_row_data(self: Pin<&Self>, row: usize, data: C::Data) {
         if let Some(c) = self.data().inner.borrow_mut().components.get_mut(row) {
             if c.0 == RepeatedComponentState::Dirty {
                 if let Some(comp) = c.1.as_ref() {
-                    comp.update(row, model.row_data(row).unwrap());
+                    model.visit_row_data(row, &mut |data| comp.update(row, data));
                     c.0 = RepeatedComponentState::Clean;
                 }
             }
@@ -1016,6 +1610,20 @@ pub fn range(&self) -> (usize, usize) {
         (inner.offset, inner.offset + inner.components.len())
     }
 
+    /// Return the range of rows of the model for which a component is currently instantiated.
+    ///
+    /// In a ListView that only instantiates the visible rows, this is the visible window of the
+    /// model, which can be compared to [`Self::model_row_count()`].
+    pub fn instantiated_range(&self) -> core::ops::Range<usize> {
+        let (start, end) = self.range();
+        start..end
+    }
+
+    /// Return the number of rows in the model, including the ones that are not instantiated.
+    pub fn model_row_count(self: Pin<&Self>) -> usize {
+        self.model().row_count()
+    }
+
     pub fn component_at(&self, index: usize) -> Option<ComponentRc<C>> {
         let inner = self.0.inner.borrow();
         inner
@@ -1138,6 +1746,105 @@ fn test_data_tracking() {
     assert!(tracker.is_dirty());
 }
 
+#[test]
+fn test_with_row_data_does_not_clone() {
+    #[derive(PartialEq, Debug)]
+    struct Large(Vec<u32>);
+    thread_local!(static CLONES: Cell<usize> = Cell::new(0));
+    impl Clone for Large {
+        fn clone(&self) -> Self {
+            CLONES.with(|c| c.set(c.get() + 1));
+            Self(self.0.clone())
+        }
+    }
+
+    let model = Rc::new(SharedVectorModel::from(SharedVector::from_slice(&[
+        Large(vec![1; 1000]),
+        Large(vec![2; 1000]),
+    ])));
+    CLONES.with(|c| c.set(0));
+    let handle = ModelRc::from(model.clone());
+    assert_eq!(handle.with_row_data(1, |data| data.0[0]), Some(2));
+    assert_eq!(model.with_row_data(0, |data| data.0.len()), Some(1000));
+    assert_eq!(handle.with_row_data(2, |data| data.0.len()), None);
+    assert_eq!(CLONES.with(Cell::get), 0);
+
+    // Models that don't implement visit_row_data fall back to row_data
+    let mapped = handle.map(|x| x);
+    assert_eq!(mapped.with_row_data(0, |data| data.0[0]), Some(1));
+    assert_eq!(CLONES.with(Cell::get), 1);
+}
+
+#[test]
+fn test_visit_row_data_modifying_the_model() {
+    let model = VecModel::from(vec![1, 2]);
+    model.visit_row_data(0, &mut |data| model.push(*data + 10));
+    assert_eq!(model.with_row_data(1, |data| model.set_row_data(1, *data + 1)), Some(()));
+    assert_eq!(model.iter().collect::<Vec<_>>(), vec![1, 3, 11]);
+
+    let model = SharedVectorModel::from(SharedVector::from_slice(&[1, 2]));
+    model.visit_row_data(0, &mut |data| model.push(*data + 10));
+    assert_eq!(model.with_row_data(1, |data| model.set_row_data(1, *data + 1)), Some(()));
+    assert_eq!(model.iter().collect::<Vec<_>>(), vec![1, 3, 11]);
+}
+
+#[test]
+fn test_vecmodel_to_vec_from_vec() {
+    let model = VecModel::from_vec(vec![1, 2, 3]);
+    model.push(4);
+    assert_eq!(model.to_vec(), vec![1, 2, 3, 4]);
+    assert_eq!(model.row_count(), 4);
+}
+
+#[test]
+fn test_vecmodel_iter_for_each_row_collect() {
+    let model: VecModel<i32> = (1..=3).collect();
+    assert_eq!(model.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    // The iterator reads the rows one by one, and sees the rows added meanwhile
+    let mut iter = model.iter();
+    assert_eq!(iter.next(), Some(1));
+    model.push(4);
+    assert_eq!(iter.count(), 3);
+
+    let mut rows = Vec::new();
+    model.for_each_row(|row, data| rows.push((row, *data)));
+    assert_eq!(rows, vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_vecmodel_serde_round_trip() {
+    #[derive(Default)]
+    struct CountingView {
+        added_rows: Cell<usize>,
+    }
+    impl ModelChangeListener for CountingView {
+        fn row_changed(&self, _: usize) {}
+        fn row_added(&self, _: usize, count: usize) {
+            self.added_rows.set(self.added_rows.get() + count);
+        }
+        fn row_removed(&self, _: usize, _: usize) {}
+        fn reset(&self) {}
+    }
+
+    let view = Box::pin(ModelChangeListenerContainer::<CountingView>::default());
+    let model = VecModel::from(vec![String::from("a"), String::from("b")]);
+    model.model_tracker().attach_peer(Pin::as_ref(&view).model_peer());
+
+    let json = serde_json::to_string(&model).unwrap();
+    assert_eq!(json, r#"["a","b"]"#);
+
+    let restored: VecModel<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.to_vec(), model.to_vec());
+
+    // The restored model doesn't notify the view of the original model
+    restored.push(String::from("c"));
+    assert_eq!(view.added_rows.get(), 0);
+    model.push(String::from("c"));
+    assert_eq!(view.added_rows.get(), 1);
+}
+
 #[test]
 fn test_vecmodel_set_vec() {
     #[derive(Default)]
@@ -1146,6 +1853,7 @@ struct TestView {
         // The last field in the tuple is the row size the model reports at the time
         // of callback
         changed_rows: RefCell<Vec<(usize, usize)>>,
+        changed_ranges: RefCell<Vec<core::ops::Range<usize>>>,
         added_rows: RefCell<Vec<(usize, usize, usize)>>,
         removed_rows: RefCell<Vec<(usize, usize, usize)>>,
         reset: RefCell<usize>,
@@ -1154,6 +1862,7 @@ struct TestView {
     impl TestView {
         fn clear(&self) {
             self.changed_rows.borrow_mut().clear();
+            self.changed_ranges.borrow_mut().clear();
             self.added_rows.borrow_mut().clear();
             self.removed_rows.borrow_mut().clear();
         }
@@ -1170,6 +1879,10 @@ fn row_changed(&self, row: usize) {
             self.changed_rows.borrow_mut().push((row, self.row_count()));
         }
 
+        fn row_changed_range(&self, range: core::ops::Range<usize>) {
+            self.changed_ranges.borrow_mut().push(range);
+        }
+
         fn row_added(&self, index: usize, count: usize) {
             self.added_rows.borrow_mut().push((index, count, self.row_count()));
         }
@@ -1202,4 +1915,478 @@ fn reset(&self) {
     assert!(view.removed_rows.borrow().is_empty());
     assert_eq!(*view.reset.borrow(), 1);
     view.clear();
+
+    model.set_vec(vec![0; 10]);
+    view.clear();
+
+    // Contiguous rows are notified as one range
+    model.set_rows([(2, 1), (0, 1), (1, 1)]);
+    assert_eq!(model.array.borrow().as_slice(), &[1, 1, 1, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(view.changed_rows.borrow().is_empty());
+    assert_eq!(&*view.changed_ranges.borrow(), &[0..3]);
+    view.clear();
+
+    // Dense enough rows are also notified as one range
+    model.set_rows([(4, 2), (7, 2)]);
+    assert!(view.changed_rows.borrow().is_empty());
+    assert_eq!(&*view.changed_ranges.borrow(), &[4..8]);
+    view.clear();
+
+    // Sparse rows are notified one by one, and rows out of bounds are ignored
+    model.set_rows([(9, 3), (0, 3), (10, 3)]);
+    assert_eq!(&*view.changed_rows.borrow(), &[(0, 10), (9, 10)]);
+    assert!(view.changed_ranges.borrow().is_empty());
+    view.clear();
+
+    model.set_rows(core::iter::empty());
+    assert!(view.changed_rows.borrow().is_empty());
+    assert!(view.changed_ranges.borrow().is_empty());
+
+    model.insert(1, 5);
+    assert_eq!(&*view.added_rows.borrow(), &[(1, 1, 11)]);
+    assert!(view.changed_rows.borrow().is_empty());
+    view.clear();
+
+    model.swap(0, 1);
+    assert_eq!(&model.array.borrow()[..3], &[5, 3, 1]);
+    assert_eq!(&*view.changed_rows.borrow(), &[(0, 11), (1, 11)]);
+    assert!(view.added_rows.borrow().is_empty());
+    assert!(view.removed_rows.borrow().is_empty());
+    view.clear();
+
+    model.swap(2, 2);
+    assert!(view.changed_rows.borrow().is_empty());
+    assert_eq!(*view.reset.borrow(), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_vecmodel_insert_out_of_bounds() {
+    VecModel::from(vec![1, 2]).insert(3, 0);
+}
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+mod test_row {
+    use super::*;
+    use crate::accessibility::AccessibleStringProperty;
+    use crate::component::{Component, ComponentWeak, IndexRange};
+    use crate::item_tree::{ItemTreeNode, ItemVisitorVTable, ItemWeak, VisitChildrenResult};
+    use crate::items::{AccessibleRole, ItemVTable, Rectangle};
+    use crate::layout::LayoutInfo;
+    use crate::slice::Slice;
+
+    /// A row that is just a rectangle with the height, or the width in a horizontal ListView,
+    /// from the model
+    #[derive(Default)]
+    pub struct TestRow {
+        rectangle: Rectangle,
+    }
+
+    impl TestRow {
+        fn rectangle(self: Pin<&Self>) -> Pin<&Rectangle> {
+            unsafe { self.map_unchecked(|s| &s.rectangle) }
+        }
+    }
+
+    impl Component for TestRow {
+        fn visit_children_item(
+            self: Pin<&Self>,
+            _: isize,
+            _: TraversalOrder,
+            _: vtable::VRefMut<ItemVisitorVTable>,
+        ) -> VisitChildrenResult {
+            unimplemented!("Not needed for this test")
+        }
+        fn get_item_ref(self: Pin<&Self>, _: usize) -> Pin<vtable::VRef<ItemVTable>> {
+            vtable::VRef::new_pin(self.rectangle())
+        }
+        fn get_item_tree(self: Pin<&Self>) -> Slice<ItemTreeNode> {
+            Slice::default()
+        }
+        fn parent_node(self: Pin<&Self>, _: &mut ItemWeak) {}
+        fn layout_info(self: Pin<&Self>, _: Orientation) -> LayoutInfo {
+            unimplemented!("Not needed for this test")
+        }
+        fn subtree_index(self: Pin<&Self>) -> usize {
+            core::usize::MAX
+        }
+        fn get_subtree_range(self: Pin<&Self>, _: usize) -> IndexRange {
+            unimplemented!("Not needed for this test")
+        }
+        fn get_subtree_component(self: Pin<&Self>, _: usize, _: usize, _: &mut ComponentWeak) {
+            unimplemented!("Not needed for this test")
+        }
+        fn accessible_role(self: Pin<&Self>, _: usize) -> AccessibleRole {
+            unimplemented!("Not needed for this test")
+        }
+        fn accessible_string_property(
+            self: Pin<&Self>,
+            _: usize,
+            _: AccessibleStringProperty,
+            _: &mut SharedString,
+        ) {
+        }
+    }
+
+    crate::component::ComponentVTable_static!(static TEST_ROW_VT for TestRow);
+
+    impl RepeatedComponent for TestRow {
+        type Data = Coord;
+        fn update(&self, _: usize, size: &Coord) {
+            self.rectangle.width.set(*size);
+            self.rectangle.height.set(*size);
+        }
+        fn listview_layout(self: Pin<&Self>, offset_y: &mut Coord, _: Pin<&Property<Coord>>) {
+            self.rectangle.y.set(*offset_y);
+            *offset_y += Rectangle::FIELD_OFFSETS.height.apply_pin(self.rectangle()).get();
+        }
+        fn listview_layout_horizontal(
+            self: Pin<&Self>,
+            offset_x: &mut Coord,
+            _: Pin<&Property<Coord>>,
+        ) {
+            self.rectangle.x.set(*offset_x);
+            *offset_x += Rectangle::FIELD_OFFSETS.width.apply_pin(self.rectangle()).get();
+        }
+    }
+
+    /// Returns a repeater of `TestRow`s for the rows of `model`
+    pub fn repeater(model: &Rc<VecModel<Coord>>) -> Pin<Box<Repeater<TestRow>>> {
+        let repeater = Box::pin(Repeater::<TestRow>::default());
+        repeater.set_model_binding({
+            let model = model.clone();
+            move || ModelRc::from(model.clone())
+        });
+        repeater
+    }
+
+    /// Updates `repeater`, and counts the components it creates in `created`
+    pub fn ensure_updated(repeater: Pin<&Repeater<TestRow>>, created: &Cell<usize>) {
+        repeater.ensure_updated(|| {
+            created.set(created.get() + 1);
+            vtable::VRc::new(TestRow::default())
+        })
+    }
+
+    /// A ListView, 100px wide and 50px high, that only instantiates the visible rows of the model
+    pub struct TestListView {
+        pub repeater: Pin<Box<Repeater<TestRow>>>,
+        pub viewport_width: Pin<Box<Property<Coord>>>,
+        pub viewport_height: Pin<Box<Property<Coord>>>,
+        pub viewport_y: Pin<Box<Property<Coord>>>,
+        pub listview_height: Pin<Box<Property<Coord>>>,
+    }
+
+    impl TestListView {
+        pub fn new(model: &Rc<VecModel<Coord>>) -> Self {
+            let repeater = repeater(model);
+            repeater.set_listview_windowing_threshold(0);
+            Self {
+                repeater,
+                viewport_width: Box::pin(Property::new(0 as Coord)),
+                viewport_height: Box::pin(Property::new(0 as Coord)),
+                viewport_y: Box::pin(Property::new(0 as Coord)),
+                listview_height: Box::pin(Property::new(50 as Coord)),
+            }
+        }
+
+        pub fn update(&self) {
+            self.repeater.as_ref().ensure_updated_listview(
+                || vtable::VRc::new(TestRow::default()),
+                self.viewport_width.as_ref(),
+                self.viewport_height.as_ref(),
+                self.viewport_y.as_ref(),
+                100 as Coord,
+                self.listview_height.as_ref(),
+            )
+        }
+    }
+}
+
+#[test]
+fn test_listview_scroll_callback() {
+    use test_row::TestListView;
+
+    let model = Rc::new(VecModel::<Coord>::default());
+    let listview = TestListView::new(&model);
+    let TestListView { repeater, viewport_y, .. } = &listview;
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    repeater.set_listview_scroll_callback({
+        let reported = reported.clone();
+        move |row, fraction| reported.borrow_mut().push((row, fraction))
+    });
+    let update = || listview.update();
+
+    // Nothing is reported while the model is empty
+    update();
+    assert!(reported.borrow().is_empty());
+
+    for _ in 0..100 {
+        model.push(10 as Coord);
+    }
+    update();
+    assert_eq!(*reported.borrow(), vec![(0, 0.)]);
+
+    // Half of the third row is scrolled out
+    viewport_y.set(-25 as Coord);
+    update();
+    assert_eq!(reported.borrow().last(), Some(&(2, 0.5)));
+
+    // The position didn't change
+    update();
+    assert_eq!(reported.borrow().len(), 2);
+
+    // Jump far away
+    viewport_y.set(-500 as Coord);
+    update();
+    assert_eq!(reported.borrow().last(), Some(&(50, 0.)));
+}
+
+#[test]
+fn test_listview_end_reached_callback() {
+    use test_row::TestListView;
+
+    let model = Rc::new(VecModel::<Coord>::default());
+    let listview = TestListView::new(&model);
+    let TestListView { repeater, viewport_y, .. } = &listview;
+    let calls = Rc::new(Cell::new(0));
+    repeater.set_listview_end_reached_callback(5, {
+        let calls = calls.clone();
+        move || calls.set(calls.get() + 1)
+    });
+    let update = || listview.update();
+
+    // An empty model asks for the first rows
+    update();
+    assert_eq!(calls.get(), 1);
+
+    for _ in 0..100 {
+        model.push(10 as Coord);
+    }
+    update();
+    assert_eq!(calls.get(), 1);
+
+    // The rows 85 to 89 are visible, more than 5 rows before the end
+    viewport_y.set(-850 as Coord);
+    update();
+    assert_eq!(calls.get(), 1);
+
+    // The rows 90 to 94 are visible
+    viewport_y.set(-900 as Coord);
+    update();
+    assert_eq!(calls.get(), 2);
+    // Only once for the same row count
+    viewport_y.set(-910 as Coord);
+    update();
+    assert_eq!(calls.get(), 2);
+
+    // More rows were loaded, the end is far again
+    for _ in 0..50 {
+        model.push(10 as Coord);
+    }
+    update();
+    assert_eq!(calls.get(), 2);
+    viewport_y.set(-1400 as Coord);
+    update();
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn test_listview_overscroll_springback() {
+    use test_row::TestListView;
+
+    let model = Rc::new(VecModel::<Coord>::from(vec![10 as Coord; 100]));
+    let listview = TestListView::new(&model);
+    let TestListView { repeater, viewport_y, .. } = &listview;
+    repeater.set_listview_overscroll_springback(Some(core::time::Duration::from_millis(100)));
+    let update = || listview.update();
+    update();
+    assert_eq!(viewport_y.as_ref().get(), 0 as Coord);
+
+    // Scrolled 10px past the bottom, which is at -950
+    viewport_y.set(-960 as Coord);
+    update();
+    assert_eq!(viewport_y.as_ref().get(), -960 as Coord);
+
+    crate::tests::slint_mock_elapsed_time(50);
+    update();
+    assert!(viewport_y.as_ref().get() > -960 as Coord && viewport_y.as_ref().get() < -950 as Coord);
+
+    crate::tests::slint_mock_elapsed_time(50);
+    update();
+    assert_eq!(viewport_y.as_ref().get(), -950 as Coord);
+    update();
+    assert_eq!(viewport_y.as_ref().get(), -950 as Coord);
+
+    // Past the top
+    viewport_y.set(30 as Coord);
+    update();
+    assert_eq!(viewport_y.as_ref().get(), 30 as Coord);
+    crate::tests::slint_mock_elapsed_time(100);
+    update();
+    assert_eq!(viewport_y.as_ref().get(), 0 as Coord);
+
+    // Without spring back, the viewport is clamped right away
+    repeater.set_listview_overscroll_springback(None);
+    viewport_y.set(-960 as Coord);
+    update();
+    assert_eq!(viewport_y.as_ref().get(), -950 as Coord);
+}
+
+#[test]
+fn test_listview_horizontal() {
+    use test_row::TestRow;
+
+    let model = Rc::new(VecModel::<Coord>::from(vec![10 as Coord; 100]));
+    let repeater = test_row::repeater(&model);
+    repeater.set_listview_windowing_threshold(0);
+
+    let viewport_width = Box::pin(Property::new(0 as Coord));
+    let viewport_height = Box::pin(Property::new(0 as Coord));
+    let viewport_x = Box::pin(Property::new(0 as Coord));
+    let listview_width = Box::pin(Property::new(50 as Coord));
+    let update = || {
+        repeater.as_ref().ensure_updated_listview_horizontal(
+            || vtable::VRc::new(TestRow::default()),
+            viewport_width.as_ref(),
+            viewport_height.as_ref(),
+            viewport_x.as_ref(),
+            listview_width.as_ref(),
+            100 as Coord,
+        )
+    };
+
+    update();
+    assert_eq!(repeater.instantiated_range(), 0..5);
+    assert_eq!(viewport_width.as_ref().get(), 1000 as Coord);
+    assert_eq!(viewport_height.as_ref().get(), 100 as Coord);
+
+    // The third row is half visible, the rows up to the one right of the viewport are instantiated
+    viewport_x.set(-25 as Coord);
+    update();
+    assert_eq!(repeater.instantiated_range(), 2..8);
+
+    // Jump far away
+    viewport_x.set(-500 as Coord);
+    update();
+    assert_eq!(repeater.instantiated_range(), 50..55);
+
+    // Below the windowing threshold, all the rows are laid out from left to right
+    repeater.set_listview_windowing_threshold(1000);
+    viewport_x.set(0 as Coord);
+    update();
+    assert_eq!(repeater.instantiated_range(), 0..100);
+    assert_eq!(viewport_width.as_ref().get(), 1000 as Coord);
+    assert_eq!(viewport_x.as_ref().get(), 0 as Coord);
+}
+
+#[test]
+fn test_repeater_set_vec() {
+    let model = Rc::new(VecModel::<Coord>::from(vec![10 as Coord; 3]));
+    let repeater = test_row::repeater(&model);
+    let created = Cell::new(0);
+    let update = || test_row::ensure_updated(repeater.as_ref(), &created);
+
+    update();
+    assert_eq!(repeater.len(), 3);
+    assert_eq!(created.get(), 3);
+
+    // The whole model is replaced at once and all the rows are rebuilt in a single update
+    model.set_vec(vec![20 as Coord; 10]);
+    update();
+    assert_eq!(repeater.len(), 10);
+    assert_eq!(created.get(), 13);
+
+    // Nothing to do when the model didn't change
+    update();
+    assert_eq!(created.get(), 13);
+
+    model.set_vec(Vec::new());
+    update();
+    assert_eq!(repeater.len(), 0);
+}
+
+#[test]
+fn test_repeater_clear() {
+    let model = Rc::new(VecModel::<Coord>::from(vec![10 as Coord; 5]));
+    let repeater = test_row::repeater(&model);
+    let created = Cell::new(0);
+    let update = || test_row::ensure_updated(repeater.as_ref(), &created);
+
+    update();
+    let first_row = vtable::VRc::downgrade(&repeater.component_at(0).unwrap());
+
+    model.clear();
+    assert_eq!(model.row_count(), 0);
+    // The components are dropped right away, not at the next update
+    assert_eq!(repeater.len(), 0);
+    assert!(first_row.upgrade().is_none());
+    update();
+    assert_eq!(repeater.len(), 0);
+
+    // Clearing an empty model doesn't do anything
+    model.clear();
+    model.push(20 as Coord);
+    update();
+    assert_eq!(repeater.len(), 1);
+    assert_eq!(created.get(), 6);
+}
+
+#[test]
+fn test_repeater_reset_rebuilds_all() {
+    let model = Rc::new(VecModel::<Coord>::from(vec![10 as Coord; 3]));
+    let repeater = test_row::repeater(&model);
+    let created = Cell::new(0);
+    let update = || test_row::ensure_updated(repeater.as_ref(), &created);
+
+    update();
+    assert_eq!(created.get(), 3);
+
+    // A changed row reuses its component
+    model.set_row_data(1, 20 as Coord);
+    update();
+    assert_eq!(created.get(), 3);
+
+    // After a reset, the components are dropped and all the rows are instantiated again
+    model.notify.reset();
+    update();
+    assert_eq!(created.get(), 6);
+    assert_eq!(repeater.len(), 3);
+}
+
+#[test]
+fn test_listview_instantiated_range() {
+    use test_row::TestListView;
+
+    let model = Rc::new(VecModel::<Coord>::from(vec![10 as Coord; 100]));
+    let listview = TestListView::new(&model);
+    let TestListView { repeater, viewport_y, .. } = &listview;
+    let update = || listview.update();
+
+    update();
+    assert_eq!(repeater.as_ref().model_row_count(), 100);
+    assert_eq!(repeater.instantiated_range(), 0..5);
+    assert_eq!(repeater.len(), 5);
+
+    // The third row is half visible, the rows up to the one below the viewport are instantiated
+    viewport_y.set(-25 as Coord);
+    update();
+    assert_eq!(repeater.instantiated_range(), 2..8);
+
+    // Jump far away
+    viewport_y.set(-500 as Coord);
+    update();
+    assert_eq!(repeater.instantiated_range(), 50..55);
+
+    // The end of the model
+    viewport_y.set(-950 as Coord);
+    update();
+    assert_eq!(repeater.instantiated_range(), 95..100);
+
+    // Below the windowing threshold, all the rows are instantiated
+    repeater.set_listview_windowing_threshold(1000);
+    update();
+    assert_eq!(repeater.instantiated_range(), 0..100);
+    assert_eq!(repeater.as_ref().model_row_count(), 100);
 }
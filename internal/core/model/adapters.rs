@@ -83,6 +83,10 @@
 /// assert_eq!(mapped_model.row_data(2).unwrap(), SharedString::from("Tisch, Roman"));
 ///
 /// ```
+///
+/// The map function only goes one way, so the `MapModel` can't write rows back to the other
+/// Model: [`Model::set_row_data`] does nothing. Modify the other Model directly instead, as
+/// above.
 pub struct MapModel<M, F> {
     wrapped_model: M,
     map_function: F,
@@ -121,6 +125,8 @@ impl<M, F, T, U> MapModel<M, F>
     F: Fn(T) -> U,
     M: Model<Data = T>,
 {
+    /// Creates a new MapModel based on the given `model` and mapped by `map_function`.
+    /// Alternatively you can use [`ModelExt::map`] on your Model.
     pub fn new(model: M, map_function: F) -> Self {
         Self { wrapped_model: model, map_function }
     }
@@ -139,6 +145,245 @@ fn test_map_model() {
     assert_eq!(map.row_data(1).unwrap(), "2");
 }
 
+#[test]
+fn test_map_model_notifications() {
+    #[derive(Default)]
+    struct TestView {
+        changes: RefCell<Vec<(&'static str, usize, usize)>>,
+    }
+    impl ModelChangeListener for TestView {
+        fn row_changed(&self, row: usize) {
+            self.changes.borrow_mut().push(("changed", row, 1));
+        }
+        fn row_added(&self, index: usize, count: usize) {
+            self.changes.borrow_mut().push(("added", index, count));
+        }
+        fn row_removed(&self, index: usize, count: usize) {
+            self.changes.borrow_mut().push(("removed", index, count));
+        }
+        fn reset(&self) {
+            self.changes.borrow_mut().push(("reset", 0, 0));
+        }
+    }
+
+    let wrapped_rc = Rc::new(VecModel::from(vec![1, 2, 3]));
+    let map = MapModel::new(wrapped_rc.clone(), |x| x * 10);
+
+    // A repeater attaches to the tracker of the MapModel, and is notified of the changes of the
+    // wrapped model
+    let view = Box::pin(ModelChangeListenerContainer::<TestView>::default());
+    map.model_tracker().attach_peer(view.as_ref().model_peer());
+
+    wrapped_rc.set_row_data(1, 5);
+    wrapped_rc.push(4);
+    wrapped_rc.remove(0);
+    assert_eq!(*view.changes.borrow(), vec![("changed", 1, 1), ("added", 3, 1), ("removed", 0, 1)]);
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![50, 30, 40]);
+}
+
+struct LazyMapModelInner<M, F, U>
+where
+    M: Model + 'static,
+    F: Fn(M::Data) -> U + 'static,
+    U: Clone + 'static,
+{
+    wrapped_model: M,
+    map_function: F,
+    // The mapped rows, indexed like the rows of the wrapped model. `None` for the rows that
+    // were not requested yet or that changed since they were mapped.
+    cache: RefCell<Vec<Option<U>>>,
+    notify: ModelNotify,
+}
+
+impl<M, F, U> ModelChangeListener for LazyMapModelInner<M, F, U>
+where
+    M: Model + 'static,
+    F: Fn(M::Data) -> U + 'static,
+    U: Clone + 'static,
+{
+    fn row_changed(&self, row: usize) {
+        if let Some(cached) = self.cache.borrow_mut().get_mut(row) {
+            *cached = None;
+        }
+        self.notify.row_changed(row);
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        {
+            let mut cache = self.cache.borrow_mut();
+            let index = index.min(cache.len());
+            cache.splice(index..index, core::iter::repeat_with(|| None).take(count));
+        }
+        self.notify.row_added(index, count);
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        {
+            let mut cache = self.cache.borrow_mut();
+            let start = index.min(cache.len());
+            let end = (index + count).min(cache.len());
+            cache.drain(start..end);
+        }
+        self.notify.row_removed(index, count);
+    }
+
+    fn reset(&self) {
+        let mut cache = self.cache.borrow_mut();
+        cache.clear();
+        cache.resize_with(self.wrapped_model.row_count(), || None);
+        drop(cache);
+        self.notify.reset();
+    }
+}
+
+/// Provides rows that are generated by a map function based on the rows of another Model,
+/// and keeps the mapped rows in a cache.
+///
+/// Unlike [`MapModel`], which calls the map function every time a row is accessed, the
+/// `LazyMapModel` calls it the first time a row is accessed and only again after the row was
+/// changed in the other Model. Use it when the map function is expensive.
+///
+/// When rows are added to or removed from the other Model, the cached rows are moved
+/// accordingly. A reset of the other Model clears the cache.
+///
+/// ## Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use slint::{Model, VecModel, SharedString, LazyMapModel};
+/// let model = Rc::new(VecModel::from(vec![1, 2, 3]));
+///
+/// let mapped_model = LazyMapModel::new(model.clone(), |n| SharedString::from(n.to_string()));
+///
+/// assert_eq!(mapped_model.row_data(1).unwrap(), SharedString::from("2"));
+///
+/// model.set_row_data(1, 42);
+///
+/// assert_eq!(mapped_model.row_data(1).unwrap(), SharedString::from("42"));
+/// ```
+///
+/// Alternatively you can use the shortcut [`ModelExt::lazy_map`].
+pub struct LazyMapModel<M, F, U>(
+    Pin<Box<ModelChangeListenerContainer<LazyMapModelInner<M, F, U>>>>,
+)
+where
+    M: Model + 'static,
+    F: Fn(M::Data) -> U + 'static,
+    U: Clone + 'static;
+
+impl<M, F, U> LazyMapModel<M, F, U>
+where
+    M: Model + 'static,
+    F: Fn(M::Data) -> U + 'static,
+    U: Clone + 'static,
+{
+    /// Creates a new LazyMapModel based on the given `wrapped_model` and mapped by `map_function`.
+    /// Alternatively you can use [`ModelExt::lazy_map`] on your Model.
+    pub fn new(wrapped_model: M, map_function: F) -> Self {
+        let mut cache = Vec::new();
+        cache.resize_with(wrapped_model.row_count(), || None);
+        let inner = LazyMapModelInner {
+            wrapped_model,
+            map_function,
+            cache: RefCell::new(cache),
+            notify: Default::default(),
+        };
+
+        let container = Box::pin(ModelChangeListenerContainer::new(inner));
+
+        container.wrapped_model.model_tracker().attach_peer(container.as_ref().model_peer());
+
+        Self(container)
+    }
+
+    /// Clears the cache, so that all rows are mapped again when accessed. You need to run
+    /// this e.g. if the map function depends on mutable state and it has changed.
+    pub fn invalidate(&self) {
+        self.0.reset();
+    }
+}
+
+impl<M, F, U> Model for LazyMapModel<M, F, U>
+where
+    M: Model + 'static,
+    F: Fn(M::Data) -> U + 'static,
+    U: Clone + 'static,
+{
+    type Data = U;
+
+    fn row_count(&self) -> usize {
+        self.0.wrapped_model.row_count()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        if let Some(Some(cached)) = self.0.cache.borrow().get(row) {
+            return Some(cached.clone());
+        }
+        let mapped = (self.0.map_function)(self.0.wrapped_model.row_data(row)?);
+        let mut cache = self.0.cache.borrow_mut();
+        if cache.len() <= row {
+            cache.resize_with(row + 1, || None);
+        }
+        cache[row] = Some(mapped.clone());
+        Some(mapped)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.0.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_lazy_map_model() {
+    let calls = Rc::new(Cell::new(0));
+    let wrapped_rc = Rc::new(VecModel::from(vec![1, 2, 3]));
+    let map = LazyMapModel::new(wrapped_rc.clone(), {
+        let calls = calls.clone();
+        move |x| {
+            calls.set(calls.get() + 1);
+            x.to_string()
+        }
+    });
+
+    // Several passes over the model, like a repeater does during layout, map each row once
+    for _ in 0..3 {
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+    }
+    assert_eq!(calls.get(), 3);
+
+    // Only the changed row is mapped again
+    wrapped_rc.set_row_data(1, 42);
+    for _ in 0..3 {
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec!["1", "42", "3"]);
+    }
+    assert_eq!(calls.get(), 4);
+
+    // Added rows are mapped, the cache of the following rows moves with them
+    wrapped_rc.insert(0, 0);
+    wrapped_rc.push(4);
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec!["0", "1", "42", "3", "4"]);
+    assert_eq!(calls.get(), 6);
+
+    wrapped_rc.remove(1);
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec!["0", "42", "3", "4"]);
+    assert_eq!(calls.get(), 6);
+    assert_eq!(map.row_data(4), None);
+
+    map.invalidate();
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec!["0", "42", "3", "4"]);
+    assert_eq!(calls.get(), 10);
+}
+
 struct FilterModelInner<M, F>
 where
     M: Model + 'static,
@@ -165,6 +410,45 @@ fn build_mapping_vec(&self) {
             .filter_map(|(i, e)| (self.filter_function)(&e).then(|| i))
             .collect();
     }
+
+    /// Re-evaluates the filter on every row and notifies about the rows that got
+    /// filtered in or out, instead of resetting the whole model.
+    fn update_mapping_vec(&self) {
+        let old_mapping = self.mapping.take();
+        self.build_mapping_vec();
+        let new_mapping = self.mapping.borrow().clone();
+
+        // Walk both sorted vectors and notify each run of added or removed rows at the
+        // position it has in the model after the previous notifications.
+        let (mut old_it, mut new_it) =
+            (old_mapping.iter().peekable(), new_mapping.iter().peekable());
+        let mut pos = 0;
+        loop {
+            match (old_it.peek(), new_it.peek()) {
+                (Some(o), Some(n)) if o == n => {
+                    old_it.next();
+                    new_it.next();
+                    pos += 1;
+                }
+                (Some(o), n) if n.map_or(true, |n| o < n) => {
+                    let mut count = 0;
+                    while old_it.next_if(|o| new_it.peek().map_or(true, |n| o < n)).is_some() {
+                        count += 1;
+                    }
+                    self.notify.row_removed(pos, count);
+                }
+                (_, Some(_)) => {
+                    let mut count = 0;
+                    while new_it.next_if(|n| old_it.peek().map_or(true, |o| n < o)).is_some() {
+                        count += 1;
+                    }
+                    self.notify.row_added(pos, count);
+                    pos += count;
+                }
+                (_, None) => break,
+            }
+        }
+    }
 }
 
 impl<M, F> ModelChangeListener for FilterModelInner<M, F>
@@ -346,6 +630,14 @@ pub fn new(wrapped_model: M, filter_function: F) -> Self {
     pub fn apply_filter(&self) {
         self.0.reset();
     }
+
+    /// Reapplies the filter like [`Self::apply_filter`], but instead of resetting the whole
+    /// model, only notifies about the rows that were filtered in or out. Any component
+    /// instantiated by a repeater for a row that stays visible is kept.
+    pub fn reset(&self) {
+        self.0.update_mapping_vec();
+    }
+
     /// Gets the row index of the underlying unfiltered model for a given filtered row index.
     pub fn unfiltered_row(&self, filtered_row: usize) -> usize {
         self.0.mapping.borrow()[filtered_row]
@@ -371,6 +663,15 @@ fn row_data(&self, row: usize) -> Option<Self::Data> {
             .map(|&wrapped_row| self.0.wrapped_model.row_data(wrapped_row).unwrap())
     }
 
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        let wrapped_row = self.0.mapping.borrow().get(row).copied();
+        if let Some(wrapped_row) = wrapped_row {
+            // The wrapped model notifies us, which updates the mapping if the filter no
+            // longer matches the new data.
+            self.0.wrapped_model.set_row_data(wrapped_row, data);
+        }
+    }
+
     fn model_tracker(&self) -> &dyn ModelTracker {
         &self.0.notify
     }
@@ -413,3 +714,399 @@ fn test_filter_model() {
     assert_eq!(filter.row_data(4).unwrap(), 8);
     assert_eq!(filter.row_count(), 5);
 }
+
+#[test]
+fn test_filter_model_set_row_data_and_reset() {
+    #[derive(Default)]
+    struct TestView {
+        // The wrapped row each entry was created for, moved around by the notifications
+        rows: RefCell<Vec<usize>>,
+        added: Cell<usize>,
+        removed: Cell<usize>,
+        reset: Cell<usize>,
+    }
+    impl ModelChangeListener for TestView {
+        fn row_changed(&self, _row: usize) {}
+        fn row_added(&self, index: usize, count: usize) {
+            self.added.set(self.added.get() + count);
+            self.rows.borrow_mut().splice(index..index, core::iter::repeat(usize::MAX).take(count));
+        }
+        fn row_removed(&self, index: usize, count: usize) {
+            self.removed.set(self.removed.get() + count);
+            self.rows.borrow_mut().drain(index..index + count);
+        }
+        fn reset(&self) {
+            self.reset.set(self.reset.get() + 1);
+        }
+    }
+
+    let threshold = Rc::new(Cell::new(3));
+    let wrapped_rc = Rc::new(VecModel::from(vec![1, 2, 3, 4, 5, 6]));
+    let filter = FilterModel::new(wrapped_rc.clone(), {
+        let threshold = threshold.clone();
+        move |x| *x > threshold.get()
+    });
+
+    let view = Box::pin(ModelChangeListenerContainer::<TestView>::default());
+    filter.model_tracker().attach_peer(view.as_ref().model_peer());
+    *view.rows.borrow_mut() = (0..filter.row_count()).map(|r| filter.unfiltered_row(r)).collect();
+
+    filter.set_row_data(0, 10);
+    assert_eq!(wrapped_rc.iter().collect::<Vec<_>>(), vec![1, 2, 3, 10, 5, 6]);
+    assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 5, 6]);
+    // Setting data the filter rejects removes the row
+    filter.set_row_data(1, 0);
+    assert_eq!(wrapped_rc.iter().collect::<Vec<_>>(), vec![1, 2, 3, 10, 0, 6]);
+    assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 6]);
+    assert_eq!(view.removed.get(), 1);
+    view.removed.set(0);
+
+    threshold.set(1);
+    filter.reset();
+    assert_eq!(filter.iter().collect::<Vec<_>>(), vec![2, 3, 10, 6]);
+    assert_eq!((view.added.get(), view.removed.get(), view.reset.get()), (2, 0, 0));
+    assert_eq!(view.rows.borrow().len(), 4);
+    assert_eq!(view.rows.borrow()[2..], [3, 5]);
+
+    threshold.set(5);
+    filter.reset();
+    assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 6]);
+    assert_eq!((view.added.get(), view.removed.get(), view.reset.get()), (2, 2, 0));
+    assert_eq!(*view.rows.borrow(), vec![3, 5]);
+
+    threshold.set(0);
+    filter.reset();
+    assert_eq!(filter.iter().collect::<Vec<_>>(), vec![1, 2, 3, 10, 6]);
+    assert_eq!((view.added.get(), view.removed.get(), view.reset.get()), (5, 2, 0));
+    assert_eq!(view.rows.borrow()[3..], [3, 5]);
+}
+
+struct SortModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> core::cmp::Ordering + 'static,
+{
+    wrapped_model: M,
+    sort_function: RefCell<F>,
+    // This vector saves the indices of the elements of the wrapped model, in the sorted order
+    mapping: RefCell<Vec<usize>>,
+    notify: ModelNotify,
+}
+
+impl<M, F> SortModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> core::cmp::Ordering + 'static,
+{
+    fn build_mapping_vec(&self) {
+        let sort_function = self.sort_function.borrow();
+        // The sort is stable, so rows that compare equal stay in the order of the wrapped model
+        let mut rows = self.wrapped_model.iter().enumerate().collect::<Vec<_>>();
+        rows.sort_by(|a, b| (*sort_function)(&a.1, &b.1));
+        *self.mapping.borrow_mut() = rows.into_iter().map(|(i, _)| i).collect();
+    }
+
+    /// Returns the index in `mapping` where the wrapped row `row` with the given data belongs.
+    /// `row` must not be in `mapping`.
+    fn insertion_index(&self, mapping: &[usize], row: usize, data: &M::Data) -> usize {
+        let sort_function = self.sort_function.borrow();
+        mapping.partition_point(|&r| {
+            match (*sort_function)(&self.wrapped_model.row_data(r).unwrap(), data) {
+                core::cmp::Ordering::Less => true,
+                core::cmp::Ordering::Equal => r < row,
+                core::cmp::Ordering::Greater => false,
+            }
+        })
+    }
+}
+
+impl<M, F> ModelChangeListener for SortModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> core::cmp::Ordering + 'static,
+{
+    fn row_changed(&self, row: usize) {
+        let mut mapping = self.mapping.borrow_mut();
+        let old_index = match mapping.iter().position(|&r| r == row) {
+            Some(index) => index,
+            None => return,
+        };
+        mapping.remove(old_index);
+        let new_index =
+            self.insertion_index(&mapping, row, &self.wrapped_model.row_data(row).unwrap());
+        mapping.insert(new_index, row);
+        drop(mapping);
+
+        if old_index == new_index {
+            self.notify.row_changed(new_index);
+        } else {
+            self.notify.row_removed(old_index, 1);
+            self.notify.row_added(new_index, 1);
+        }
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.mapping.borrow_mut().iter_mut().filter(|r| **r >= index).for_each(|r| *r += count);
+        for row in index..index + count {
+            let mut mapping = self.mapping.borrow_mut();
+            let insertion_index =
+                self.insertion_index(&mapping, row, &self.wrapped_model.row_data(row).unwrap());
+            mapping.insert(insertion_index, row);
+            drop(mapping);
+            self.notify.row_added(insertion_index, 1);
+        }
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let removed = index..index + count;
+        // Remove from the end, so that the indices that are notified are still valid
+        let mut i = self.mapping.borrow().len();
+        while i > 0 {
+            i -= 1;
+            let mut mapping = self.mapping.borrow_mut();
+            if removed.contains(&mapping[i]) {
+                mapping.remove(i);
+                drop(mapping);
+                self.notify.row_removed(i, 1);
+            }
+        }
+        self.mapping.borrow_mut().iter_mut().filter(|r| **r >= index).for_each(|r| *r -= count);
+    }
+
+    fn reset(&self) {
+        self.build_mapping_vec();
+        self.notify.reset();
+    }
+}
+
+/// Provides the rows of another [`Model`], sorted by a comparison function.
+///
+/// The other Model is not modified. When it is updated, the `SortModel` is updated
+/// accordingly: a changed row is moved to its new position without sorting all the rows again.
+/// Rows that compare equal are kept in the order of the other Model.
+///
+/// ## Example
+///
+/// Here we have a [`VecModel`] holding [`SharedString`]s.
+/// It is then sorted into a `SortModel`.
+///
+/// ```
+/// # use slint::{Model, VecModel, SharedString, SortModel};
+/// let model = VecModel::from(vec![
+///     SharedString::from("Lorem"),
+///     SharedString::from("ipsum"),
+///     SharedString::from("dolor"),
+/// ]);
+///
+/// let sorted_model = SortModel::new(model, |a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+///
+/// assert_eq!(sorted_model.row_data(0).unwrap(), SharedString::from("dolor"));
+/// assert_eq!(sorted_model.row_data(1).unwrap(), SharedString::from("ipsum"));
+/// assert_eq!(sorted_model.row_data(2).unwrap(), SharedString::from("Lorem"));
+/// ```
+///
+/// Alternatively you can use the shortcut [`ModelExt::sort_by`].
+/// ```
+/// # use slint::{Model, ModelExt, VecModel, SharedString, SortModel};
+/// let sorted_model = VecModel::from(vec![
+///     SharedString::from("Lorem"),
+///     SharedString::from("ipsum"),
+///     SharedString::from("dolor"),
+/// ]).sort_by(|a, b| a.cmp(b));
+/// # assert_eq!(sorted_model.row_data(0).unwrap(), SharedString::from("Lorem"));
+/// # assert_eq!(sorted_model.row_data(1).unwrap(), SharedString::from("dolor"));
+/// ```
+///
+/// To change the sort order at runtime with [`SortModel::set_sort_function`], use a boxed
+/// function so that the different closures have the same type:
+/// ```
+/// # use slint::{Model, VecModel, SortModel};
+/// let sorted_model = SortModel::new(
+///     VecModel::from(vec![3, 1, 2]),
+///     Box::new(|a: &i32, b: &i32| a.cmp(b)) as Box<dyn Fn(&i32, &i32) -> core::cmp::Ordering>,
+/// );
+/// assert_eq!(sorted_model.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+///
+/// sorted_model.set_sort_function(Box::new(|a: &i32, b: &i32| b.cmp(a)));
+/// assert_eq!(sorted_model.iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+/// ```
+pub struct SortModel<M, F>(Pin<Box<ModelChangeListenerContainer<SortModelInner<M, F>>>>)
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> core::cmp::Ordering + 'static;
+
+impl<M, F> SortModel<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> core::cmp::Ordering + 'static,
+{
+    /// Creates a new SortModel based on the given `wrapped_model` and sorted by `sort_function`.
+    /// Alternatively you can use [`ModelExt::sort_by`] on your Model.
+    pub fn new(wrapped_model: M, sort_function: F) -> Self {
+        let sort_model_inner = SortModelInner {
+            wrapped_model,
+            sort_function: RefCell::new(sort_function),
+            mapping: RefCell::new(Vec::new()),
+            notify: Default::default(),
+        };
+
+        sort_model_inner.build_mapping_vec();
+
+        let container = Box::pin(ModelChangeListenerContainer::new(sort_model_inner));
+
+        container.wrapped_model.model_tracker().attach_peer(container.as_ref().model_peer());
+
+        Self(container)
+    }
+
+    /// Replaces the sort function and sorts the rows again. The model is reset.
+    pub fn set_sort_function(&self, sort_function: F) {
+        *self.0.sort_function.borrow_mut() = sort_function;
+        self.0.reset();
+    }
+
+    /// Manually sort the rows again. You need to run this e.g. if the sort function compares
+    /// against mutable state and it has changed.
+    pub fn apply_sorting(&self) {
+        self.0.reset();
+    }
+
+    /// Gets the row index of the underlying unsorted model for a given sorted row index.
+    pub fn unsorted_row(&self, sorted_row: usize) -> usize {
+        self.0.mapping.borrow()[sorted_row]
+    }
+}
+
+impl<M, F> Model for SortModel<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> core::cmp::Ordering + 'static,
+{
+    type Data = M::Data;
+
+    fn row_count(&self) -> usize {
+        self.0.mapping.borrow().len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        let wrapped_row = self.0.mapping.borrow().get(row).copied();
+        wrapped_row.and_then(|wrapped_row| self.0.wrapped_model.row_data(wrapped_row))
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        let wrapped_row = self.0.mapping.borrow().get(row).copied();
+        if let Some(wrapped_row) = wrapped_row {
+            // The wrapped model notifies us, which moves the row if needed
+            self.0.wrapped_model.set_row_data(wrapped_row, data);
+        }
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.0.notify
+    }
+}
+
+#[test]
+fn test_sort_model() {
+    #[derive(Default)]
+    struct TestView {
+        // The wrapped row each entry was created for, moved around by the notifications
+        rows: RefCell<Vec<usize>>,
+        changed: RefCell<Vec<usize>>,
+        reset: Cell<usize>,
+    }
+    impl ModelChangeListener for TestView {
+        fn row_changed(&self, row: usize) {
+            self.changed.borrow_mut().push(row);
+        }
+        fn row_added(&self, index: usize, count: usize) {
+            self.rows.borrow_mut().splice(index..index, core::iter::repeat(usize::MAX).take(count));
+        }
+        fn row_removed(&self, index: usize, count: usize) {
+            self.rows.borrow_mut().drain(index..index + count);
+        }
+        fn reset(&self) {
+            self.reset.set(self.reset.get() + 1);
+        }
+    }
+
+    let wrapped_rc = Rc::new(VecModel::from(vec![5, 3, 1, 4, 2]));
+    let sorted = SortModel::new(wrapped_rc.clone(), |a: &i32, b: &i32| a.cmp(b));
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(sorted.unsorted_row(0), 2);
+
+    let view = Box::pin(ModelChangeListenerContainer::<TestView>::default());
+    sorted.model_tracker().attach_peer(view.as_ref().model_peer());
+    *view.rows.borrow_mut() = (0..sorted.row_count()).map(|r| sorted.unsorted_row(r)).collect();
+
+    // A change that keeps the position of the row
+    wrapped_rc.set_row_data(1, 3);
+    assert_eq!(*view.changed.borrow(), vec![2]);
+
+    // The row with 5 moves to the front
+    wrapped_rc.set_row_data(0, 0);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(*view.rows.borrow(), vec![usize::MAX, 2, 4, 1, 3]);
+    assert_eq!(*view.changed.borrow(), vec![2]);
+
+    // Writes through to the wrapped model
+    sorted.set_row_data(1, 10);
+    assert_eq!(wrapped_rc.iter().collect::<Vec<_>>(), vec![0, 3, 10, 4, 2]);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 2, 3, 4, 10]);
+    assert_eq!(view.rows.borrow()[1..4], [4, 1, 3]);
+
+    wrapped_rc.insert(1, 3);
+    wrapped_rc.push(1);
+    assert_eq!(wrapped_rc.iter().collect::<Vec<_>>(), vec![0, 3, 3, 10, 4, 2, 1]);
+    // Equal rows stay in the order of the wrapped model
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 3, 4, 10]);
+    assert_eq!(
+        (0..7).map(|r| sorted.unsorted_row(r)).collect::<Vec<_>>(),
+        vec![0, 6, 5, 1, 2, 4, 3]
+    );
+    assert_eq!(view.rows.borrow().len(), 7);
+
+    wrapped_rc.remove(2);
+    wrapped_rc.remove(0);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 10]);
+    assert_eq!((0..5).map(|r| sorted.unsorted_row(r)).collect::<Vec<_>>(), vec![4, 3, 0, 2, 1]);
+    assert_eq!(view.rows.borrow().len(), 5);
+    assert_eq!(view.reset.get(), 0);
+}
+
+#[test]
+fn test_sort_model_set_sort_function() {
+    let wrapped_rc = Rc::new(VecModel::from(vec![2, 3, 1]));
+    let sorted = SortModel::new(
+        wrapped_rc.clone(),
+        Box::new(|a: &i32, b: &i32| a.cmp(b)) as Box<dyn Fn(&i32, &i32) -> core::cmp::Ordering>,
+    );
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    #[derive(Default)]
+    struct ResetCounter(Cell<usize>);
+    impl ModelChangeListener for ResetCounter {
+        fn row_changed(&self, _row: usize) {}
+        fn row_added(&self, _index: usize, _count: usize) {}
+        fn row_removed(&self, _index: usize, _count: usize) {}
+        fn reset(&self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let view = Box::pin(ModelChangeListenerContainer::<ResetCounter>::default());
+    sorted.model_tracker().attach_peer(view.as_ref().model_peer());
+
+    sorted.set_sort_function(Box::new(|a: &i32, b: &i32| b.cmp(a)));
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    assert_eq!(view.0.get(), 1);
+
+    wrapped_rc.push(4);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+}
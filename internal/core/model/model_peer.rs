@@ -57,6 +57,25 @@ pub fn row_changed(&self, row: usize) {
             inner.as_ref().project_ref().peers.for_each(|p| unsafe { &**p }.row_changed(row))
         }
     }
+    /// Notify the peers that all the rows in `range` were changed
+    pub fn row_changed_range(&self, range: core::ops::Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        if let Some(inner) = self.inner.get() {
+            let tracked_rows = inner.tracked_rows.borrow();
+            let first_tracked = tracked_rows.partition_point(|row| *row < range.start);
+            if tracked_rows.get(first_tracked).map_or(false, |row| *row < range.end) {
+                inner.model_row_data_dirty_property.mark_dirty();
+            }
+            drop(tracked_rows);
+            inner
+                .as_ref()
+                .project_ref()
+                .peers
+                .for_each(|p| unsafe { &**p }.row_changed_range(range.clone()))
+        }
+    }
     /// Notify the peers that rows were added
     pub fn row_added(&self, index: usize, count: usize) {
         if let Some(inner) = self.inner.get() {
@@ -82,6 +101,10 @@ pub fn row_removed(&self, index: usize, count: usize) {
 
     /// Notify the peer that the model has been changed in some way and
     /// everything needs to be reloaded
+    ///
+    /// Call this after large structural changes instead of a series of [`Self::row_added`] and
+    /// [`Self::row_removed`]: a repeater then drops all its components and instantiates them
+    /// again for all the rows the next time it is updated.
     pub fn reset(&self) {
         if let Some(inner) = self.inner.get() {
             inner.model_row_count_dirty_property.mark_dirty();
@@ -120,6 +143,12 @@ fn track_row_data_changes(&self, row: usize) {
 
 pub trait ModelChangeListener {
     fn row_changed(&self, row: usize);
+    /// Called instead of [`Self::row_changed`] when all the rows in `range` changed at once.
+    fn row_changed_range(&self, range: core::ops::Range<usize>) {
+        for row in range {
+            self.row_changed(row)
+        }
+    }
     fn row_added(&self, index: usize, count: usize);
     fn row_removed(&self, index: usize, count: usize);
     fn reset(&self);
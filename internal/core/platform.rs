@@ -10,19 +10,28 @@
 use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::unsafe_single_threaded::{thread_local, OnceCell};
 #[cfg(feature = "std")]
 use once_cell::sync::OnceCell;
 
-pub use crate::items::{InputType, MouseCursor};
+use crate::api::{euclid, PhysicalPx};
+pub use crate::input::{
+    set_shortcut_map, shortcut_map, KeyCombination, KeyboardModifiers, ShortcutMap,
+    StandardShortcut, TextShortcut,
+};
+pub use crate::items::{ColorScheme, InputType, MouseCursor, TextCursorDirection};
 pub use crate::lengths::{PhysicalLength, PhysicalPoint};
 pub use crate::renderer::Renderer;
 #[cfg(feature = "swrenderer")]
 pub use crate::swrenderer;
 pub use crate::window::PlatformWindow;
 
+#[cfg(all(feature = "std", feature = "swrenderer"))]
+pub mod headless;
+
 #[derive(Copy, Clone)]
 /// Behavior describing how the event loop should terminate.
 pub enum EventLoopQuitBehavior {
@@ -32,6 +41,19 @@ pub enum EventLoopQuitBehavior {
     QuitOnlyExplicitly,
 }
 
+/// A screen connected to the system, as returned by [`PlatformAbstraction::screens`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScreenInfo {
+    /// The name of the screen, if the platform provides one.
+    pub name: Option<String>,
+    /// The position of the top left corner of the screen in the virtual desktop, in physical pixels.
+    pub position: euclid::Point2D<i32, PhysicalPx>,
+    /// The size of the screen in physical pixels.
+    pub size: euclid::Size2D<u32, PhysicalPx>,
+    /// The ratio between the physical and the logical pixels of the screen.
+    pub scale_factor: f32,
+}
+
 /// Interface implemented by back-ends
 pub trait PlatformAbstraction {
     /// Instantiate a window for a component.
@@ -75,6 +97,57 @@ fn set_clipboard_text(&self, _text: &str) {}
     fn clipboard_text(&self) -> Option<String> {
         None
     }
+
+    /// Sends the given text into the primary selection of X11 and Wayland, which holds the last
+    /// selected text and is pasted with the middle mouse button.
+    ///
+    /// The default implementation does nothing, for the platforms without a primary selection.
+    fn set_primary_selection_text(&self, _text: &str) {}
+    /// Returns a copy of the text stored in the primary selection, if any.
+    fn primary_selection_text(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the screens connected to the system.
+    ///
+    /// The default implementation returns an empty list, for the backends that don't know about the
+    /// screens, such as headless ones.
+    fn screens(&self) -> Vec<ScreenInfo> {
+        Vec::new()
+    }
+
+    /// Returns whether the system uses light or dark colors.
+    ///
+    /// Backends that get notified when the user changes the color scheme should read it from a
+    /// [`Property`](crate::Property), so that the bindings that depend on it are re-evaluated.
+    ///
+    /// The default implementation returns [`ColorScheme::Unknown`].
+    fn color_scheme(&self) -> ColorScheme {
+        ColorScheme::Unknown
+    }
+
+    /// Returns the maximum delay between the presses of a double click, as configured in the
+    /// system settings.
+    ///
+    /// The default implementation returns 500 milliseconds.
+    fn double_click_interval(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(500)
+    }
+
+    /// Returns how long the left button or the finger must be held down without moving to make a
+    /// long press, see [`MouseEvent::LongPress`](crate::input::MouseEvent::LongPress).
+    ///
+    /// The default implementation returns 500 milliseconds.
+    fn long_press_duration(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(500)
+    }
+
+    /// Sends the given image into the system clipboard
+    fn set_clipboard_image(&self, _image: &crate::graphics::Image) {}
+    /// Returns a copy of the image stored in the system clipboard, if any.
+    fn clipboard_image(&self) -> Option<crate::graphics::Image> {
+        None
+    }
 }
 
 /// Trait that is returned by the [`PlatformAbstraction::new_event_loop_proxy`]
@@ -138,10 +211,45 @@ pub fn set_platform_abstraction(
 /// This function should be called before rendering or processing input event.
 /// It should basically be called on every iteration of the event loop.
 pub fn update_timers_and_animations() {
-    crate::timers::TimerList::maybe_activate_timers();
+    crate::timers::TimerList::maybe_activate_timers(crate::animations::Instant::now());
     crate::animations::update_animations();
 }
 
+/// Fire timer events and update animations, using an explicitly supplied time.
+///
+/// This is the equivalent of [`update_timers_and_animations()`] for hosts that drive Slint
+/// from their own loop and have their own notion of time, such as a game engine.
+///
+/// `now` is the time elapsed since the start of the program. It is interpreted in the same
+/// time base as [`PlatformAbstraction::duration_since_start()`]: with the default implementation
+/// of that function, zero corresponds to the first time Slint queried the clock.
+/// Hosts that supply their own time should also implement `duration_since_start()` so that it
+/// returns values consistent with `now`, because timers started from callbacks compute their
+/// expiry relative to it.
+///
+/// `now` is expected to be monotonic: passing a time earlier than a previous call does not
+/// rewind any animation or timer.
+pub fn update_timers_and_animations_with_time(now: core::time::Duration) {
+    let now_instant = crate::animations::Instant(now.as_millis() as u64);
+    let current_tick = crate::animations::current_tick();
+    if now_instant < current_tick {
+        return;
+    }
+    crate::timers::TimerList::maybe_activate_timers(now_instant);
+    crate::animations::update_animations_with_time(now);
+}
+
+/// Stop all the running timers, for example on shutdown or when tearing down a test, so that
+/// no timer callback is invoked anymore.
+///
+/// If `flush` is true, the timers marked with [`Timer::set_run_on_shutdown()`](crate::timers::Timer::set_run_on_shutdown)
+/// are fired one last time before being stopped.
+///
+/// When called from a timer callback, the timers are stopped once that callback returns.
+pub fn cancel_all_timers(flush: bool) {
+    crate::timers::TimerList::cancel_all(flush);
+}
+
 /// Return the duration before the next timer should be activated. This is basically the
 /// maximum time before calling [`upate_timers_and_animation()`].
 ///
@@ -0,0 +1,213 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+A platform abstraction that doesn't need a windowing system nor a GPU, to run Slint in unit tests and
+on CI machines.
+
+The windows are never shown on a screen: their size is set with [`Window::set_size()`](crate::api::Window::set_size),
+input events are sent with [`Window::dispatch_pointer_event()`](crate::api::Window::dispatch_pointer_event), and
+[`Window::take_snapshot()`](crate::api::Window::take_snapshot) renders them with the software renderer.
+As with any use of the software renderer, the fonts must be embedded in the application.
+The clipboard is kept in memory, so that copy and paste in a `TextInput` work.
+
+Instead of running the event loop, tests call [`process_events()`] to run the callbacks queued with
+[`invoke_from_event_loop()`](crate::api::invoke_from_event_loop) and to fire the timers and animations
+that are due.
+
+```rust,no_run
+i_slint_core::platform::headless::init().unwrap();
+// create the component, set the size of its window, dispatch events...
+i_slint_core::platform::headless::process_events();
+```
+*/
+
+use super::{EventLoopProxy, EventLoopQuitBehavior, PlatformAbstraction};
+use crate::api::Window;
+use crate::graphics::{Image, Rgb8Pixel, Rgba8Pixel, SharedPixelBuffer};
+use crate::lengths::PhysicalLength;
+use crate::renderer::Renderer;
+use crate::swrenderer::{DirtyTracking, SoftwareRenderer};
+use crate::window::{PlatformWindow, WindowHandleAccess};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use std::sync::Mutex;
+
+/// How long [`HeadlessPlatform::run_event_loop`] sleeps at most between two iterations.
+const MAX_IDLE_DURATION: core::time::Duration = core::time::Duration::from_millis(16);
+
+#[derive(Default)]
+struct EventQueue {
+    events: Vec<Box<dyn FnOnce() + Send>>,
+    quit_requested: bool,
+}
+
+/// The events are sent from any thread, so they can't be stored in the platform abstraction, which
+/// lives in the thread of the event loop.
+static EVENT_QUEUE: once_cell::sync::Lazy<Mutex<EventQueue>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+struct HeadlessEventLoopProxy;
+
+impl EventLoopProxy for HeadlessEventLoopProxy {
+    fn quit_event_loop(&self) {
+        EVENT_QUEUE.lock().unwrap().quit_requested = true;
+    }
+
+    fn invoke_from_event_loop(&self, event: Box<dyn FnOnce() + Send>) {
+        EVENT_QUEUE.lock().unwrap().events.push(event);
+    }
+}
+
+/// The platform abstraction installed by [`init()`]
+#[derive(Default)]
+pub struct HeadlessPlatform {
+    clipboard: RefCell<Option<String>>,
+    primary_selection: RefCell<Option<String>>,
+    clipboard_image: RefCell<Option<Image>>,
+}
+
+impl PlatformAbstraction for HeadlessPlatform {
+    fn create_window(&self) -> Rc<dyn PlatformWindow> {
+        Rc::new_cyclic(|self_weak| HeadlessWindow {
+            window: Window::new(self_weak.clone() as _),
+            renderer: SoftwareRenderer::new(DirtyTracking::None),
+        })
+    }
+
+    /// Calls [`process_events()`] until [`quit_event_loop()`](crate::api::quit_event_loop) is called.
+    /// No window is ever closed by the user, so the behavior is ignored.
+    fn run_event_loop(&self, _behavior: EventLoopQuitBehavior) {
+        while process_events() {
+            let idle_duration = super::duration_until_next_timer_update()
+                .map_or(MAX_IDLE_DURATION, |duration| duration.min(MAX_IDLE_DURATION));
+            std::thread::sleep(idle_duration);
+        }
+    }
+
+    fn new_event_loop_proxy(&self) -> Option<Box<dyn EventLoopProxy>> {
+        Some(Box::new(HeadlessEventLoopProxy))
+    }
+
+    fn set_clipboard_text(&self, text: &str) {
+        *self.clipboard.borrow_mut() = Some(text.into());
+    }
+
+    fn clipboard_text(&self) -> Option<String> {
+        self.clipboard.borrow().clone()
+    }
+
+    fn set_primary_selection_text(&self, text: &str) {
+        *self.primary_selection.borrow_mut() = Some(text.into());
+    }
+
+    fn primary_selection_text(&self) -> Option<String> {
+        self.primary_selection.borrow().clone()
+    }
+
+    fn set_clipboard_image(&self, image: &Image) {
+        *self.clipboard_image.borrow_mut() = Some(image.clone());
+    }
+
+    fn clipboard_image(&self) -> Option<Image> {
+        self.clipboard_image.borrow().clone()
+    }
+}
+
+/// A window of the [`HeadlessPlatform`], rendered with the software renderer when a snapshot is taken.
+pub struct HeadlessWindow {
+    window: Window,
+    renderer: SoftwareRenderer,
+}
+
+impl PlatformWindow for HeadlessWindow {
+    fn take_snapshot(&self) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+        let size = self.window.size();
+        let (width, height) = (size.width as usize, size.height as usize);
+        let mut buffer = alloc::vec![Rgb8Pixel::default(); width * height];
+        self.window.window_handle().draw_contents(|_component| {
+            self.renderer.render(
+                &self.window,
+                buffer.as_mut_slice(),
+                PhysicalLength::new(width as _),
+            );
+        });
+        let mut snapshot = SharedPixelBuffer::new(size.width, size.height);
+        for (target, pixel) in snapshot.make_mut_slice().iter_mut().zip(buffer) {
+            *target = pixel.alpha(255);
+        }
+        Some(snapshot)
+    }
+
+    fn renderer(&self) -> &dyn Renderer {
+        &self.renderer
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+
+    fn window(&self) -> &Window {
+        &self.window
+    }
+}
+
+/// Installs the [`HeadlessPlatform`] as platform abstraction.
+///
+/// Must be called before any call that would otherwise initialize the platform abstraction, such as
+/// the creation of a component. Returns `Err` if the platform abstraction was already set.
+pub fn init() -> Result<(), ()> {
+    super::set_platform_abstraction(Box::new(HeadlessPlatform::default()))
+}
+
+/// Runs the callbacks queued with [`invoke_from_event_loop()`](crate::api::invoke_from_event_loop), then
+/// fires the timers and updates the animations, like one iteration of an event loop.
+///
+/// Returns false if [`quit_event_loop()`](crate::api::quit_event_loop) was called since the last call.
+pub fn process_events() -> bool {
+    let (events, quit_requested) = {
+        let mut queue = EVENT_QUEUE.lock().unwrap();
+        (core::mem::take(&mut queue.events), core::mem::take(&mut queue.quit_requested))
+    };
+    for event in events {
+        event();
+    }
+    super::update_timers_and_animations();
+    !quit_requested
+}
+
+#[test]
+fn headless_event_loop_and_clipboard() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    init().unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_from_thread = calls.clone();
+    std::thread::spawn(move || {
+        crate::api::invoke_from_event_loop(move || {
+            calls_from_thread.fetch_add(1, Ordering::SeqCst);
+        })
+    })
+    .join()
+    .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(process_events());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    crate::api::quit_event_loop();
+    assert!(!process_events());
+    assert!(process_events());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    super::PLAFTORM_ABSTRACTION_INSTANCE.with(|platform| {
+        let platform = platform.get().unwrap();
+        platform.set_clipboard_text("hello");
+        assert_eq!(platform.clipboard_text().as_deref(), Some("hello"));
+        assert_eq!(platform.primary_selection_text(), None);
+    });
+}
@@ -463,10 +463,19 @@ unsafe fn set_lock_flag(&self, set: bool) {
         self.handle.set(if set { self.handle.get() | 0b1 } else { self.handle.get() & !0b1 })
     }
 
+    /// Panics if the property is already being accessed, which happens with binding loops.
+    fn assert_not_locked(&self) {
+        if self.lock_flag() {
+            #[cfg(feature = "binding-loop-recovery")]
+            binding_loop::loop_detected();
+            panic!("Recursion detected");
+        }
+    }
+
     /// Access the value.
     /// Panics if the function try to recursively access the value
     fn access<R>(&self, f: impl FnOnce(Option<Pin<&mut BindingHolder>>) -> R) -> R {
-        assert!(!self.lock_flag(), "Recursion detected");
+        self.assert_not_locked();
         unsafe {
             self.set_lock_flag(true);
             scopeguard::defer! { self.set_lock_flag(false); }
@@ -481,7 +490,7 @@ fn access<R>(&self, f: impl FnOnce(Option<Pin<&mut BindingHolder>>) -> R) -> R {
     }
 
     fn remove_binding(&self) {
-        assert!(!self.lock_flag(), "Recursion detected");
+        self.assert_not_locked();
         let val = self.handle.get();
         if val & 0b10 == 0b10 {
             unsafe {
@@ -555,7 +564,7 @@ fn set_binding_impl(&self, binding: *mut BindingHolder) {
     }
 
     fn dependencies(&self) -> *mut DependencyListHead {
-        assert!(!self.lock_flag(), "Recursion detected");
+        self.assert_not_locked();
         if (self.handle.get() & 0b10) != 0 {
             self.access(|binding| binding.unwrap().dependencies.as_ptr() as *mut DependencyListHead)
         } else {
@@ -571,10 +580,20 @@ unsafe fn update<T>(&self, value: *mut T) {
                 if binding.dirty.get() {
                     // clear all the nodes so that we can start from scratch
                     binding.dep_nodes.set(Default::default());
+                    #[cfg(feature = "binding-timing")]
+                    let start = binding_timing::start();
                     let r = (binding.vtable.evaluate)(
                         binding.as_mut().get_unchecked_mut() as *mut BindingHolder,
                         value as *mut (),
                     );
+                    #[cfg(feature = "binding-timing")]
+                    {
+                        #[cfg(slint_debug_property)]
+                        let name = binding.debug_name.as_str();
+                        #[cfg(not(slint_debug_property))]
+                        let name = "";
+                        binding_timing::finish(start, name);
+                    }
                     binding.dirty.set(false);
                     if r == BindingResult::RemoveBinding {
                         return true;
@@ -625,6 +644,9 @@ fn mark_dirty(&self, #[cfg(slint_debug_property)] debug_name: &str) {
                 "Constant property being changed {}",
                 debug_name
             );
+            #[cfg(feature = "layout-invalidation")]
+            let _change =
+                layout_invalidation::enter_change(self as *const Self as usize, debug_name);
             mark_dependencies_dirty(dependencies)
         };
     }
@@ -755,7 +777,7 @@ pub fn new_named(value: T, _name: &'static str) -> Self {
     /// of another Property, a dependency will be registered.
     ///
     /// Panics if this property is get while evaluating its own binding or
-    /// cloning the value.
+    /// cloning the value. See `try_get` with the `binding-loop-recovery` feature to recover from that.
     pub fn get(self: Pin<&Self>) -> T {
         unsafe { self.handle.update(self.value.get()) };
         let handle = unsafe { Pin::new_unchecked(&self.handle) };
@@ -766,6 +788,14 @@ pub fn get(self: Pin<&Self>) -> T {
         self.get_internal()
     }
 
+    /// Same as [`Self::get`], but returns an error instead of panicking if the binding of this property
+    /// depends on the value of the property itself. The panic message is still printed by the panic
+    /// hook. The bindings involved in the loop stay dirty.
+    #[cfg(feature = "binding-loop-recovery")]
+    pub fn try_get(self: Pin<&Self>) -> Result<T, binding_loop::BindingLoopError> {
+        binding_loop::catch_binding_loop(|| self.get())
+    }
+
     /// Same as get() but without registering a dependency
     ///
     /// This allow to optimize bindings that know that they might not need to
@@ -803,7 +833,8 @@ fn get_internal(&self) -> T {
     /// Change the value of this property
     ///
     /// If other properties have binding depending of this property, these properties will
-    /// be marked as dirty.
+    /// be marked as dirty. Setting a value equal to the current one doesn't mark them dirty,
+    /// so they are not evaluated again. Use [`Self::mark_dirty`] to force that.
     // FIXME  pub fn set(self: Pin<&Self>, t: T) {
     pub fn set(&self, t: T)
     where
@@ -842,6 +873,9 @@ pub fn set(&self, t: T)
     /// If other properties have bindings depending of this property, these properties will
     /// be marked as dirty.
     ///
+    /// The binding is not evaluated by this function: it is evaluated the first time the
+    /// value of the property is read.
+    ///
     /// Closures of type `Fn()->T` implements Binding<T> and can be used as a binding
     ///
     /// ## Example
@@ -879,6 +913,89 @@ pub fn set_binding(&self, binding: impl Binding<T> + 'static) {
         );
     }
 
+    /// Set a binding to this property that updates the current value in place.
+    ///
+    /// The `binding` is called with a reference to the current value of the property, which it
+    /// can modify. This is useful for bindings that depend on their previous value, such as a
+    /// smoothing towards a target.
+    ///
+    /// Like with [`Self::set_binding`], the properties accessed from `binding` are tracked, and the
+    /// binding is lazily re-evaluated when the property is read after one of them changed. Reading
+    /// the previous value does not register a dependency. If a dependency changes several times
+    /// before the property is read, the binding is only evaluated once with the latest values.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use i_slint_core::Property;
+    /// let target = Rc::pin(Property::new(100));
+    /// let smoothed = Rc::pin(Property::new(0));
+    /// smoothed.as_ref().set_binding_with_old({
+    ///     let target = target.clone();
+    ///     move |value: &mut i32| *value += (target.as_ref().get() - *value) / 2
+    /// });
+    /// assert_eq!(smoothed.as_ref().get(), 50);
+    /// target.set(150);
+    /// assert_eq!(smoothed.as_ref().get(), 100);
+    /// ```
+    pub fn set_binding_with_old(&self, binding: impl Fn(&mut T) + 'static) {
+        // Safety: This will make a binding callable for the type T
+        unsafe {
+            self.handle.set_binding(
+                move |val: *mut ()| {
+                    binding(&mut *(val as *mut T));
+                    BindingResult::KeepBinding
+                },
+                #[cfg(slint_debug_property)]
+                self.debug_name.borrow().as_str(),
+            )
+        }
+        self.handle.mark_dirty(
+            #[cfg(slint_debug_property)]
+            self.debug_name.borrow().as_str(),
+        );
+    }
+
+    /// Set a binding to this property that computes the value from `owner`, which is usually the
+    /// structure that contains this property.
+    ///
+    /// The owner keeps its properties, and therefore their bindings, alive. So a binding set with
+    /// [`Self::set_binding`] that captures a strong reference to its owner creates a reference cycle
+    /// and the owner is never dropped. This function only keeps a weak reference to the owner and
+    /// upgrades it to call `binding`. If the owner is already dropped, the value of the
+    /// property is not changed.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use i_slint_core::Property;
+    /// let width = Rc::pin(Property::new(4));
+    /// let double_width = Property::<i32>::default();
+    /// double_width.set_binding_with_owner(&width, |width| width.as_ref().get() * 2);
+    /// width.set(8);
+    /// assert_eq!(unsafe { std::pin::Pin::new_unchecked(&double_width) }.get(), 16);
+    /// ```
+    pub fn set_binding_with_owner<Owner: 'static>(
+        &self,
+        owner: &Pin<Rc<Owner>>,
+        binding: impl Fn(Pin<Rc<Owner>>) -> T + 'static,
+    ) {
+        struct OwnerBinding<Owner, F> {
+            owner: pin_weak::rc::PinWeak<Owner>,
+            binding: F,
+        }
+        impl<T: Clone, Owner, F: Fn(Pin<Rc<Owner>>) -> T> Binding<T> for OwnerBinding<Owner, F> {
+            fn evaluate(&self, old_value: &T) -> T {
+                match self.owner.upgrade() {
+                    Some(owner) => (self.binding)(owner),
+                    None => old_value.clone(),
+                }
+            }
+        }
+        self.set_binding(OwnerBinding {
+            owner: pin_weak::rc::PinWeak::downgrade(owner.clone()),
+            binding,
+        })
+    }
+
     /// Any of the properties accessed during the last evaluation of the closure called
     /// from the last call to evaluate is potentially dirty.
     pub fn is_dirty(&self) -> bool {
@@ -900,6 +1017,128 @@ pub fn set_constant(&self) {
     }
 }
 
+#[test]
+fn properties_set_equal_value_test() {
+    use std::rc::Rc;
+    let source = Rc::pin(Property::new(10));
+    let evaluations = Rc::new(Cell::new(0));
+    let dependent = Rc::pin(Property::new(0));
+    dependent.as_ref().set_binding({
+        let (source, evaluations) = (source.clone(), evaluations.clone());
+        move || {
+            evaluations.set(evaluations.get() + 1);
+            source.as_ref().get() * 2
+        }
+    });
+    assert_eq!(dependent.as_ref().get(), 20);
+    assert_eq!(evaluations.get(), 1);
+
+    // Setting an equal value doesn't mark the dependent binding dirty
+    source.set(10);
+    assert!(!dependent.is_dirty());
+    assert_eq!(dependent.as_ref().get(), 20);
+    assert_eq!(evaluations.get(), 1);
+
+    source.set(11);
+    assert!(dependent.is_dirty());
+    assert_eq!(dependent.as_ref().get(), 22);
+    assert_eq!(evaluations.get(), 2);
+
+    // mark_dirty always re-evaluates
+    source.mark_dirty();
+    assert_eq!(dependent.as_ref().get(), 22);
+    assert_eq!(evaluations.get(), 3);
+}
+
+#[test]
+fn properties_binding_with_old_test() {
+    use std::rc::Rc;
+    let sample = Rc::pin(Property::new(10));
+    // (sum, count) of all the samples
+    let accumulator = Rc::pin(Property::new((0, 0)));
+    accumulator.as_ref().set_binding_with_old({
+        let sample = sample.clone();
+        move |(sum, count): &mut (i32, i32)| {
+            *sum += sample.as_ref().get();
+            *count += 1;
+        }
+    });
+    let average = Rc::pin(Property::new(0));
+    average.as_ref().set_binding({
+        let accumulator = accumulator.clone();
+        move || {
+            let (sum, count) = accumulator.as_ref().get();
+            sum / count
+        }
+    });
+
+    assert_eq!(average.as_ref().get(), 10);
+    sample.set(20);
+    assert_eq!(average.as_ref().get(), 15);
+    // Not re-evaluated if the dependencies did not change
+    assert_eq!(average.as_ref().get(), 15);
+    assert_eq!(accumulator.as_ref().get(), (30, 2));
+    sample.set(60);
+    assert_eq!(average.as_ref().get(), 30);
+    assert_eq!(accumulator.as_ref().get(), (90, 3));
+}
+
+#[test]
+fn properties_binding_lazy_evaluation_test() {
+    use std::rc::Rc;
+    let evaluations = Rc::new(Cell::new(0));
+    let source = Rc::pin(Property::new(1));
+    let prop = Rc::pin(Property::new(0));
+    // A binding and a tracker that depend on `prop`
+    let dependent = Rc::pin(Property::new(0));
+    dependent.as_ref().set_binding({
+        let prop = prop.clone();
+        move || prop.as_ref().get() * 10
+    });
+    assert_eq!(dependent.as_ref().get(), 0);
+    let tracker = Box::pin(PropertyTracker::default());
+    tracker.as_ref().evaluate(|| prop.as_ref().get());
+
+    prop.as_ref().set_binding({
+        let (evaluations, source) = (evaluations.clone(), source.clone());
+        move || {
+            evaluations.set(evaluations.get() + 1);
+            source.as_ref().get() + 1
+        }
+    });
+    // Setting the binding only marks the dependents dirty, without evaluating anything
+    assert_eq!(evaluations.get(), 0);
+    assert!(tracker.as_ref().is_dirty());
+    assert!(dependent.is_dirty());
+
+    assert_eq!(dependent.as_ref().get(), 20);
+    assert_eq!(evaluations.get(), 1);
+    assert_eq!(prop.as_ref().get(), 2);
+    assert_eq!(evaluations.get(), 1);
+
+    source.set(5);
+    source.set(6);
+    assert_eq!(evaluations.get(), 1);
+    assert_eq!(prop.as_ref().get(), 7);
+    assert_eq!(evaluations.get(), 2);
+
+    // The same with properties linked by a two way binding
+    let p1 = Rc::pin(Property::new(0));
+    let p2 = Rc::pin(Property::new(0));
+    Property::link_two_way(p1.as_ref(), p2.as_ref());
+    let linked_evaluations = Rc::new(Cell::new(0));
+    p2.as_ref().set_binding({
+        let linked_evaluations = linked_evaluations.clone();
+        move || {
+            linked_evaluations.set(linked_evaluations.get() + 1);
+            42
+        }
+    });
+    assert_eq!(linked_evaluations.get(), 0);
+    assert_eq!(p1.as_ref().get(), 42);
+    assert_eq!(linked_evaluations.get(), 1);
+}
+
 #[test]
 fn properties_simple_test() {
     use pin_weak::rc::PinWeak;
@@ -937,10 +1176,43 @@ struct Component {
     assert_eq!(g(&compo.area), 8 * 8 * 2);
 }
 
+#[test]
+fn properties_binding_with_owner_test() {
+    use pin_weak::rc::PinWeak;
+    use std::rc::Rc;
+
+    #[derive(Default)]
+    struct Component {
+        width: Property<i32>,
+        height: Property<i32>,
+        area: Property<i32>,
+    }
+
+    fn g(prop: &Property<i32>) -> i32 {
+        unsafe { Pin::new_unchecked(prop).get() }
+    }
+
+    let compo = Rc::pin(Component::default());
+    compo.width.set_binding_with_owner(&compo, |compo| g(&compo.height) * 2);
+    compo.area.set_binding_with_owner(&compo, |compo| g(&compo.width) * g(&compo.height));
+    compo.height.set(8);
+    assert_eq!(g(&compo.width), 8 * 2);
+    assert_eq!(g(&compo.area), 8 * 8 * 2);
+
+    // The bindings refer to the component itself but don't keep it alive
+    let weak = PinWeak::downgrade(compo.clone());
+    drop(compo);
+    assert!(weak.upgrade().is_none());
+}
+
 impl<T: PartialEq + Clone + 'static> Property<T> {
     /// Link two property such that any change to one property is affecting the other property as if they
     /// where, in fact, a single property.
     /// The value or binding of prop2 is kept.
+    ///
+    /// Both properties forward their value, their `set` and their `set_binding` to a shared
+    /// property, so setting either side marks the dependents of both dirty once, without the
+    /// two bindings depending on each other.
     pub fn link_two_way(prop1: Pin<&Self>, prop2: Pin<&Self>) {
         struct TwoWayBinding<T> {
             common_property: Pin<Rc<Property<T>>>,
@@ -1038,6 +1310,97 @@ unsafe fn intercept_set_binding(
             );
         }
     }
+
+    /// Register a `callback` that is called with the new value when the value of this property
+    /// changes.
+    ///
+    /// The callback is called lazily: when the property is read after its value, or the value of
+    /// one of the dependencies of its binding, was changed, and only if the value read is different
+    /// from the one read before. The properties read from the callback are not registered as
+    /// dependencies of anything. The callback must not read this property, it receives the value.
+    ///
+    /// Like with [`Self::link_two_way`], the value and the binding of the property are moved to an
+    /// inner property, so `set` and `set_binding` keep working and the callback stays registered.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::Cell;
+    /// use i_slint_core::Property;
+    /// let prop = Rc::pin(Property::new(1));
+    /// let last_change = Rc::new(Cell::new(0));
+    /// prop.as_ref().on_changed({
+    ///     let last_change = last_change.clone();
+    ///     move |value| last_change.set(*value)
+    /// });
+    /// prop.set(42);
+    /// // The callback is not called until the property is read
+    /// assert_eq!(last_change.get(), 0);
+    /// assert_eq!(prop.as_ref().get(), 42);
+    /// assert_eq!(last_change.get(), 42);
+    /// ```
+    pub fn on_changed(self: Pin<&Self>, callback: impl Fn(&T) + 'static) {
+        struct ChangeCallbackBinding<T, F> {
+            inner_property: Pin<Rc<Property<T>>>,
+            last_value: RefCell<T>,
+            callback: F,
+        }
+        unsafe impl<T: PartialEq + Clone + 'static, F: Fn(&T)> BindingCallable
+            for ChangeCallbackBinding<T, F>
+        {
+            unsafe fn evaluate(self: Pin<&Self>, value: *mut ()) -> BindingResult {
+                let new_value = self.inner_property.as_ref().get();
+                *(value as *mut T) = new_value.clone();
+                let old_value = self.last_value.replace(new_value.clone());
+                if old_value != new_value {
+                    evaluate_no_tracking(|| (self.callback)(&new_value));
+                }
+                BindingResult::KeepBinding
+            }
+
+            unsafe fn intercept_set(self: Pin<&Self>, value: *const ()) -> bool {
+                self.inner_property.as_ref().set((*(value as *const T)).clone());
+                true
+            }
+
+            unsafe fn intercept_set_binding(
+                self: Pin<&Self>,
+                new_binding: *mut BindingHolder,
+            ) -> bool {
+                self.inner_property.handle.set_binding_impl(new_binding);
+                true
+            }
+        }
+
+        #[cfg(slint_debug_property)]
+        let debug_name = format!("<on_changed {}>", self.debug_name.borrow());
+
+        let value = self.get_untracked();
+
+        let handle_val = self.handle.handle.get();
+        let handle = if handle_val & 0b10 == 0b10 {
+            // If the property has a binding, move it to the inner property
+            self.handle.handle.set(0);
+            PropertyHandle { handle: Cell::new(handle_val) }
+        } else {
+            PropertyHandle::default()
+        };
+
+        let inner_property = Rc::pin(Property {
+            handle,
+            value: UnsafeCell::new(value.clone()),
+            pinned: PhantomPinned,
+            #[cfg(slint_debug_property)]
+            debug_name: debug_name.clone().into(),
+        });
+        // Safety: ChangeCallbackBinding's T is the same as the type of the property
+        unsafe {
+            self.handle.set_binding(
+                ChangeCallbackBinding { inner_property, last_value: RefCell::new(value), callback },
+                #[cfg(slint_debug_property)]
+                debug_name.as_str(),
+            );
+        }
+    }
 }
 
 #[test]
@@ -1091,9 +1454,100 @@ fn property_two_ways_test_binding() {
     assert_eq!(depends.as_ref().get(), 55 + 9 + 8);
 }
 
+#[test]
+fn property_two_ways_no_dirty_loop() {
+    let p1 = Rc::pin(Property::new(1));
+    let p2 = Rc::pin(Property::new(2));
+    Property::link_two_way(p1.as_ref(), p2.as_ref());
+
+    // A binding that reads both sides is evaluated once per change, whichever side is set
+    let evaluations = Rc::new(Cell::new(0));
+    let sum = Box::pin(Property::new(0));
+    sum.as_ref().set_binding({
+        let (p1, p2, evaluations) = (p1.clone(), p2.clone(), evaluations.clone());
+        move || {
+            evaluations.set(evaluations.get() + 1);
+            p1.as_ref().get() + p2.as_ref().get()
+        }
+    });
+    assert_eq!(sum.as_ref().get(), 4);
+    assert_eq!(evaluations.get(), 1);
+
+    p1.as_ref().set(10);
+    assert_eq!(p2.as_ref().get(), 10);
+    assert_eq!(sum.as_ref().get(), 20);
+    assert_eq!(evaluations.get(), 2);
+    p2.as_ref().set(7);
+    assert_eq!(p1.as_ref().get(), 7);
+    assert_eq!(sum.as_ref().get(), 14);
+    assert_eq!(evaluations.get(), 3);
+    // Reading again doesn't re-evaluate anything
+    assert_eq!(sum.as_ref().get(), 14);
+    assert_eq!(evaluations.get(), 3);
+}
+
+#[test]
+fn property_on_changed_test() {
+    let prop = Rc::pin(Property::new(1));
+    let source = Rc::pin(Property::new(10));
+    let unrelated = Rc::pin(Property::new(0));
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    prop.as_ref().on_changed({
+        let (changes, unrelated) = (changes.clone(), unrelated.clone());
+        move |value: &i32| changes.borrow_mut().push(*value + unrelated.as_ref().get())
+    });
+    assert_eq!(prop.as_ref().get(), 1);
+    assert!(changes.borrow().is_empty());
+
+    prop.set(2);
+    assert!(changes.borrow().is_empty());
+    assert_eq!(prop.as_ref().get(), 2);
+    assert_eq!(*changes.borrow(), [2]);
+    // Setting the same value doesn't call the callback
+    prop.set(2);
+    assert_eq!(prop.as_ref().get(), 2);
+    assert_eq!(*changes.borrow(), [2]);
+
+    // The callback stays registered when a binding is set
+    prop.as_ref().set_binding({
+        let source = source.clone();
+        move || source.as_ref().get() * 2
+    });
+    assert_eq!(prop.as_ref().get(), 20);
+    assert_eq!(*changes.borrow(), [2, 20]);
+    source.set(15);
+    assert_eq!(prop.as_ref().get(), 30);
+    assert_eq!(*changes.borrow(), [2, 20, 30]);
+
+    // The properties read by the callback are not dependencies
+    unrelated.set(100);
+    assert_eq!(prop.as_ref().get(), 30);
+    assert_eq!(*changes.borrow(), [2, 20, 30]);
+
+    // Dependent bindings still see the changes
+    let depends = Box::pin(Property::new(0));
+    depends.as_ref().set_binding({
+        let prop = prop.clone();
+        move || prop.as_ref().get() + 1
+    });
+    assert_eq!(depends.as_ref().get(), 31);
+    source.set(20);
+    assert_eq!(depends.as_ref().get(), 41);
+    assert_eq!(*changes.borrow(), [2, 20, 30, 140]);
+}
+
 mod properties_animations;
 pub use properties_animations::*;
 
+#[cfg(feature = "binding-timing")]
+pub mod binding_timing;
+
+#[cfg(feature = "layout-invalidation")]
+pub mod layout_invalidation;
+
+#[cfg(feature = "binding-loop-recovery")]
+pub mod binding_loop;
+
 /// Value of the state property
 ///
 /// A state is just the current state, but also has information about the previous state and the moment it changed
@@ -1454,3 +1908,105 @@ fn test_nested_property_tracker_evaluate_if_dirty() {
 
 #[cfg(feature = "ffi")]
 pub(crate) mod ffi;
+
+#[test]
+#[cfg(feature = "binding-timing")]
+fn test_slow_binding_reported() {
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    binding_timing::set_slow_binding_handler(core::time::Duration::from_millis(10), {
+        let reported = reported.clone();
+        move |_, duration| reported.borrow_mut().push(duration)
+    });
+
+    let fast = Box::pin(Property::new(0));
+    fast.as_ref().set_binding(|| 1);
+    let slow = Box::pin(Property::new(0));
+    slow.as_ref().set_binding(|| {
+        std::thread::sleep(core::time::Duration::from_millis(20));
+        2
+    });
+
+    assert_eq!(fast.as_ref().get(), 1);
+    assert!(reported.borrow().is_empty());
+    assert_eq!(slow.as_ref().get(), 2);
+    assert_eq!(reported.borrow().len(), 1);
+    assert!(reported.borrow()[0] >= core::time::Duration::from_millis(20));
+
+    // Not evaluated again
+    assert_eq!(slow.as_ref().get(), 2);
+    assert_eq!(reported.borrow().len(), 1);
+
+    binding_timing::clear_slow_binding_handler();
+}
+
+#[test]
+#[cfg(feature = "binding-loop-recovery")]
+fn test_binding_loop_error() {
+    let a = Rc::pin(Property::new(1));
+    let b = Rc::pin(Property::new(2));
+    a.as_ref().set_binding({
+        let b = b.clone();
+        move || b.as_ref().get() + 1
+    });
+    b.as_ref().set_binding({
+        let a = a.clone();
+        move || a.as_ref().get() + 1
+    });
+    assert_eq!(a.as_ref().try_get(), Err(binding_loop::BindingLoopError));
+    assert_eq!(b.as_ref().try_get(), Err(binding_loop::BindingLoopError));
+
+    // Breaking the loop makes the properties usable again
+    b.set(10);
+    assert_eq!(a.as_ref().try_get(), Ok(11));
+    assert_eq!(a.as_ref().get(), 11);
+
+    // Other panics are not caught
+    let panicking = Rc::pin(Property::new(0));
+    panicking.as_ref().set_binding(|| -> i32 { panic!("not a loop") });
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| panicking.as_ref().try_get()));
+    assert!(result.is_err());
+}
+
+#[test]
+#[cfg(feature = "layout-invalidation")]
+fn test_layout_invalidation_reported() {
+    let toggled = Rc::pin(Property::new_named(false, "toggled"));
+    let other = Rc::pin(Property::new_named(0, "other"));
+    let width = Box::pin(Property::new(0));
+    width.as_ref().set_binding({
+        let toggled = toggled.clone();
+        let other = other.clone();
+        move || (if toggled.as_ref().get() { 10 } else { 20 }) + other.as_ref().get()
+    });
+    // Stands for the redraw tracker of the window
+    let layout_tracker =
+        Box::pin(PropertyTracker::new_with_dirty_handler(layout_invalidation::record_invalidation));
+    let layout_pass = || layout_tracker.as_ref().evaluate(|| width.as_ref().get());
+
+    layout_pass();
+    layout_invalidation::start_recording();
+    for i in 0..10 {
+        toggled.as_ref().set(i % 2 == 0);
+        layout_pass();
+    }
+    other.as_ref().set(1);
+    // Only the first change before a layout pass causes it
+    toggled.as_ref().set(true);
+    layout_pass();
+    layout_invalidation::stop_recording();
+    toggled.as_ref().set(false);
+    layout_pass();
+
+    fn name<T>(property: &Pin<Rc<Property<T>>>, name: &str) -> String {
+        if cfg!(slint_debug_property) {
+            name.into()
+        } else {
+            format!("{:#x}", &**property as *const Property<T> as usize)
+        }
+    }
+    assert_eq!(
+        layout_invalidation::report(),
+        vec![(name(&toggled, "toggled"), 10), (name(&other, "other"), 1)]
+    );
+}
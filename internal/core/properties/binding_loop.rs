@@ -0,0 +1,51 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Recovery from binding loops, where the binding of a property depends on the value of the property
+itself, directly or through other bindings.
+
+This module is only compiled with the `binding-loop-recovery` feature. A binding loop panics when the
+property is read again while its binding is evaluated. [`Property::try_get`](super::Property::try_get)
+catches that panic and returns a [`BindingLoopError`] instead, so that the application can report it
+and keep running. The bindings involved in the loop stay dirty and are evaluated again the next time
+they are read, after the loop was broken for example by setting one of the properties.
+*/
+
+use core::cell::Cell;
+
+thread_local! {
+    /// Set when a property is read while its binding is being evaluated
+    static LOOP_DETECTED: Cell<bool> = Cell::new(false);
+}
+
+/// The error returned by [`Property::try_get`](super::Property::try_get) when the binding of the
+/// property depends on the value of the property itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BindingLoopError;
+
+impl core::fmt::Display for BindingLoopError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("binding loop detected")
+    }
+}
+
+impl std::error::Error for BindingLoopError {}
+
+/// Records that a property was read while its binding is evaluated, before panicking.
+pub(super) fn loop_detected() {
+    LOOP_DETECTED.with(|d| d.set(true));
+}
+
+/// Calls `f`, and returns an error if it panicked because of a binding loop. Other panics are
+/// propagated.
+pub(super) fn catch_binding_loop<R>(f: impl FnOnce() -> R) -> Result<R, BindingLoopError> {
+    LOOP_DETECTED.with(|d| d.set(false));
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|payload| {
+        if LOOP_DETECTED.with(|d| d.replace(false)) {
+            BindingLoopError
+        } else {
+            std::panic::resume_unwind(payload)
+        }
+    })
+}
@@ -0,0 +1,60 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Instrumentation of the evaluation of the bindings, to find the bindings that are slow to evaluate.
+
+This module is only compiled with the `binding-timing` feature. A handler registered with
+[`set_slow_binding_handler`] is called for every binding that takes longer than a threshold to
+evaluate. The time includes the evaluation of the bindings of the properties it depends on
+that needed to be evaluated too.
+*/
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::time::Duration;
+
+type SlowBindingHandler = Rc<dyn Fn(&str, Duration)>;
+
+thread_local! {
+    static SLOW_BINDING_HANDLER: RefCell<Option<(Duration, SlowBindingHandler)>> = Default::default();
+}
+
+/// Registers a handler called every time the evaluation of a binding takes longer than `threshold`,
+/// with the name of the property and the time it took. The name of the property is only known when
+/// compiled with `--cfg slint_debug_property`, it is empty otherwise.
+///
+/// The handler replaces the one previously registered in this thread.
+pub fn set_slow_binding_handler(threshold: Duration, handler: impl Fn(&str, Duration) + 'static) {
+    SLOW_BINDING_HANDLER.with(|h| *h.borrow_mut() = Some((threshold, Rc::new(handler))));
+}
+
+/// Removes the handler registered with [`set_slow_binding_handler`], so that the evaluation of the
+/// bindings isn't timed anymore.
+pub fn clear_slow_binding_handler() {
+    SLOW_BINDING_HANDLER.with(|h| *h.borrow_mut() = None);
+}
+
+/// Returns the time at which a binding starts to be evaluated, if a handler wants to know about
+/// slow bindings.
+pub(super) fn start() -> Option<instant::Instant> {
+    SLOW_BINDING_HANDLER.with(|h| h.borrow().is_some()).then(instant::Instant::now)
+}
+
+/// Calls the handler if the binding of the property `name`, which started to be evaluated at
+/// `start`, is slow.
+pub(super) fn finish(start: Option<instant::Instant>, name: &str) {
+    let elapsed = match start {
+        Some(start) => start.elapsed(),
+        None => return,
+    };
+    let handler = SLOW_BINDING_HANDLER.with(|h| {
+        h.borrow()
+            .as_ref()
+            .filter(|(threshold, _)| elapsed > *threshold)
+            .map(|(_, handler)| handler.clone())
+    });
+    if let Some(handler) = handler {
+        handler(name, elapsed);
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Instrumentation of the invalidation of the layout and of the rendering, to find the properties that
+cause a window to reflow too often.
+
+This module is only compiled with the `layout-invalidation` feature. After [`start_recording`], every
+time the change of a property invalidates the layout and the rendering of a window, that property is
+recorded. [`report`] lists the recorded properties, the most frequent first.
+
+A window is invalidated at most once per layout pass: the properties that change after that, until
+the window is laid out and rendered again, don't cause more work and are not recorded.
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+thread_local! {
+    static RECORDING: Cell<bool> = Cell::new(false);
+    /// The number of invalidations per property
+    static RECORDED: RefCell<HashMap<String, usize>> = Default::default();
+    /// The property being changed, while recording
+    static CURRENT_CHANGE: RefCell<Option<String>> = Default::default();
+}
+
+/// Starts recording the properties that invalidate the layout of a window in this thread, forgetting
+/// the ones recorded previously.
+pub fn start_recording() {
+    RECORDED.with(|r| r.borrow_mut().clear());
+    RECORDING.with(|r| r.set(true));
+}
+
+/// Stops recording the properties that invalidate the layout. The [`report`] stays available until
+/// the next call to [`start_recording`].
+pub fn stop_recording() {
+    RECORDING.with(|r| r.set(false));
+}
+
+/// Returns the properties that invalidated the layout since the last call to [`start_recording`],
+/// with the number of layout passes they caused, the most frequent first.
+///
+/// The properties are identified by their name when compiled with `--cfg slint_debug_property`,
+/// and by their address otherwise.
+pub fn report() -> Vec<(String, usize)> {
+    let mut report: Vec<_> =
+        RECORDED.with(|r| r.borrow().iter().map(|(name, count)| (name.clone(), *count)).collect());
+    report.sort_by(|(a_name, a_count), (b_name, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+    });
+    report
+}
+
+fn is_recording() -> bool {
+    RECORDING.with(|r| r.get())
+}
+
+/// Marks the property at `address` as being changed until the returned value is dropped, so that
+/// the invalidations that happen in the meantime are attributed to it.
+pub(super) fn enter_change(address: usize, name: &str) -> ChangeScope {
+    if !is_recording() {
+        return ChangeScope { previous: None, active: false };
+    }
+    let source = if name.is_empty() { alloc::format!("{:#x}", address) } else { name.into() };
+    let previous = CURRENT_CHANGE.with(|c| c.replace(Some(source)));
+    ChangeScope { previous, active: true }
+}
+
+/// Restores the property that was previously being changed when dropped
+pub(super) struct ChangeScope {
+    previous: Option<String>,
+    active: bool,
+}
+
+impl Drop for ChangeScope {
+    fn drop(&mut self) {
+        if self.active {
+            CURRENT_CHANGE.with(|c| *c.borrow_mut() = self.previous.take());
+        }
+    }
+}
+
+/// Records that the layout of a window was invalidated by the property currently being changed.
+/// Called by the tracker of the window that gets dirty when it needs to be laid out and rendered again.
+pub(crate) fn record_invalidation() {
+    if !is_recording() {
+        return;
+    }
+    if let Some(source) = CURRENT_CHANGE.with(|c| c.borrow().clone()) {
+        RECORDED.with(|r| {
+            *r.borrow_mut().entry(source).or_default() += 1;
+        });
+    }
+}
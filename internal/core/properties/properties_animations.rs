@@ -959,4 +959,40 @@ fn test_loop_via_binding() {
 
         assert_eq!(get_prop_value(&compo.width), 300);
     }
+
+    #[test]
+    fn test_animation_driven_with_explicit_time() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            iteration_count: 1.,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+
+        let start_time =
+            instant::Duration::from_millis(crate::animations::current_tick().0) + DURATION;
+        crate::platform::update_timers_and_animations_with_time(start_time);
+
+        compo.width.set_animated_value(200, animation_details);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::platform::update_timers_and_animations_with_time(start_time + DURATION / 4);
+        assert_eq!(get_prop_value(&compo.width), 125);
+
+        crate::platform::update_timers_and_animations_with_time(start_time + DURATION / 2);
+        assert_eq!(get_prop_value(&compo.width), 150);
+
+        // Going back in time is ignored
+        crate::platform::update_timers_and_animations_with_time(start_time);
+        assert_eq!(get_prop_value(&compo.width), 150);
+
+        crate::platform::update_timers_and_animations_with_time(start_time + DURATION);
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        // the binding should be removed
+        compo.width.handle.access(|binding| assert!(binding.is_none()));
+    }
 }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::Range;
 use core::pin::Pin;
 
 use crate::graphics::{Point, Rect, Size};
@@ -9,19 +11,37 @@
 
 pub trait Renderer {
     /// Returns the size of the given text in logical pixels.
-    /// When set, `max_width` means that one need to wrap the text so it does not go further than that
+    /// When set, `max_width` means that one need to wrap the text so it does not go further than that.
+    /// `rules` tells how the tabs and a trailing newline count in the size.
     fn text_size(
         &self,
         font_request: crate::graphics::FontRequest,
         text: &str,
         max_width: Option<Coord>,
         scale_factor: f32,
+        rules: crate::graphics::TextMeasureRules,
     ) -> Size;
 
+    /// Returns the (UTF-8) byte ranges of the characters in `text` that have no glyph in the font
+    /// selected for `font_request`, including its fallback fonts. These characters would be
+    /// rendered as a placeholder box (tofu). Consecutive missing characters are merged in one
+    /// range, and whitespace and control characters are never reported.
+    ///
+    /// The default implementation reports nothing, for renderers that can't tell.
+    fn missing_glyphs(
+        &self,
+        _font_request: crate::graphics::FontRequest,
+        _text: &str,
+        _scale_factor: f32,
+    ) -> Vec<Range<usize>> {
+        Vec::new()
+    }
+
     /// Returns the (UTF-8) byte offset in the text property that refers to the character that contributed to
     /// the glyph cluster that's visually nearest to the given coordinate. This is used for hit-testing,
     /// for example when receiving a mouse click into a text field. Then this function returns the "cursor"
-    /// position.
+    /// position. While composing with an input method, the text is laid out with the pre-edit text
+    /// and the offset is in [`crate::items::TextInput::text_with_preedit`].
     fn text_input_byte_offset_for_position(
         &self,
         text_input: Pin<&crate::items::TextInput>,
@@ -30,7 +50,8 @@ fn text_input_byte_offset_for_position(
 
     /// That's the opposite of [`Self::text_input_byte_offset_for_position`]
     /// It takes a (UTF-8) byte offset in the text property, and returns a Rectangle
-    /// left to the char. It is one logical pixel wide and ends at the baseline.
+    /// left to the char. It is one logical pixel wide and ends at the baseline. Like the result of
+    /// [`Self::text_input_byte_offset_for_position`], the offset is in the text with the pre-edit text.
     fn text_input_cursor_rect_for_byte_offset(
         &self,
         text_input: Pin<&crate::items::TextInput>,
@@ -84,3 +105,33 @@ fn set_rendering_notifier(
         Err(crate::api::SetRenderingNotifierError::Unsupported)
     }
 }
+
+/// Helper for implementations of [`Renderer::missing_glyphs`]: returns the byte ranges of the
+/// characters of `text` for which `has_glyph` returns false, merging consecutive characters.
+/// `has_glyph` is not called for whitespace and control characters.
+pub fn missing_glyph_ranges(
+    text: &str,
+    mut has_glyph: impl FnMut(char) -> bool,
+) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (offset, ch) in text.char_indices() {
+        if ch.is_whitespace() || ch.is_control() || has_glyph(ch) {
+            continue;
+        }
+        let end = offset + ch.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == offset => last.end = end,
+            _ => ranges.push(offset..end),
+        }
+    }
+    ranges
+}
+
+#[test]
+fn test_missing_glyph_ranges() {
+    let latin_only = |ch: char| ch.is_ascii();
+    assert_eq!(missing_glyph_ranges("Hello", latin_only), Vec::<Range<usize>>::new());
+    // 'ह' and 'ि' are 3 bytes each, '🍌' is 4 bytes
+    assert_eq!(missing_glyph_ranges("aहि b🍌", latin_only), [1..7, 9..13]);
+    assert_eq!(missing_glyph_ranges("🍌\n🍌", latin_only), [0..4, 5..9]);
+}
@@ -240,8 +240,19 @@ fn text_size(
         text: &str,
         max_width: Option<Coord>,
         scale_factor: f32,
+        rules: crate::graphics::TextMeasureRules,
     ) -> crate::graphics::Size {
-        fonts::text_size(font_request, text, max_width, ScaleFactor::new(scale_factor)).to_untyped()
+        fonts::text_size(font_request, text, max_width, ScaleFactor::new(scale_factor), rules)
+            .to_untyped()
+    }
+
+    fn missing_glyphs(
+        &self,
+        font_request: crate::graphics::FontRequest,
+        text: &str,
+        scale_factor: f32,
+    ) -> Vec<core::ops::Range<usize>> {
+        fonts::missing_glyphs(font_request, text, ScaleFactor::new(scale_factor))
     }
 
     fn text_input_byte_offset_for_position(
@@ -816,6 +827,70 @@ fn should_draw(&self, rect: &LogicalRect) -> bool {
             && self.current_state.clip.intersects(rect)
     }
 
+    /// Draws the glyphs of the paragraph in `geom`, once for every pass: each pass shifts the
+    /// glyphs by its vector and draws them with its color.
+    fn draw_text_paragraph(
+        &mut self,
+        geom: LogicalRect,
+        paragraph: &TextParagraphLayout<fonts::PixelFont>,
+        passes: &[(euclid::Vector2D<f32, PhysicalPx>, Color)],
+    ) {
+        // Clip glyphs not only against the global clip but also against the text's geometry to avoid drawing outside
+        // of its boundaries (that breaks partial rendering and the cast to usize for the item relative coordinate below).
+        // FIXME: we should allow drawing outside of the element's boundaries.
+        let physical_clip = if let Some(logical_clip) = self.current_state.clip.intersection(&geom)
+        {
+            logical_clip.cast() * self.scale_factor
+        } else {
+            return; // This should have been caught earlier already
+        };
+        let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
+
+        let geom_origin = geom.origin.to_vector().cast() * self.scale_factor;
+        let font = paragraph.layout.font;
+
+        paragraph.layout_lines(|glyphs, line_x, line_y| {
+            let baseline_y = line_y + font.ascent();
+            let glyphs = glyphs.collect::<Vec<_>>();
+            for (shift, color) in passes {
+                for positioned_glyph in &glyphs {
+                    let src_rect = PhysicalRect::new(
+                        PhysicalPoint::from_lengths(
+                            line_x + positioned_glyph.x + positioned_glyph.platform_glyph.x(),
+                            baseline_y
+                                - positioned_glyph.platform_glyph.y()
+                                - positioned_glyph.platform_glyph.height(),
+                        ),
+                        positioned_glyph.platform_glyph.size(),
+                    )
+                    .cast()
+                    .translate(geom_origin + *shift);
+
+                    if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
+                        let geometry = clipped_src.translate(offset).round();
+                        let origin = (geometry.origin - offset.round()).cast::<isize>();
+                        // The shifted copies can start before the clip
+                        let actual_x = (origin.x - src_rect.origin.x as isize) as usize;
+                        let actual_y = (origin.y - src_rect.origin.y as isize) as usize;
+                        let stride = positioned_glyph.platform_glyph.width().get() as u16;
+                        let geometry = geometry.cast();
+                        self.processor.process_texture(
+                            geometry,
+                            SceneTexture {
+                                data: &positioned_glyph.platform_glyph.data().as_slice()
+                                    [actual_x + actual_y * stride as usize..],
+                                stride,
+                                source_size: geometry.size,
+                                format: PixelFormat::AlphaMap,
+                                color: *color,
+                            },
+                        );
+                    }
+                }
+            }
+        });
+    }
+
     fn draw_image_impl(
         &mut self,
         geom: LogicalRect,
@@ -1128,6 +1203,7 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
 
         let color = text.color().color();
         let max_size = (geom.size.cast() * self.scale_factor).cast();
+        let ellipsis = text.ellipsis();
 
         let paragraph = TextParagraphLayout {
             string: &string,
@@ -1138,55 +1214,25 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
             vertical_alignment: text.vertical_alignment(),
             wrap: text.wrap(),
             overflow: text.overflow(),
+            ellipsis: &ellipsis,
             single_line: false,
         };
 
-        // Clip glyphs not only against the global clip but also against the Text's geometry to avoid drawing outside
-        // of its boundaries (that breaks partial rendering and the cast to usize for the item relative coordinate below).
-        // FIXME: we should allow drawing outside of the Text element's boundaries.
-        let physical_clip = if let Some(logical_clip) = self.current_state.clip.intersection(&geom)
-        {
-            logical_clip.cast() * self.scale_factor
-        } else {
-            return; // This should have been caught earlier already
-        };
-        let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
-
-        paragraph.layout_lines(|glyphs, line_x, line_y| {
-            let baseline_y = line_y + font.ascent();
-            while let Some(positioned_glyph) = glyphs.next() {
-                let src_rect = PhysicalRect::new(
-                    PhysicalPoint::from_lengths(
-                        line_x + positioned_glyph.x + positioned_glyph.platform_glyph.x(),
-                        baseline_y
-                            - positioned_glyph.platform_glyph.y()
-                            - positioned_glyph.platform_glyph.height(),
-                    ),
-                    positioned_glyph.platform_glyph.size(),
-                )
-                .cast();
-
-                if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
-                    let geometry = clipped_src.translate(offset).round();
-                    let origin = (geometry.origin - offset.round()).cast::<usize>();
-                    let actual_x = origin.x - src_rect.origin.x as usize;
-                    let actual_y = origin.y - src_rect.origin.y as usize;
-                    let stride = positioned_glyph.platform_glyph.width().get() as u16;
-                    let geometry = geometry.cast();
-                    self.processor.process_texture(
-                        geometry,
-                        SceneTexture {
-                            data: &positioned_glyph.platform_glyph.data().as_slice()
-                                [actual_x + actual_y * stride as usize..],
-                            stride,
-                            source_size: geometry.size,
-                            format: PixelFormat::AlphaMap,
-                            color,
-                        },
-                    );
-                }
+        // There is no stroke of the glyphs in this renderer: the outline is approximated with
+        // copies of the glyphs shifted around them, drawn before the glyphs themselves.
+        let stroke_width = text.text_stroke_width() as f32 * self.scale_factor.get();
+        let stroke_color = text.text_stroke_color().color();
+        let mut passes = Vec::new();
+        if stroke_width > 0. && stroke_color.alpha() > 0 {
+            let (w, d) = (stroke_width, stroke_width * core::f32::consts::FRAC_1_SQRT_2);
+            for (x, y) in [(w, 0.), (-w, 0.), (0., w), (0., -w), (d, d), (d, -d), (-d, d), (-d, -d)]
+            {
+                passes.push((euclid::vec2(x, y).round(), stroke_color));
             }
-        });
+        }
+        passes.push((euclid::vec2(0., 0.), color));
+
+        self.draw_text_paragraph(geom, &paragraph, &passes);
     }
 
     fn draw_text_input(&mut self, text_input: Pin<&crate::items::TextInput>, _: &ItemRc) {
@@ -1194,6 +1240,42 @@ fn draw_text_input(&mut self, text_input: Pin<&crate::items::TextInput>, _: &Ite
         // TODO
     }
 
+    fn draw_text_input_placeholder(
+        &mut self,
+        text_input: Pin<&crate::items::TextInput>,
+        _: &ItemRc,
+    ) {
+        let string = text_input.placeholder_text();
+        if string.trim().is_empty() {
+            return;
+        }
+        let geom = LogicalRect::from_untyped(&text_input.text_rect());
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let font_request = text_input.font_request(&self.window.platform_window());
+        let font = fonts::match_font(&font_request, self.scale_factor);
+        let layout = fonts::text_layout_for_font(&font, &font_request, self.scale_factor);
+        let max_size = (geom.size.cast() * self.scale_factor).cast();
+
+        let paragraph = TextParagraphLayout {
+            string: &string,
+            layout,
+            max_width: max_size.width_length(),
+            max_height: max_size.height_length(),
+            horizontal_alignment: text_input.horizontal_alignment(),
+            vertical_alignment: text_input.vertical_alignment(),
+            wrap: text_input.wrap(),
+            overflow: crate::items::TextOverflow::Clip,
+            ellipsis: "",
+            single_line: text_input.single_line(),
+        };
+
+        let color = text_input.placeholder_color().color();
+        self.draw_text_paragraph(geom, &paragraph, &[(euclid::vec2(0., 0.), color)]);
+    }
+
     #[cfg(feature = "std")]
     fn draw_path(&mut self, path: Pin<&crate::items::Path>, _: &ItemRc) {
         path.logical_geometry();
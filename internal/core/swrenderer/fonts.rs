@@ -3,11 +3,14 @@
 
 use alloc::vec::Vec;
 use core::cell::RefCell;
+use core::ops::Range;
 
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::thread_local_ as thread_local;
 
-use crate::graphics::{BitmapFont, BitmapGlyph, BitmapGlyphs, FontRequest};
+use crate::graphics::{
+    BitmapFont, BitmapGlyph, BitmapGlyphs, FontRequest, TextMeasureRules, DEFAULT_FONT_SIZE,
+};
 use crate::lengths::{LogicalLength, LogicalSize, PhysicalLength, PhysicalSize, ScaleFactor};
 use crate::slice::Slice;
 use crate::textlayout::{Glyph, TextLayout, TextShaper};
@@ -68,8 +71,6 @@ fn pixel_size(&self) -> PhysicalLength {
     }
 }
 
-const DEFAULT_FONT_SIZE: Coord = 12 as Coord;
-
 // A font that is resolved to a specific pixel size.
 pub struct PixelFont {
     bitmap_font: &'static BitmapFont,
@@ -80,6 +81,16 @@ impl PixelFont {
     pub fn pixel_size(&self) -> PhysicalLength {
         self.glyphs.pixel_size()
     }
+
+    /// Returns the byte ranges of the characters of `text` that are not in this font.
+    pub fn missing_glyphs(&self, text: &str) -> Vec<Range<usize>> {
+        crate::renderer::missing_glyph_ranges(text, |ch| {
+            self.bitmap_font
+                .character_map
+                .binary_search_by_key(&ch, |char_map_entry| char_map_entry.code_point)
+                .is_ok()
+        })
+    }
 }
 
 impl TextShaper for PixelFont {
@@ -198,6 +209,7 @@ pub fn text_size(
     text: &str,
     max_width: Option<Coord>,
     scale_factor: ScaleFactor,
+    measure_rules: TextMeasureRules,
 ) -> LogicalSize {
     let font = match_font(&font_request, scale_factor);
     let layout = text_layout_for_font(&font, &font_request, scale_factor);
@@ -205,7 +217,54 @@ pub fn text_size(
     let (longest_line_width, height) = layout.text_size(
         text,
         max_width.map(|max_width| (LogicalLength::new(max_width).cast() * scale_factor).cast()),
+        measure_rules,
     );
 
     (PhysicalSize::from_lengths(longest_line_width, height).cast() / scale_factor).cast()
 }
+
+pub fn missing_glyphs(
+    font_request: FontRequest,
+    text: &str,
+    scale_factor: ScaleFactor,
+) -> Vec<Range<usize>> {
+    match_font(&font_request, scale_factor).missing_glyphs(text)
+}
+
+#[test]
+fn test_missing_glyphs() {
+    use crate::graphics::CharacterMapEntry;
+
+    static GLYPHS: [BitmapGlyph; 1] = [BitmapGlyph {
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+        x_advance: 10,
+        data: Slice::from_slice(&[]),
+    }];
+    static GLYPH_SETS: [BitmapGlyphs; 1] =
+        [BitmapGlyphs { pixel_size: 10, glyph_data: Slice::from_slice(&GLYPHS) }];
+    // A Latin-only font, that has the letters of "Hello" (sorted by code point)
+    static CHARACTER_MAP: [CharacterMapEntry; 4] = [
+        CharacterMapEntry { code_point: 'H', glyph_index: 0 },
+        CharacterMapEntry { code_point: 'e', glyph_index: 0 },
+        CharacterMapEntry { code_point: 'l', glyph_index: 0 },
+        CharacterMapEntry { code_point: 'o', glyph_index: 0 },
+    ];
+    static FONT: BitmapFont = BitmapFont {
+        family_name: Slice::from_slice(b"Latin"),
+        character_map: Slice::from_slice(&CHARACTER_MAP),
+        units_per_em: 10.,
+        ascent: 8.,
+        descent: -2.,
+        glyphs: Slice::from_slice(&GLYPH_SETS),
+    };
+
+    let font = PixelFont { bitmap_font: &FONT, glyphs: &GLYPH_SETS[0] };
+    assert_eq!(font.missing_glyphs("Hello"), Vec::<Range<usize>>::new());
+    assert_eq!(font.missing_glyphs("Hello\n"), Vec::<Range<usize>>::new());
+    // '✓' is 3 bytes long
+    assert_eq!(font.missing_glyphs("Hello ✓"), [6..9]);
+    assert_eq!(font.missing_glyphs("He✓✓llo"), [2..8]);
+}
@@ -25,6 +25,7 @@
 
 use euclid::num::{One, Zero};
 
+use crate::graphics::TextMeasureRules;
 use crate::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
 
 #[cfg(feature = "unicode-linebreak")]
@@ -55,12 +56,13 @@ pub struct TextLayout<'a, Font: AbstractFont> {
 
 impl<'a, Font: AbstractFont> TextLayout<'a, Font> {
     // Measures the size of the given text when rendered with the specified font and optionally constrained
-    // by the provided `max_width`.
+    // by the provided `max_width`, following the given measure `rules`.
     // Returns a tuple of the width of the longest line as well as height of all lines.
     pub fn text_size(
         &self,
         text: &str,
         max_width: Option<Font::Length>,
+        rules: TextMeasureRules,
     ) -> (Font::Length, Font::Length)
     where
         Font::Length: core::fmt::Debug,
@@ -69,10 +71,15 @@ pub fn text_size(
         let mut line_count: i16 = 0;
         let shape_buffer = ShapeBuffer::new(self, text);
 
-        for line in TextLineBreaker::<Font>::new(text, &shape_buffer, max_width) {
+        for line in
+            TextLineBreaker::<Font>::new(text, &shape_buffer, max_width, rules.break_anywhere)
+        {
             max_line_width = euclid::approxord::max(max_line_width, line.text_width);
             line_count += 1;
         }
+        if rules.adds_trailing_line(text) {
+            line_count += 1;
+        }
 
         (max_line_width, self.font.height() * line_count.into())
     }
@@ -93,6 +100,8 @@ pub struct TextParagraphLayout<'a, Font: AbstractFont> {
     pub vertical_alignment: TextVerticalAlignment,
     pub wrap: TextWrap,
     pub overflow: TextOverflow,
+    /// The string that replaces the end of the lines that don't fit, with `TextOverflow::Elide`
+    pub ellipsis: &'a str,
     pub single_line: bool,
 }
 
@@ -108,14 +117,12 @@ pub fn layout_lines(
             Font::Length,
         ),
     ) -> Font::Length {
-        let wrap = self.wrap == TextWrap::WordWrap;
-        let elide_glyph = if self.overflow == TextOverflow::Elide {
-            self.layout.font.glyph_for_char('…')
-        } else {
-            None
-        };
+        let wrap = self.wrap != TextWrap::NoWrap;
+        let elide_buffer = (self.overflow == TextOverflow::Elide)
+            .then(|| ShapeBuffer::new(&self.layout, self.ellipsis));
+        let elide_glyphs = elide_buffer.as_ref().map_or(&[][..], |buffer| &buffer.glyphs[..]);
         let max_width_without_elision =
-            self.max_width - elide_glyph.as_ref().map_or(Font::Length::zero(), |g| g.advance);
+            elide_glyphs.iter().fold(self.max_width, |max_width, glyph| max_width - glyph.advance);
 
         let shape_buffer = ShapeBuffer::new(&self.layout, self.string);
 
@@ -124,6 +131,7 @@ pub fn layout_lines(
                 self.string,
                 &shape_buffer,
                 if wrap { Some(self.max_width) } else { None },
+                self.wrap == TextWrap::CharWrap,
             )
         };
         let mut text_lines = None;
@@ -161,31 +169,32 @@ pub fn layout_lines(
                     }
                 };
 
-                let mut elide_glyph = elide_glyph.as_ref().clone();
+                let line_glyphs = &glyphs[line.glyph_range.clone()];
+                // The glyphs that start beyond the width are replaced with the ellipsis
+                // TODO: cut off at grapheme boundaries
+                let mut glyph_x = Font::Length::zero();
+                let visible_glyphs = line_glyphs
+                    .iter()
+                    .position(|glyph| {
+                        let beyond = glyph_x > max_width_without_elision;
+                        glyph_x += glyph.advance;
+                        beyond
+                    })
+                    .unwrap_or(line_glyphs.len());
+                let elision =
+                    if visible_glyphs < line_glyphs.len() { elide_glyphs } else { &[][..] };
 
-                let glyph_it = glyphs[line.glyph_range.clone()].iter();
                 let mut glyph_x = Font::Length::zero();
-                let mut positioned_glyph_it = glyph_it.map_while(|glyph| {
-                    // TODO: cut off at grapheme boundaries
-                    if glyph_x > max_width_without_elision {
-                        if let Some(elide_glyph) = elide_glyph.take() {
-                            return Some(PositionedGlyph {
-                                x: glyph_x,
-                                y: Font::Length::zero(),
-                                platform_glyph: &elide_glyph.platform_glyph,
-                            });
-                        } else {
-                            return None;
-                        }
-                    }
-                    let positioned_glyph = PositionedGlyph {
-                        x: glyph_x,
-                        y: Font::Length::zero(),
-                        platform_glyph: &glyph.platform_glyph,
-                    };
-                    glyph_x += glyph.advance;
-                    Some(positioned_glyph)
-                });
+                let mut positioned_glyph_it =
+                    line_glyphs[..visible_glyphs].iter().chain(elision).map(|glyph| {
+                        let positioned_glyph = PositionedGlyph {
+                            x: glyph_x,
+                            y: Font::Length::zero(),
+                            platform_glyph: &glyph.platform_glyph,
+                        };
+                        glyph_x += glyph.advance;
+                        positioned_glyph
+                    });
 
                 line_callback(&mut positioned_glyph_it, x, y);
                 y += self.layout.font.height();
@@ -289,6 +298,7 @@ fn test_elision() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
+        ellipsis: "…",
         single_line: true,
     };
     paragraph.layout_lines(|glyphs, _, _| {
@@ -321,6 +331,7 @@ fn test_exact_fit() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
+        ellipsis: "…",
         single_line: true,
     };
     paragraph.layout_lines(|glyphs, _, _| {
@@ -336,3 +347,25 @@ fn test_exact_fit() {
         lines[0].iter().map(|platform_glyph| platform_glyph.char.unwrap()).collect::<String>();
     debug_assert_eq!(rendered_text, "Fits")
 }
+
+#[test]
+fn test_text_size_trailing_newline() {
+    let font = FixedTestFont;
+    let layout = TextLayout { font: &font, letter_spacing: None };
+    let rules = TextMeasureRules::default();
+    assert_eq!(layout.text_size("Hello\n", None, rules), (50., 10.));
+    assert_eq!(layout.text_size("Hello\n\n", None, rules), (50., 20.));
+
+    let rules = TextMeasureRules::for_text_input();
+    assert_eq!(layout.text_size("Hello", None, rules), (50., 10.));
+    assert_eq!(layout.text_size("Hello\n", None, rules), (50., 20.));
+    assert_eq!(layout.text_size("Hello\n\n", None, rules), (50., 30.));
+}
+
+#[test]
+fn test_text_size_tab() {
+    let font = FixedTestFont;
+    let layout = TextLayout { font: &font, letter_spacing: None };
+    assert_eq!(layout.text_size("a\tb", None, Default::default()), (60., 10.));
+    assert_eq!(layout.text_size("\tb", None, Default::default()), (50., 10.));
+}
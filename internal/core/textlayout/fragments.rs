@@ -47,6 +47,19 @@ fn next(&mut self) -> Option<Self::Item> {
         let mut fragment = Self::Item::default();
 
         let next_break_offset = if self.break_anywhere {
+            // Skip the break opportunities within this glyph cluster, to stay in sync with the
+            // glyph clusters, but keep a mandatory break at its end.
+            while let Some((offset, break_type)) = self.line_breaks.clone().next() {
+                if offset > first_glyph_cluster.byte_range.end {
+                    break;
+                }
+                self.line_breaks.next();
+                if offset == first_glyph_cluster.byte_range.end
+                    && matches!(break_type, BreakOpportunity::Mandatory)
+                {
+                    fragment.trailing_mandatory_break = true;
+                }
+            }
             0
         } else if let Some((next_break_offset, break_type)) = self.line_breaks.next() {
             if matches!(break_type, BreakOpportunity::Mandatory) {
@@ -62,6 +62,8 @@ pub fn add_fragment(&mut self, fragment: &TextFragment<Length>) {
 pub struct TextLineBreaker<'a, Font: TextShaper> {
     fragments: TextFragmentIterator<'a, Font::Length, Font::PlatformGlyphData>,
     available_width: Option<Font::Length>,
+    /// Break the lines at any grapheme cluster, and not only when a word doesn't fit on a line.
+    break_anywhere: bool,
     current_line: TextLine<Font::Length>,
     num_emitted_lines: usize,
     mandatory_line_break_on_next_iteration: bool,
@@ -72,10 +74,12 @@ pub fn new(
         text: &'a str,
         shape_buffer: &'a ShapeBuffer<Font::Length, Font::PlatformGlyphData>,
         available_width: Option<Font::Length>,
+        break_anywhere: bool,
     ) -> Self {
         Self {
             fragments: TextFragmentIterator::new(text, shape_buffer),
             available_width,
+            break_anywhere,
             current_line: Default::default(),
             num_emitted_lines: 0,
             mandatory_line_break_on_next_iteration: false,
@@ -92,7 +96,7 @@ fn next(&mut self) -> Option<Self::Item> {
             return Some(core::mem::take(&mut self.current_line));
         }
 
-        self.fragments.break_anywhere = false;
+        self.fragments.break_anywhere = self.break_anywhere;
 
         let mut next_line = loop {
             // Clone the fragment iterator so that we can roll back in case we must break down the first
@@ -168,8 +172,8 @@ fn test_empty_line_break() {
     let font = FixedTestFont;
     let text = "";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
     assert_eq!(lines[0].line_text(&text), "");
 }
@@ -179,20 +183,33 @@ fn test_basic_line_break() {
     let font = FixedTestFont;
     let text = "Hello World";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "Hello");
     assert_eq!(lines[1].line_text(&text), "World");
 }
 
+#[test]
+fn test_break_anywhere_line_break() {
+    let font = FixedTestFont;
+    let text = "Hello World\nFoo";
+    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(80.), true)
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].line_text(&text), "Hello Wo");
+    assert_eq!(lines[1].line_text(&text), "rld");
+    assert_eq!(lines[2].line_text(&text), "Foo");
+}
+
 #[test]
 fn test_linebreak_trailing_space() {
     let font = FixedTestFont;
     let text = "Hello              ";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
     assert_eq!(lines[0].line_text(&text), "Hello");
 }
@@ -203,7 +220,7 @@ fn test_forced_break() {
     let text = "Hello\nWorld";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
     let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None).collect::<Vec<_>>();
+        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None, false).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "Hello");
     assert_eq!(lines[1].line_text(&text), "World");
@@ -215,7 +232,7 @@ fn test_forced_break_multi() {
     let text = "Hello\n\n\nWorld";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
     let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None).collect::<Vec<_>>();
+        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None, false).collect::<Vec<_>>();
     assert_eq!(lines.len(), 4);
     assert_eq!(lines[0].line_text(&text), "Hello");
     assert_eq!(lines[1].line_text(&text), "");
@@ -228,8 +245,8 @@ fn test_nbsp_break() {
     let font = FixedTestFont;
     let text = "Ok Hello\u{00a0}World";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(110.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(110.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "Ok");
     assert_eq!(lines[1].line_text(&text), "Hello\u{00a0}World");
@@ -241,7 +258,7 @@ fn test_single_line_multi_break_opportunity() {
     let text = "a b c";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
     let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None).collect::<Vec<_>>();
+        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None, false).collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
     assert_eq!(lines[0].line_text(&text), "a b c");
 }
@@ -251,8 +268,8 @@ fn test_basic_line_break_anywhere_fallback() {
     let font = FixedTestFont;
     let text = "HelloWorld";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "Hello");
     assert_eq!(lines[1].line_text(&text), "World");
@@ -263,8 +280,8 @@ fn test_basic_line_break_anywhere_fallback_multi_line() {
     let font = FixedTestFont;
     let text = "HelloWorld\nHelloWorld";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 4);
     assert_eq!(lines[0].line_text(&text), "Hello");
     assert_eq!(lines[1].line_text(&text), "World");
@@ -277,8 +294,8 @@ fn test_basic_line_break_anywhere_fallback_multi_line_v2() {
     let font = FixedTestFont;
     let text = "HelloW orldHellow";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 4);
     assert_eq!(lines[0].line_text(&text), "Hello");
     assert_eq!(lines[1].line_text(&text), "W");
@@ -292,8 +309,8 @@ fn test_basic_line_break_space() {
     let font = FixedTestFont;
     let text = "H W";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(25.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(25.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "H");
     assert_eq!(lines[1].line_text(&text), "W");
@@ -305,8 +322,8 @@ fn test_basic_line_break_space_v2() {
     let font = FixedTestFont;
     let text = "B B W";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(45.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(45.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "B B");
     assert_eq!(lines[1].line_text(&text), "W");
@@ -318,8 +335,8 @@ fn test_basic_line_break_space_v3() {
     let font = FixedTestFont;
     let text = "H   W";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(15.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(15.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
     assert_eq!(lines[0].line_text(&text), "H");
     assert_eq!(lines[1].line_text(&text), "W");
@@ -331,8 +348,8 @@ fn test_basic_line_break_space_v4() {
     let font = FixedTestFont;
     let text = "H W  H  ";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(65.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(65.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
     assert_eq!(lines[0].line_text(&text), "H W  H");
 }
@@ -342,8 +359,8 @@ fn test_line_width_with_whitespace() {
     let font = FixedTestFont;
     let text = "Hello World";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines =
-        TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(200.)).collect::<Vec<_>>();
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(200.), false)
+        .collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
     assert_eq!(lines[0].text_width, text.len() as f32 * 10.);
 }
@@ -353,7 +370,7 @@ fn zero_width() {
     let font = FixedTestFont;
     let text = "He\nHe o";
     let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
-    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(0.0001))
+    let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(0.0001), false)
         .map(|t| t.line_text(&text))
         .collect::<Vec<_>>();
     assert_eq!(lines, ["H", "e", "", "H", "e", "o"]);
@@ -161,6 +161,9 @@ pub struct TextRun {
     // TODO: direction, etc.
 }
 
+/// A tab advances by the width of this many spaces, both when measuring and when drawing a text.
+const TAB_WIDTH_IN_SPACES: i16 = 4;
+
 pub struct ShapeBuffer<Length, PlatformGlyphData> {
     pub glyphs: Vec<Glyph<Length, PlatformGlyphData>>,
     pub text_runs: Vec<TextRun>,
@@ -173,12 +176,25 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
         Length: Copy + core::ops::AddAssign,
     {
         let mut glyphs = Vec::new();
+        // A tab advances by the width of a number of spaces
+        let tab_advance = layout
+            .font
+            .glyph_for_char(' ')
+            .map(|space| space.advance * Font::LengthPrimitive::from(TAB_WIDTH_IN_SPACES));
         let text_runs = ShapeBoundaries::new(text)
             .scan(0, |run_start, run_end| {
                 let glyphs_start = glyphs.len();
 
                 layout.font.shape_text(&text[*run_start..run_end], &mut glyphs);
 
+                if let Some(tab_advance) = tab_advance {
+                    for glyph in &mut glyphs[glyphs_start..] {
+                        if text[*run_start + glyph.text_byte_offset..].starts_with('\t') {
+                            glyph.advance = tab_advance;
+                        }
+                    }
+                }
+
                 if let Some(letter_spacing) = layout.letter_spacing {
                     if glyphs.len() > glyphs_start {
                         let mut last_byte_offset = glyphs[glyphs_start].text_byte_offset;
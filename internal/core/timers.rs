@@ -134,6 +134,18 @@ pub fn restart(&self) {
         }
     }
 
+    /// Marks the timer to be run one last time when [`TimerList::cancel_all()`] is called
+    /// with `flush` set to true, for example to save pending changes of a debounce timer on shutdown.
+    ///
+    /// Does nothing if the timer was never started.
+    pub fn set_run_on_shutdown(&self, run_on_shutdown: bool) {
+        if let Some(id) = self.id.get() {
+            CURRENT_TIMERS.with(|timers| {
+                timers.borrow_mut().timers[id].run_on_shutdown = run_on_shutdown;
+            });
+        }
+    }
+
     /// Returns true if the timer is running; false otherwise.
     pub fn running(&self) -> bool {
         self.id
@@ -181,6 +193,8 @@ struct TimerData {
     running: bool,
     /// Set to true when it is removed when the callback is still running
     removed: bool,
+    /// Set to true if the callback should be invoked by `TimerList::cancel_all` when flushing
+    run_on_shutdown: bool,
     callback: CallbackVariant,
 }
 
@@ -198,6 +212,9 @@ pub struct TimerList {
     active_timers: Vec<ActiveTimer>,
     /// If a callback is currently running, this is the id of the currently running callback
     callback_active: Option<usize>,
+    /// Set when `cancel_all` is called from a timer callback, with its `flush` argument. The
+    /// timers are then cancelled once the callback returns.
+    cancel_all_requested: Option<bool>,
 }
 
 impl TimerList {
@@ -213,10 +230,11 @@ pub fn next_timeout() -> Option<Instant> {
         })
     }
 
-    /// Activates any expired timers by calling their callback function. Returns true if any timers were
-    /// activated; false otherwise.
-    pub fn maybe_activate_timers() -> bool {
-        let now = Instant::now();
+    /// Activates any timers that expired at or before `now` by calling their callback function.
+    /// Returns true if any timers were activated; false otherwise.
+    ///
+    /// Repeated timers are re-armed relative to `now`.
+    pub fn maybe_activate_timers(now: Instant) -> bool {
         // Shortcut: Is there any timer worth activating?
         if TimerList::next_timeout().map(|timeout| now < timeout).unwrap_or(false) {
             return false;
@@ -230,24 +248,37 @@ pub fn maybe_activate_timers() -> bool {
             // The active timer list is cleared here and not-yet-fired ones are inserted below, in order to allow
             // timer callbacks to register their own timers.
             let timers_to_process = core::mem::take(&mut timers.borrow_mut().active_timers);
-            for active_timer in timers_to_process.into_iter() {
+            let mut timers_to_process = timers_to_process.into_iter();
+            while let Some(active_timer) = timers_to_process.next() {
                 if active_timer.timeout <= now {
                     any_activated = true;
 
-                    timers.borrow_mut().callback_active = Some(active_timer.id);
-                    let callback = core::mem::replace(
-                        &mut timers.borrow_mut().timers[active_timer.id].callback,
-                        CallbackVariant::Empty,
-                    );
-                    let callback = callback.invoke();
-                    let mut timers = timers.borrow_mut();
-                    timers.timers[active_timer.id].callback = callback;
-                    timers.callback_active = None;
-
-                    if timers.timers[active_timer.id].removed {
-                        timers.timers.remove(active_timer.id);
-                    } else if matches!(timers.timers[active_timer.id].mode, TimerMode::Repeated) {
-                        timers.activate_timer(active_timer.id);
+                    Self::invoke_callback(timers, active_timer.id);
+                    let cancel_all_requested = {
+                        let mut timers = timers.borrow_mut();
+
+                        if timers.timers[active_timer.id].removed {
+                            timers.timers.remove(active_timer.id);
+                        } else if matches!(timers.timers[active_timer.id].mode, TimerMode::Repeated)
+                            && timers.timers[active_timer.id].running
+                        {
+                            let timeout = now + timers.timers[active_timer.id].duration;
+                            timers.register_active_timer(ActiveTimer {
+                                id: active_timer.id,
+                                timeout,
+                            });
+                        }
+                        timers.cancel_all_requested.take()
+                    };
+
+                    if let Some(flush) = cancel_all_requested {
+                        // The callback called cancel_all: the timers that were not processed yet
+                        // are cancelled too
+                        for active_timer in timers_to_process.by_ref() {
+                            timers.borrow_mut().register_active_timer(active_timer);
+                        }
+                        Self::cancel_all_timers(timers, flush);
+                        break;
                     }
                 } else {
                     timers.borrow_mut().register_active_timer(active_timer);
@@ -258,6 +289,68 @@ pub fn maybe_activate_timers() -> bool {
         })
     }
 
+    /// Stops all the running timers, so that none of their callbacks is invoked anymore.
+    /// This is meant to be used on shutdown or when tearing down a test, and includes internal
+    /// timers such as the one making the text cursor blink.
+    ///
+    /// If `flush` is true, the callbacks of the timers that were marked with
+    /// [`Timer::set_run_on_shutdown()`] are invoked once before the timers are stopped.
+    /// Timers started from these callbacks are stopped as well.
+    ///
+    /// Stopped timers can be started again.
+    ///
+    /// When called from a timer callback, the timers are cancelled once that callback returns,
+    /// including the ones it started.
+    pub fn cancel_all(flush: bool) {
+        CURRENT_TIMERS.with(|timers| {
+            let mut timer_list = timers.borrow_mut();
+            if timer_list.callback_active.is_some() {
+                let flush = flush || timer_list.cancel_all_requested.unwrap_or(false);
+                timer_list.cancel_all_requested = Some(flush);
+                return;
+            }
+            drop(timer_list);
+            Self::cancel_all_timers(timers, flush);
+        })
+    }
+
+    fn cancel_all_timers(timers: &RefCell<TimerList>, flush: bool) {
+        let timers_to_cancel = core::mem::take(&mut timers.borrow_mut().active_timers);
+        for active_timer in timers_to_cancel.iter() {
+            if flush && timers.borrow().timers[active_timer.id].run_on_shutdown {
+                Self::invoke_callback(timers, active_timer.id);
+            }
+            timers.borrow_mut().cancel_deactivated_timer(active_timer.id);
+        }
+
+        let started_while_flushing = core::mem::take(&mut timers.borrow_mut().active_timers);
+        for active_timer in started_while_flushing.iter() {
+            timers.borrow_mut().cancel_deactivated_timer(active_timer.id);
+        }
+        // The flushed callbacks may have asked for it, and it's done
+        timers.borrow_mut().cancel_all_requested = None;
+    }
+
+    fn invoke_callback(timers: &RefCell<TimerList>, id: usize) {
+        timers.borrow_mut().callback_active = Some(id);
+        let callback =
+            core::mem::replace(&mut timers.borrow_mut().timers[id].callback, CallbackVariant::Empty);
+        let callback = callback.invoke();
+        let mut timers = timers.borrow_mut();
+        timers.timers[id].callback = callback;
+        timers.callback_active = None;
+    }
+
+    /// Marks a timer that was taken out of the active timers as not running, and frees
+    /// it if nothing refers to it anymore.
+    fn cancel_deactivated_timer(&mut self, id: usize) {
+        if self.timers[id].removed {
+            self.timers.remove(id);
+        } else {
+            self.timers[id].running = false;
+        }
+    }
+
     fn start_or_restart_timer(
         &mut self,
         id: Option<usize>,
@@ -265,7 +358,14 @@ fn start_or_restart_timer(
         duration: core::time::Duration,
         callback: CallbackVariant,
     ) -> usize {
-        let timer_data = TimerData { duration, mode, running: false, removed: false, callback };
+        let timer_data = TimerData {
+            duration,
+            mode,
+            running: false,
+            removed: false,
+            run_on_shutdown: false,
+            callback,
+        };
         let inactive_timer_id = if let Some(id) = id {
             self.deactivate_timer(id);
             self.timers[id] = timer_data;
@@ -288,6 +388,11 @@ fn deactivate_timer(&mut self, id: usize) {
                 i += 1;
             }
         }
+        if self.callback_active == Some(id) {
+            // Stopped from its own callback, which was taken out of the active timers: it must not
+            // be re-armed when the callback returns
+            self.timers[id].running = false;
+        }
     }
 
     fn activate_timer(&mut self, timer_id: usize) {
@@ -338,6 +443,162 @@ fn lower_bound<T>(vec: &[T], mut less_than: impl FnMut(&T) -> bool) -> usize {
     left
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+
+    #[test]
+    fn cancel_all_timers() {
+        let fired = Rc::new(Cell::new(0));
+        let timer = Timer::default();
+        timer.start(TimerMode::Repeated, core::time::Duration::from_millis(100), {
+            let fired = fired.clone();
+            move || fired.set(fired.get() + 1)
+        });
+        Timer::single_shot(core::time::Duration::from_millis(100), {
+            let fired = fired.clone();
+            move || fired.set(fired.get() + 10)
+        });
+
+        let blinker = crate::input::TextCursorBlinker::new();
+        let cursor_visible = Box::pin(crate::Property::new(false));
+        crate::input::TextCursorBlinker::set_binding(blinker.clone(), &cursor_visible, || true);
+        assert!(cursor_visible.as_ref().get());
+
+        let now = Instant::now();
+        TimerList::cancel_all(false);
+        assert!(!timer.running());
+        assert!(TimerList::next_timeout().is_none());
+
+        assert!(!TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(5000)));
+        assert_eq!(fired.get(), 0);
+        assert!(cursor_visible.as_ref().get());
+
+        // The timer can be started again
+        timer.restart();
+        assert!(timer.running());
+    }
+
+    #[test]
+    fn text_cursor_blinker_disabled() {
+        let enabled = Rc::pin(crate::Property::new(true));
+        let blinker = crate::input::TextCursorBlinker::new();
+        let cursor_visible = Box::pin(crate::Property::new(false));
+        crate::input::TextCursorBlinker::set_binding(blinker.clone(), &cursor_visible, {
+            let enabled = enabled.clone();
+            move || enabled.as_ref().get()
+        });
+        assert!(cursor_visible.as_ref().get());
+
+        let now = Instant::now();
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(500));
+        assert!(!cursor_visible.as_ref().get());
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(1000));
+        assert!(cursor_visible.as_ref().get());
+
+        // Disabling hides the cursor, which doesn't blink anymore
+        enabled.set(false);
+        assert!(!cursor_visible.as_ref().get());
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(1500));
+        assert!(!cursor_visible.as_ref().get());
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(2000));
+        assert!(!cursor_visible.as_ref().get());
+
+        // Enabling shows it again, and it blinks
+        enabled.set(true);
+        assert!(cursor_visible.as_ref().get());
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(2500));
+        assert!(!cursor_visible.as_ref().get());
+
+        TimerList::cancel_all(false);
+    }
+
+    #[test]
+    fn stop_repeated_timer_from_callback() {
+        let fired = Rc::new(Cell::new(0));
+        let timer = Rc::new(Timer::default());
+        timer.start(TimerMode::Repeated, core::time::Duration::from_millis(100), {
+            let fired = fired.clone();
+            let timer = Rc::downgrade(&timer);
+            move || {
+                fired.set(fired.get() + 1);
+                if fired.get() == 2 {
+                    timer.upgrade().unwrap().stop();
+                }
+            }
+        });
+
+        let now = Instant::now();
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(100));
+        assert!(timer.running());
+        TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(200));
+        assert!(!timer.running());
+        assert!(!TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(5000)));
+        assert_eq!(fired.get(), 2);
+    }
+
+    #[test]
+    fn cancel_all_timers_with_flush() {
+        let fired = Rc::new(Cell::new(0));
+        let flushed = Timer::default();
+        flushed.start(TimerMode::SingleShot, core::time::Duration::from_millis(100), {
+            let fired = fired.clone();
+            move || fired.set(fired.get() + 1)
+        });
+        flushed.set_run_on_shutdown(true);
+        let not_flushed = Timer::default();
+        not_flushed.start(TimerMode::Repeated, core::time::Duration::from_millis(100), {
+            let fired = fired.clone();
+            move || fired.set(fired.get() + 10)
+        });
+
+        let now = Instant::now();
+        TimerList::cancel_all(true);
+        assert_eq!(fired.get(), 1);
+        assert!(!flushed.running());
+        assert!(!not_flushed.running());
+
+        assert!(!TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(5000)));
+        assert_eq!(fired.get(), 1);
+    }
+
+    #[test]
+    fn cancel_all_timers_from_callback() {
+        let fired = Rc::new(Cell::new(0));
+        let cancelling = Timer::default();
+        cancelling.start(TimerMode::Repeated, core::time::Duration::from_millis(100), {
+            let fired = fired.clone();
+            move || {
+                fired.set(fired.get() + 1);
+                TimerList::cancel_all(false);
+            }
+        });
+        let due = Timer::default();
+        due.start(TimerMode::SingleShot, core::time::Duration::from_millis(150), {
+            let fired = fired.clone();
+            move || fired.set(fired.get() + 10)
+        });
+        let later = Timer::default();
+        later.start(TimerMode::SingleShot, core::time::Duration::from_millis(200), {
+            let fired = fired.clone();
+            move || fired.set(fired.get() + 100)
+        });
+
+        // The timers are cancelled once the callback returns, even the ones that were due
+        let now = Instant::now();
+        assert!(TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(300)));
+        assert_eq!(fired.get(), 1);
+        assert!(!cancelling.running());
+        assert!(!due.running());
+        assert!(!later.running());
+        assert!(TimerList::next_timeout().is_none());
+
+        assert!(!TimerList::maybe_activate_timers(now + core::time::Duration::from_millis(5000)));
+        assert_eq!(fired.get(), 1);
+    }
+}
+
 #[cfg(feature = "ffi")]
 pub(crate) mod ffi {
     #![allow(unsafe_code)]
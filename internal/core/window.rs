@@ -10,10 +10,11 @@
 use crate::component::{ComponentRc, ComponentRef, ComponentVTable, ComponentWeak};
 use crate::graphics::{Point, Rect, Size};
 use crate::input::{
-    key_codes, KeyEvent, KeyEventType, MouseEvent, MouseInputState, TextCursorBlinker,
+    key_codes, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent, MouseInputState,
+    MultiTouchState, TextCursorBlinker, TouchPhase,
 };
 use crate::item_tree::ItemRc;
-use crate::items::{ItemRef, MouseCursor};
+use crate::items::{ItemRef, MouseCursor, PointerEventButton};
 use crate::properties::{Property, PropertyTracker};
 use crate::renderer::Renderer;
 use crate::{Callback, Coord};
@@ -83,12 +84,31 @@ fn apply_geometry_constraint(
     /// Set the mouse cursor
     fn set_mouse_cursor(&self, _cursor: MouseCursor) {}
 
+    /// Set a custom image as mouse cursor. `hotspot` is the position in the image, in physical pixels, that
+    /// designates the pointed location. The cursor stays until the next call to [`Self::set_mouse_cursor`].
+    /// It is called instead of `set_mouse_cursor(MouseCursor::Default)` while a custom cursor is set with
+    /// [`WindowInner::set_custom_cursor`].
+    ///
+    /// The default implementation ignores the image, so the cursor stays the default one.
+    fn set_custom_cursor(
+        &self,
+        _image: &crate::graphics::Image,
+        _hotspot: crate::lengths::PhysicalPoint,
+    ) {
+        self.set_mouse_cursor(MouseCursor::Default)
+    }
+
     /// This is called when the virtual keyboard should be shown because a widget that
     /// uses input has the focus.
     fn show_virtual_keyboard(&self, _: crate::items::InputType) {}
     /// This is called when the widget that needed the keyboard loses focus
     fn hide_virtual_keyboard(&self) {}
 
+    /// This is called when the text cursor of the focused text input moves, so that the input
+    /// method of the windowing system can show its candidate window next to it.
+    /// `rect` is the rectangle of the text cursor in logical window coordinates.
+    fn set_ime_cursor_area(&self, _rect: Rect) {}
+
     /// Return self as any so the backend can upcast
     fn as_any(&self) -> &dyn core::any::Any;
 
@@ -114,6 +134,21 @@ fn set_position(&self, _position: euclid::Point2D<i32, PhysicalPx>) {}
     /// The default implementation does nothing
     fn set_inner_size(&self, _size: euclid::Size2D<u32, PhysicalPx>) {}
 
+    /// Renders the current content of the window into a buffer, without presenting it on the screen.
+    ///
+    /// The default implementation returns None, meaning that the renderer can't do that.
+    fn take_snapshot(
+        &self,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        None
+    }
+
+    /// Requests that the presentation of new frames is synchronized (or not) with the vertical
+    /// blank of the display. The request is applied when the window is shown and whenever it changes.
+    ///
+    /// The default implementation does nothing
+    fn set_vsync(&self, _enabled: bool) {}
+
     /// Return the renderer
     fn renderer(&self) -> &dyn Renderer;
 
@@ -139,6 +174,8 @@ struct WindowRedrawTracker {
 
 impl crate::properties::PropertyDirtyHandler for WindowRedrawTracker {
     fn notify(&self) {
+        #[cfg(feature = "layout-invalidation")]
+        crate::properties::layout_invalidation::record_invalidation();
         if let Some(platform_window) = self.platform_window_weak.upgrade() {
             platform_window.request_redraw();
         };
@@ -162,23 +199,70 @@ pub struct PopupWindow {
     pub component: ComponentRc,
 }
 
+/// The interval at which the selection scrolls while the mouse selects past the visible area of
+/// a text input.
+const SELECTION_AUTOSCROLL_INTERVAL: core::time::Duration = core::time::Duration::from_millis(50);
+
+/// How long a key is held down before its key press repeats, see [`WindowInner::start_key_repeat`].
+const KEY_REPEAT_DELAY: core::time::Duration = core::time::Duration::from_millis(500);
+
+/// The interval at which the press of a held key repeats, after [`KEY_REPEAT_DELAY`].
+const KEY_REPEAT_INTERVAL: core::time::Duration = core::time::Duration::from_millis(50);
+
+/// The maximum distance between the presses of a double click, in logical pixels.
+const MULTI_CLICK_DISTANCE: Coord = 5 as Coord;
+
+/// The distance the mouse or the finger can move after a press and still make a long press, in
+/// logical pixels.
+const LONG_PRESS_DISTANCE: Coord = 10 as Coord;
+
 /// Inner datastructure for the [`crate::api::Window`]
 pub struct WindowInner {
     platform_window_weak: Weak<dyn PlatformWindow>,
     component: RefCell<ComponentWeak>,
     mouse_input_state: Cell<MouseInputState>,
+    multi_touch_state: RefCell<MultiTouchState>,
     redraw_tracker: Pin<Box<PropertyTracker<WindowRedrawTracker>>>,
     window_properties_tracker: Pin<Box<PropertyTracker<WindowPropertiesTracker>>>,
     /// Gets dirty when the layout restrictions, or some other property of the windows change
     meta_properties_tracker: Pin<Rc<PropertyTracker>>,
 
     focus_item: RefCell<crate::item_tree::ItemWeak>,
+    /// True while sending a FocusOut event that the focus item can't reject, see
+    /// [`Self::is_focus_out_forced`]
+    focus_out_forced: Cell<bool>,
+    /// The item whose subtree exclusively receives the input, see [`Self::set_input_mask`]
+    input_mask: RefCell<crate::item_tree::ItemWeak>,
     cursor_blinker: RefCell<pin_weak::rc::PinWeak<crate::input::TextCursorBlinker>>,
+    /// Scrolls the selection of the text input while the mouse selects past its visible area
+    selection_autoscroll_timer: crate::timers::Timer,
+    /// Repeats the press of the key that a text input handles while it is held down
+    key_repeat_timer: crate::timers::Timer,
+    /// Sends a [`MouseEvent::LongPress`] when the left button or the finger is held down long enough
+    long_press_timer: crate::timers::Timer,
+    /// The position of the press that can still become a long press, in window coordinates
+    long_press_position: Cell<Option<Point>>,
 
     scale_factor: Pin<Box<Property<f32>>>,
     active: Pin<Box<Property<bool>>>,
     active_popup: RefCell<Option<PopupWindow>>,
     close_requested: Callback<(), CloseRequestResponse>,
+    layout_settled: RefCell<Option<Box<dyn FnMut()>>>,
+    /// True while processing an event that comes from a touch screen
+    touch_input: Cell<bool>,
+    /// The modifier keys that are held, as last reported by the backend
+    keyboard_modifiers: Cell<KeyboardModifiers>,
+    /// The time, position and button of the last press, if the next press can continue its
+    /// series of clicks
+    last_press: Cell<Option<(crate::animations::Instant, Point, PointerEventButton)>>,
+    /// The number of consecutive clicks made by the last press
+    click_count: Cell<u8>,
+    minimum_touch_target_size: Cell<Coord>,
+    /// The area of the window covered by the virtual keyboard, while it is shown
+    virtual_keyboard_rect: Cell<Option<Rect>>,
+    /// The image and the hotspot of the cursor that replaces the default cursor, see
+    /// [`Self::set_custom_cursor`]
+    custom_cursor: RefCell<Option<(crate::graphics::Image, crate::lengths::PhysicalPoint)>>,
     /// This is a cache of the size set by the set_inner_size setter.
     /// It should be mapping with the WindowItem::width and height (only in physical)
     pub(crate) inner_size: Cell<euclid::Size2D<u32, PhysicalPx>>,
@@ -217,15 +301,30 @@ pub fn new(platform_window_weak: Weak<dyn PlatformWindow>) -> Self {
             platform_window_weak,
             component: Default::default(),
             mouse_input_state: Default::default(),
+            multi_touch_state: Default::default(),
             redraw_tracker: Box::pin(redraw_tracker),
             window_properties_tracker: Box::pin(window_properties_tracker),
             meta_properties_tracker: Rc::pin(Default::default()),
             focus_item: Default::default(),
+            focus_out_forced: Default::default(),
+            input_mask: Default::default(),
             cursor_blinker: Default::default(),
+            selection_autoscroll_timer: Default::default(),
+            key_repeat_timer: Default::default(),
+            long_press_timer: Default::default(),
+            long_press_position: Default::default(),
             scale_factor: Box::pin(Property::new_named(1., "i_slint_core::Window::scale_factor")),
             active: Box::pin(Property::new_named(false, "i_slint_core::Window::active")),
             active_popup: Default::default(),
             close_requested: Default::default(),
+            layout_settled: Default::default(),
+            touch_input: Default::default(),
+            keyboard_modifiers: Default::default(),
+            last_press: Default::default(),
+            click_count: Default::default(),
+            minimum_touch_target_size: Cell::new(0 as Coord),
+            virtual_keyboard_rect: Default::default(),
+            custom_cursor: Default::default(),
             inner_size: Default::default(),
         };
 
@@ -237,6 +336,7 @@ pub fn new(platform_window_weak: Weak<dyn PlatformWindow>) -> Self {
     pub fn set_component(&self, component: &ComponentRc) {
         self.close_popup();
         self.focus_item.replace(Default::default());
+        self.input_mask.replace(Default::default());
         self.mouse_input_state.replace(Default::default());
         self.component.replace(ComponentRc::downgrade(component));
         self.meta_properties_tracker.set_dirty(); // component changed, layout constraints for sure must be re-calculated
@@ -265,6 +365,7 @@ pub fn try_component(&self) -> Option<ComponentRc> {
     /// * `component`: The Slint compiled component that provides the tree of items.
     pub fn process_mouse_input(&self, mut event: MouseEvent) {
         crate::animations::update_animations();
+        self.recognize_long_press(&event);
 
         let embedded_popup_component =
             self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
@@ -316,6 +417,192 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
             }
         }
     }
+
+    /// Same as [`Self::process_mouse_input`], but for an event that comes from a touch screen.
+    ///
+    /// While processing the event, the area of the items that are smaller than the minimum
+    /// touch target size is enlarged, see [`Self::hit_test_rect`].
+    pub fn process_touch_input(&self, event: MouseEvent) {
+        self.touch_input.set(true);
+        self.process_mouse_input(event);
+        self.touch_input.set(false);
+    }
+
+    /// Receive the event of the touch point `id` of a touch screen, at `position` in window
+    /// coordinates. The events of a single touch point are processed like the ones of the left
+    /// mouse button with [`Self::process_touch_input`], and the ones of two touch points are
+    /// turned into [`MouseEvent::Pinch`], see [`MultiTouchState`].
+    pub fn process_touch_point(&self, id: u64, position: Point, phase: TouchPhase) {
+        let event = self.multi_touch_state.borrow_mut().touch_event(id, position, phase);
+        if let Some(event) = event {
+            self.process_touch_input(event);
+        }
+    }
+
+    /// Starts the long press timer when the left button or the finger is pressed, and cancels it
+    /// when it is released or moves too far before the timer fires.
+    fn recognize_long_press(&self, event: &MouseEvent) {
+        match *event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                self.long_press_position.set(Some(position));
+                let duration = crate::platform::PLAFTORM_ABSTRACTION_INSTANCE.with(|p| {
+                    p.get()
+                        .map_or(core::time::Duration::from_millis(500), |p| p.long_press_duration())
+                });
+                let touch_input = self.touch_input.get();
+                let platform_window_weak = self.platform_window_weak.clone();
+                self.long_press_timer.start(
+                    crate::timers::TimerMode::SingleShot,
+                    duration,
+                    move || {
+                        let platform_window = match platform_window_weak.upgrade() {
+                            Some(platform_window) => platform_window,
+                            None => return,
+                        };
+                        let window = platform_window.window().window_handle();
+                        if let Some(position) = window.long_press_position.take() {
+                            let event = MouseEvent::LongPress { position };
+                            if touch_input {
+                                window.process_touch_input(event);
+                            } else {
+                                window.process_mouse_input(event);
+                            }
+                        }
+                    },
+                );
+            }
+            MouseEvent::Moved { position } => {
+                if let Some(pressed_position) = self.long_press_position.get() {
+                    if (position - pressed_position).square_length()
+                        > LONG_PRESS_DISTANCE * LONG_PRESS_DISTANCE
+                    {
+                        self.cancel_long_press();
+                    }
+                }
+            }
+            MouseEvent::Pressed { .. } | MouseEvent::Released { .. } | MouseEvent::Exit => {
+                self.cancel_long_press();
+            }
+            MouseEvent::Wheel { .. }
+            | MouseEvent::Enter { .. }
+            | MouseEvent::LongPress { .. }
+            | MouseEvent::Pinch { .. } => {}
+        }
+    }
+
+    fn cancel_long_press(&self) {
+        self.long_press_position.set(None);
+        self.long_press_timer.stop();
+    }
+
+    /// Returns true while the focus item receives a FocusOut event that it can't reject, for example
+    /// because it became invisible. The item must then give up the focus even if it would otherwise
+    /// return [`crate::input::FocusEventResult::FocusOutRejected`].
+    pub fn is_focus_out_forced(&self) -> bool {
+        self.focus_out_forced.get()
+    }
+
+    /// Returns true while processing an event that comes from a touch screen, see
+    /// [`Self::process_touch_input`].
+    pub fn is_touch_input(&self) -> bool {
+        self.touch_input.get()
+    }
+
+    /// Returns the modifier keys that are currently held, as last reported with
+    /// [`Self::set_keyboard_modifiers`] or with the last key event.
+    pub fn keyboard_modifiers(&self) -> KeyboardModifiers {
+        self.keyboard_modifiers.get()
+    }
+
+    /// Returns the number of consecutive clicks made by the last press of a mouse button: 1 for a
+    /// single click, 2 for a double click, 3 for a triple click and so on. The presses are
+    /// consecutive if they are made with the same button, close enough in space, and within the
+    /// [double click interval](crate::platform::PlatformAbstraction::double_click_interval) of the
+    /// previous one.
+    pub fn click_count(&self) -> u8 {
+        self.click_count.get()
+    }
+
+    /// Makes the next press of a mouse button count as a single click, for example when a key is
+    /// pressed between two clicks.
+    pub fn reset_click_count(&self) {
+        self.last_press.set(None);
+    }
+
+    /// Counts the consecutive clicks with the press of `button` at `position`, in window
+    /// coordinates. Called for each press before it is dispatched to the items.
+    pub(crate) fn register_press(&self, position: Point, button: PointerEventButton) {
+        let now = crate::animations::current_tick();
+        let interval = crate::platform::PLAFTORM_ABSTRACTION_INSTANCE.with(|p| {
+            p.get().map_or(core::time::Duration::from_millis(500), |p| p.double_click_interval())
+        });
+        let count = match self.last_press.get() {
+            Some((time, last_position, last_button))
+                if last_button == button
+                    && now.duration_since(time) <= interval
+                    && (position - last_position).square_length()
+                        <= MULTI_CLICK_DISTANCE * MULTI_CLICK_DISTANCE =>
+            {
+                self.click_count.get().saturating_add(1)
+            }
+            _ => 1,
+        };
+        self.click_count.set(count);
+        self.last_press.set(Some((now, position, button)));
+    }
+
+    /// Records the modifier keys that are currently held. Backends call this when they change, so
+    /// that the mouse events are interpreted with them, for example Shift+Click extends the
+    /// selection of a text input.
+    pub fn set_keyboard_modifiers(&self, modifiers: KeyboardModifiers) {
+        self.keyboard_modifiers.set(modifiers)
+    }
+
+    /// Sets the mouse cursor of the window. The default cursor is replaced by the custom cursor set with
+    /// [`Self::set_custom_cursor`], if any.
+    pub fn set_mouse_cursor(&self, cursor: MouseCursor) {
+        let platform_window = self.platform_window();
+        match (cursor, self.custom_cursor.borrow().as_ref()) {
+            (MouseCursor::Default, Some((image, hotspot))) => {
+                platform_window.set_custom_cursor(image, *hotspot)
+            }
+            _ => platform_window.set_mouse_cursor(cursor),
+        }
+    }
+
+    /// Replaces the default mouse cursor of the window with the image, or restores it with `None`.
+    /// Items that set their own cursor still show it while they are hovered.
+    pub fn set_custom_cursor(
+        &self,
+        cursor: Option<(crate::graphics::Image, crate::lengths::PhysicalPoint)>,
+    ) {
+        *self.custom_cursor.borrow_mut() = cursor;
+        self.set_mouse_cursor(MouseCursor::Default);
+    }
+
+    /// Sets the minimum size of the area that reacts to touch events. Items that are narrower
+    /// or shorter than this size are hit by touch events in an area that is enlarged on
+    /// both sides to reach that size. The default is 0, which disables the enlargement.
+    pub fn set_minimum_touch_target_size(&self, size: Coord) {
+        self.minimum_touch_target_size.set(size);
+    }
+
+    /// Returns the area in which an item with the given `geometry` is hit by the event being
+    /// processed. This is the `geometry` itself, unless the event comes from a touch screen.
+    ///
+    /// When the enlarged areas of several items overlap, the item in front wins, as
+    /// the items are hit-tested from front to back.
+    pub fn hit_test_rect(&self, geometry: Rect) -> Rect {
+        if self.touch_input.get() {
+            let minimum_size = self.minimum_touch_target_size.get();
+            let dx = (minimum_size - geometry.width()).max(0 as Coord) / 2 as Coord;
+            let dy = (minimum_size - geometry.height()).max(0 as Coord) / 2 as Coord;
+            geometry.inflate(dx, dy)
+        } else {
+            geometry
+        }
+    }
+
     /// Receive a key event and pass it to the items of the component to
     /// change their state.
     ///
@@ -323,8 +610,18 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
     /// * `event`: The key event received by the windowing system.
     /// * `component`: The Slint compiled component that provides the tree of items.
     pub fn process_key_input(&self, event: &KeyEvent) {
+        // Backends that don't report the modifiers separately still send them with the key events
+        self.set_keyboard_modifiers(event.modifiers);
+        if event.event_type == KeyEventType::KeyPressed {
+            // Typing between two clicks doesn't make a double click
+            self.reset_click_count();
+        }
         let mut item = self.focus_item.borrow().clone().upgrade();
         while let Some(focus_item) = item {
+            if self.is_input_masked(&focus_item) {
+                // The event doesn't bubble out of the subtree of the input mask
+                break;
+            }
             if !focus_item.is_visible() {
                 // Reset the focus... not great, but better than keeping it.
                 self.take_focus_item();
@@ -348,8 +645,35 @@ pub fn process_key_input(&self, event: &KeyEvent) {
         }
     }
 
+    /// Receives an event of the input method of the windowing system, and forwards it to the
+    /// focused `TextInput`, see [`crate::items::TextInput::set_preedit`].
+    pub fn process_input_method_event(&self, event: &crate::input::InputMethodEvent) {
+        let focus_item = match self.focus_item.borrow().upgrade() {
+            Some(focus_item) => focus_item,
+            None => return,
+        };
+        if let Some(text_input) = focus_item.downcast::<crate::items::TextInput>() {
+            let text_input = text_input.as_pin_ref();
+            let platform_window = self.platform_window();
+            match event {
+                crate::input::InputMethodEvent::Preedit { text, cursor } => {
+                    text_input.set_preedit(text, *cursor, &platform_window)
+                }
+                crate::input::InputMethodEvent::Commit(text) => {
+                    text_input.set_preedit(text, None, &platform_window);
+                    text_input.commit_preedit(&platform_window);
+                }
+            }
+        }
+    }
+
     /// Installs a binding on the specified property that's toggled whenever the text cursor is supposed to be visible or not.
-    pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
+    /// The cursor stays hidden while `enabled` returns false, and the properties it reads are tracked.
+    pub fn set_cursor_blink_binding(
+        &self,
+        prop: &crate::Property<bool>,
+        enabled: impl Fn() -> bool + 'static,
+    ) {
         let existing_blinker = self.cursor_blinker.borrow().clone();
 
         let blinker = existing_blinker.upgrade().unwrap_or_else(|| {
@@ -359,15 +683,69 @@ pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
             new_blinker
         });
 
-        TextCursorBlinker::set_binding(blinker, prop);
+        TextCursorBlinker::set_binding(blinker, prop, enabled);
+    }
+
+    /// Calls `autoscroll` at regular intervals, until [`Self::stop_selection_autoscroll`] is called.
+    /// A text input calls this while the mouse selects text past its visible area. This replaces
+    /// the previous `autoscroll` function, if any.
+    pub fn start_selection_autoscroll(&self, autoscroll: impl FnMut() + 'static) {
+        self.selection_autoscroll_timer.start(
+            crate::timers::TimerMode::Repeated,
+            SELECTION_AUTOSCROLL_INTERVAL,
+            autoscroll,
+        );
+    }
+
+    /// Stops calling the function passed to [`Self::start_selection_autoscroll`].
+    pub fn stop_selection_autoscroll(&self) {
+        self.selection_autoscroll_timer.stop();
+    }
+
+    /// Calls `repeat` at regular intervals once the key repeat delay elapsed, until
+    /// [`Self::stop_key_repeat`] is called. A text input calls this when a key press moves its
+    /// cursor. The platforms that repeat the key presses themselves call this again with every
+    /// repeated press, before the delay elapsed, so that `repeat` is only called on the others.
+    pub fn start_key_repeat(&self, mut repeat: impl FnMut() + 'static) {
+        let mut delay = KEY_REPEAT_DELAY;
+        self.key_repeat_timer.start(
+            crate::timers::TimerMode::Repeated,
+            KEY_REPEAT_INTERVAL,
+            move || {
+                if delay > KEY_REPEAT_INTERVAL {
+                    delay -= KEY_REPEAT_INTERVAL;
+                } else {
+                    repeat();
+                }
+            },
+        );
+    }
+
+    /// Stops calling the function passed to [`Self::start_key_repeat`].
+    pub fn stop_key_repeat(&self) {
+        self.key_repeat_timer.stop();
     }
 
     /// Sets the focus to the item pointed to by item_ptr. This will remove the focus from any
     /// currently focused item.
+    ///
+    /// The currently focused item can prevent this by rejecting the FocusOut event.
     pub fn set_focus_item(&self, focus_item: &ItemRc) {
-        let old = self.take_focus_item();
+        if self.is_input_masked(focus_item) {
+            return;
+        }
+        let old = match self.try_take_focus_item() {
+            Ok(old) => old,
+            Err(()) => return,
+        };
         let new = self.clone().move_focus(focus_item.clone(), next_focus_item);
         self.platform_window().handle_focus_change(old, new);
+        self.scroll_focus_item_above_virtual_keyboard();
+    }
+
+    /// Returns the item that has the keyboard focus, if any.
+    pub fn focus_item(&self) -> Option<ItemRc> {
+        self.focus_item.borrow().upgrade()
     }
 
     /// Sets the focus on the window to true or false, depending on the have_focus argument.
@@ -384,17 +762,45 @@ pub fn set_focus(&self, have_focus: bool) {
         }
     }
 
-    /// Take the focus_item out of this Window
+    /// Take the focus_item out of this Window, unless it rejects the FocusOut event. In that case
+    /// the focus_item is kept and `Err(())` is returned.
     ///
     /// This sends the FocusOut event!
+    fn try_take_focus_item(&self) -> Result<Option<ItemRc>, ()> {
+        let focus_item = self.focus_item.take();
+
+        if let Some(focus_item_rc) = focus_item.upgrade() {
+            let result = focus_item_rc
+                .borrow()
+                .as_ref()
+                .focus_event(&crate::input::FocusEvent::FocusOut, &self.platform_window());
+            if result == crate::input::FocusEventResult::FocusOutRejected {
+                // Unless the item was given the focus again while handling the event
+                let mut current_focus_item = self.focus_item.borrow_mut();
+                if current_focus_item.upgrade().is_none() {
+                    *current_focus_item = focus_item;
+                }
+                return Err(());
+            }
+            Ok(Some(focus_item_rc))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Take the focus_item out of this Window, even if it would reject the FocusOut event.
+    ///
+    /// This sends the FocusOut event! During that event, [`Self::is_focus_out_forced`] returns true.
     fn take_focus_item(&self) -> Option<ItemRc> {
         let focus_item = self.focus_item.take();
 
         if let Some(focus_item_rc) = focus_item.upgrade() {
+            let was_forced = self.focus_out_forced.replace(true);
             focus_item_rc
                 .borrow()
                 .as_ref()
                 .focus_event(&crate::input::FocusEvent::FocusOut, &self.platform_window());
+            self.focus_out_forced.set(was_forced);
             Some(focus_item_rc)
         } else {
             None
@@ -425,6 +831,7 @@ fn move_focus(&self, start_item: ItemRc, forward: impl Fn(ItemRc) -> ItemRc) ->
 
         loop {
             if current_item.is_visible()
+                && !self.is_input_masked(&current_item)
                 && self.publish_focus_item(&Some(current_item.clone()))
                     == crate::input::FocusEventResult::FocusAccepted
             {
@@ -439,23 +846,28 @@ fn move_focus(&self, start_item: ItemRc, forward: impl Fn(ItemRc) -> ItemRc) ->
         }
     }
 
-    /// Move keyboard focus to the next item
+    /// Move keyboard focus to the next item, unless the focused item rejects losing it.
     pub fn focus_next_item(&self) {
         let component = self.component();
-        let start_item = self
-            .take_focus_item()
-            .map(next_focus_item)
-            .unwrap_or_else(|| ItemRc::new(component, 0));
+        let start_item = match self.try_take_focus_item() {
+            Ok(focus_item) => {
+                focus_item.map(next_focus_item).unwrap_or_else(|| ItemRc::new(component, 0))
+            }
+            Err(()) => return,
+        };
         let end_item = self.move_focus(start_item.clone(), next_focus_item);
         self.platform_window().handle_focus_change(Some(start_item), end_item);
     }
 
-    /// Move keyboard focus to the previous item.
+    /// Move keyboard focus to the previous item, unless the focused item rejects losing it.
     pub fn focus_previous_item(&self) {
         let component = self.component();
-        let start_item = previous_focus_item(
-            self.take_focus_item().unwrap_or_else(|| ItemRc::new(component, 0)),
-        );
+        let start_item = match self.try_take_focus_item() {
+            Ok(focus_item) => {
+                previous_focus_item(focus_item.unwrap_or_else(|| ItemRc::new(component, 0)))
+            }
+            Err(()) => return,
+        };
         let end_item = self.move_focus(start_item.clone(), previous_focus_item);
         self.platform_window().handle_focus_change(Some(start_item), end_item);
     }
@@ -487,7 +899,14 @@ pub fn update_window_properties(&self) {
 
     /// Calls the render_components to render the main component and any sub-window components, tracked by a
     /// property dependency tracker.
+    ///
+    /// Before that, if a callback was set with [`Self::on_layout_settled`], the layout of all items is computed
+    /// and the callback is run.
     pub fn draw_contents(&self, render_components: impl FnOnce(&[(&ComponentRc, Point)])) {
+        // Outside of the redraw tracker, so that the properties the callback reads or sets don't
+        // become dependencies of the rendering.
+        self.settle_layout();
+
         let draw_fn = || {
             let component_rc = self.component();
             let component = ComponentRc::borrow_pin(&component_rc);
@@ -667,6 +1086,39 @@ pub fn on_close_requested(&self, mut callback: impl FnMut() -> CloseRequestRespo
         self.close_requested.set_handler(move |()| callback());
     }
 
+    /// Sets the layout_settled callback. The callback will be run each time the window is about to be
+    /// rendered, after the geometry of all items was computed.
+    pub fn on_layout_settled(&self, callback: impl FnMut() + 'static) {
+        *self.layout_settled.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Computes the geometry of all items of the window's component and runs the layout_settled callback.
+    /// Does nothing if no callback is set.
+    fn settle_layout(&self) {
+        let mut callback = match self.layout_settled.take() {
+            Some(callback) => callback,
+            None => return,
+        };
+        if let Some(component_rc) = self.try_component() {
+            // Layouts are lazily evaluated bindings, reading the geometries computes them.
+            crate::item_tree::visit_items(
+                &component_rc,
+                crate::item_tree::TraversalOrder::BackToFront,
+                |_, item, _, _| {
+                    item.as_ref().geometry();
+                    crate::item_tree::ItemVisitorResult::Continue(())
+                },
+                (),
+            );
+            callback();
+        }
+        // Keep the callback, unless it was replaced from within itself
+        let mut layout_settled = self.layout_settled.borrow_mut();
+        if layout_settled.is_none() {
+            *layout_settled = Some(callback);
+        }
+    }
+
     /// Runs the close_requested callback.
     /// If the callback returns KeepWindowShown, this function returns false. That should prevent the Window from closing.
     /// Otherwise it returns true, which allows the Window to hide.
@@ -677,6 +1129,134 @@ pub fn request_close(&self) -> bool {
         }
     }
 
+    /// Calls `f` with the state of the mouse input, which holds the items under the mouse cursor
+    /// and the mouse grab.
+    pub fn with_mouse_input_state<R>(&self, f: impl FnOnce(&mut MouseInputState) -> R) -> R {
+        let mut mouse_input_state = self.mouse_input_state.take();
+        let result = f(&mut mouse_input_state);
+        self.mouse_input_state.set(mouse_input_state);
+        result
+    }
+
+    /// Reports the rectangle of the text cursor of the focused item to the platform window for the
+    /// input method. `rect` is relative to the focused item; it is converted to window coordinates by
+    /// adding the position of the item and the positions of all its parents.
+    pub fn set_ime_cursor_area_of_focus_item(&self, rect: Rect) {
+        if let Some(focus_item) = self.focus_item.borrow().upgrade() {
+            let origin =
+                focus_item.map_to_window(focus_item.geometry().origin + rect.origin.to_vector());
+            self.platform_window().set_ime_cursor_area(Rect::new(origin, rect.size));
+        }
+    }
+
+    /// Restricts the input to the subtree of `item`, or removes the restriction if `None`. This is
+    /// the primitive behind modal dialogs.
+    ///
+    /// While set, the mouse and touch events are only delivered to `item` and its children, and are
+    /// dropped where they would have reached any other item. To react to clicks outside of a dialog,
+    /// like with a scrim that closes it, make the scrim part of the subtree of `item`. The key events
+    /// are only delivered when the focused item is in the subtree and don't bubble out of it, and
+    /// the keyboard focus can't be moved out of it. If the focused item is outside of the subtree,
+    /// it loses the focus. The items of an open popup keep receiving the input.
+    ///
+    /// The ancestors of `item` still filter and clip the mouse events on their way to it, and
+    /// receive the ones they intercept, like a Flickable that scrolls the dialog when dragged.
+    pub fn set_input_mask(&self, item: Option<&ItemRc>) {
+        *self.input_mask.borrow_mut() = item.map(|item| item.downgrade()).unwrap_or_default();
+        if item.is_none() {
+            return;
+        }
+        // The items under the mouse outside of the subtree are not hovered anymore
+        let mut mouse_input_state = self.mouse_input_state.take();
+        crate::input::send_exit_events(&mut mouse_input_state, None, &self.platform_window());
+        let focus_item = self.focus_item.borrow().upgrade();
+        if let Some(focus_item) = focus_item {
+            if self.is_input_masked(&focus_item) {
+                self.take_focus_item();
+                self.platform_window().handle_focus_change(Some(focus_item), None);
+            }
+        }
+    }
+
+    /// Returns the item set with [`Self::set_input_mask`], if any.
+    pub fn input_mask(&self) -> Option<ItemRc> {
+        self.input_mask.borrow().upgrade()
+    }
+
+    /// Returns true if the input mask set with [`Self::set_input_mask`] prevents `item` from
+    /// receiving input, because it is neither in the subtree of the mask nor in an open popup.
+    pub fn is_input_masked(&self, item: &ItemRc) -> bool {
+        let filter = self.input_mask_filter();
+        let mut current = Some(item.clone());
+        while let Some(it) = current {
+            if filter.is_unmasked_root(&it) {
+                return false;
+            }
+            current = it.parent_item();
+        }
+        filter.mask_path.is_some()
+    }
+
+    /// Returns the items that the input mask set with [`Self::set_input_mask`] lets through, to
+    /// filter the items while dispatching an event to them.
+    pub(crate) fn input_mask_filter(&self) -> crate::input::InputMaskFilter {
+        let mask_path = self.input_mask.borrow().upgrade().map(|mask| {
+            let mut path = alloc::vec![mask];
+            while let Some(parent) = path.last().unwrap().parent_item() {
+                path.push(parent);
+            }
+            path
+        });
+        let popup_root = self
+            .active_popup
+            .borrow()
+            .as_ref()
+            .map(|popup| ItemRc::new(popup.component.clone(), 0));
+        crate::input::InputMaskFilter { mask_path, popup_root }
+    }
+
+    /// Sets the area of the window, in logical coordinates, that is covered by the virtual keyboard,
+    /// or `None` when the virtual keyboard is hidden.
+    ///
+    /// The platform window calls this when the virtual keyboard is shown, hidden, or changes its
+    /// size. If the focused item is covered, the window scrolls it above the keyboard with
+    /// [`Self::scroll_focus_item_above_virtual_keyboard`].
+    pub fn set_virtual_keyboard_rect(&self, rect: Option<Rect>) {
+        self.virtual_keyboard_rect.set(rect.filter(|r| !r.is_empty()));
+        self.scroll_focus_item_above_virtual_keyboard();
+    }
+
+    /// Returns the area of the window covered by the virtual keyboard, if it is shown.
+    pub fn virtual_keyboard_rect(&self) -> Option<Rect> {
+        self.virtual_keyboard_rect.get()
+    }
+
+    /// If the virtual keyboard covers the bottom of the focused item, scrolls the Flickables that
+    /// contain the item, starting with the closest one, until the item is above the keyboard or
+    /// the Flickables can't scroll further. The item may stay covered if it isn't in a Flickable.
+    pub fn scroll_focus_item_above_virtual_keyboard(&self) {
+        let (keyboard_rect, focus_item) =
+            match (self.virtual_keyboard_rect.get(), self.focus_item.borrow().upgrade()) {
+                (Some(keyboard_rect), Some(focus_item)) => (keyboard_rect, focus_item),
+                _ => return,
+            };
+        let geometry = focus_item.geometry();
+        let bottom = focus_item.map_to_window(geometry.origin).y + geometry.height();
+        let mut overlap = bottom - keyboard_rect.min_y();
+        let mut current = focus_item;
+        while overlap > 0 as Coord {
+            let parent = match current.parent_item() {
+                Some(parent) => parent,
+                None => break,
+            };
+            if let Some(flickable) = parent.downcast::<crate::items::Flickable>() {
+                overlap +=
+                    flickable.as_pin_ref().scroll_viewport_by(euclid::vec2(0 as Coord, -overlap)).y;
+            }
+            current = parent;
+        }
+    }
+
     /// Returns the upgraded rlatform window.
     pub fn platform_window(&self) -> Rc<dyn PlatformWindow> {
         self.platform_window_weak.upgrade().unwrap()
@@ -789,6 +1369,16 @@ pub enum GraphicsAPI {
         platform_window.window().window_handle().set_focus_item(focus_item)
     }
 
+    /// Sets the input mask, or removes it if `mask_item` is null.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_set_input_mask(
+        handle: *const PlatformWindowRcOpaque,
+        mask_item: Option<&ItemRc>,
+    ) {
+        let platform_window = &*(handle as *const Rc<dyn PlatformWindow>);
+        platform_window.window().window_handle().set_input_mask(mask_item)
+    }
+
     /// Associates the window with the given component.
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_set_component(
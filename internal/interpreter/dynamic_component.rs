@@ -117,11 +117,11 @@ pub(crate) struct RepeaterWithinComponent<'par_id, 'sub_id> {
 impl RepeatedComponent for ErasedComponentBox {
     type Data = Value;
 
-    fn update(&self, index: usize, data: Self::Data) {
+    fn update(&self, index: usize, data: &Self::Data) {
         generativity::make_guard!(guard);
         let s = self.unerase(guard);
         s.component_type.set_property(s.borrow(), "index", index.try_into().unwrap()).unwrap();
-        s.component_type.set_property(s.borrow(), "model_data", data).unwrap();
+        s.component_type.set_property(s.borrow(), "model_data", data.clone()).unwrap();
     }
 
     fn listview_layout(self: Pin<&Self>, offset_y: &mut f32, viewport_width: Pin<&Property<f32>>) {
@@ -150,6 +150,36 @@ fn listview_layout(self: Pin<&Self>, offset_y: &mut f32, viewport_width: Pin<&Pr
         }
     }
 
+    fn listview_layout_horizontal(
+        self: Pin<&Self>,
+        offset_x: &mut f32,
+        viewport_height: Pin<&Property<f32>>,
+    ) {
+        generativity::make_guard!(guard);
+        let s = self.unerase(guard);
+
+        s.component_type
+            .set_property(s.borrow(), "x", Value::Number(*offset_x as f64))
+            .expect("cannot set x");
+        let h: f32 = s
+            .component_type
+            .get_property(s.borrow(), "height")
+            .expect("missing height")
+            .try_into()
+            .expect("height not the right type");
+        let w: f32 = s
+            .component_type
+            .get_property(s.borrow(), "width")
+            .expect("missing width")
+            .try_into()
+            .expect("width not the right type");
+        *offset_x += w;
+        let vp_h = viewport_height.get();
+        if vp_h < h {
+            viewport_height.set(h);
+        }
+    }
+
     fn box_layout_data(self: Pin<&Self>, o: Orientation) -> BoxLayoutCellData {
         BoxLayoutCellData { constraint: self.borrow().as_ref().layout_info(o) }
     }
@@ -681,14 +711,39 @@ fn ensure_repeater_updated<'id>(
         let get_prop = |nr: &NamedReference| -> f32 {
             eval::load_property(instance_ref, &nr.element(), nr.name()).unwrap().try_into().unwrap()
         };
-        repeater.ensure_updated_listview(
-            init,
-            assume_property_f32(get_property_ptr(&lv.viewport_width, instance_ref)),
-            assume_property_f32(get_property_ptr(&lv.viewport_height, instance_ref)),
-            assume_property_f32(get_property_ptr(&lv.viewport_y, instance_ref)),
-            get_prop(&lv.listview_width),
-            assume_property_f32(get_property_ptr(&lv.listview_height, instance_ref)),
+        repeater
+            .set_listview_windowing_threshold(get_prop(&lv.windowing_threshold).max(0.) as usize);
+        repeater.set_listview_end_reached_threshold(
+            get_prop(&lv.end_reached_threshold).max(0.) as usize
+        );
+        let springback = get_prop(&lv.overscroll_springback);
+        repeater.set_listview_overscroll_springback(
+            (springback > 0.).then(|| core::time::Duration::from_millis(springback as u64)),
         );
+        let horizontal: bool =
+            eval::load_property(instance_ref, &lv.horizontal.element(), lv.horizontal.name())
+                .unwrap()
+                .try_into()
+                .unwrap();
+        if horizontal {
+            repeater.ensure_updated_listview_horizontal(
+                init,
+                assume_property_f32(get_property_ptr(&lv.viewport_width, instance_ref)),
+                assume_property_f32(get_property_ptr(&lv.viewport_height, instance_ref)),
+                assume_property_f32(get_property_ptr(&lv.viewport_x, instance_ref)),
+                assume_property_f32(get_property_ptr(&lv.listview_width, instance_ref)),
+                get_prop(&lv.listview_height),
+            );
+        } else {
+            repeater.ensure_updated_listview(
+                init,
+                assume_property_f32(get_property_ptr(&lv.viewport_width, instance_ref)),
+                assume_property_f32(get_property_ptr(&lv.viewport_height, instance_ref)),
+                assume_property_f32(get_property_ptr(&lv.viewport_y, instance_ref)),
+                get_prop(&lv.listview_width),
+                assume_property_f32(get_property_ptr(&lv.listview_height, instance_ref)),
+            );
+        }
     } else {
         repeater.ensure_updated(init);
     }
@@ -1379,6 +1434,50 @@ pub fn instantiate(
             let m = model_binding_closure();
             i_slint_core::model::ModelRc::new(crate::value_model::ValueModel::new(m))
         });
+
+        if let Some(lv) = &rep_in_comp
+            .component_to_repeat
+            .original
+            .parent_element
+            .upgrade()
+            .unwrap()
+            .borrow()
+            .repeated
+            .as_ref()
+            .unwrap()
+            .is_listview
+        {
+            let scrolled = lv.scrolled.clone();
+            repeater.set_listview_scroll_callback({
+                let self_weak = self_weak.clone();
+                move |row, fraction| {
+                    let self_rc = self_weak.upgrade().unwrap();
+                    generativity::make_guard!(guard);
+                    let self_ = self_rc.unerase(guard);
+                    eval::invoke_callback(
+                        eval::ComponentInstance::InstanceRef(self_.borrow_instance()),
+                        &scrolled.element(),
+                        scrolled.name(),
+                        &[Value::Number(row as f64), Value::Number(fraction as f64)],
+                    );
+                }
+            });
+            let end_reached = lv.end_reached.clone();
+            repeater.set_listview_end_reached_callback(0, {
+                let self_weak = self_weak.clone();
+                move || {
+                    let self_rc = self_weak.upgrade().unwrap();
+                    generativity::make_guard!(guard);
+                    let self_ = self_rc.unerase(guard);
+                    eval::invoke_callback(
+                        eval::ComponentInstance::InstanceRef(self_.borrow_instance()),
+                        &end_reached.element(),
+                        end_reached.name(),
+                        &[],
+                    );
+                }
+            });
+        }
     }
 
     self_rc
@@ -313,6 +313,40 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::SetInputMask, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to SetInputMask")
+                }
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot set the input mask from a global component")
+                };
+                if let Expression::ElementReference(mask_item) = &arguments[0] {
+                    generativity::make_guard!(guard);
+
+                    let mask_item = mask_item.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&mask_item, component, guard);
+                    let component_type = enclosing_component.component_type;
+
+                    let item_info = &component_type.items[mask_item.borrow().id.as_str()];
+
+                    let mask_item_comp = enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
+
+                    window_ref(component).unwrap().set_input_mask(Some(&corelib::items::ItemRc::new(vtable::VRc::into_dyn(mask_item_comp), item_info.item_index())));
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to SetInputMask must be an element")
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ClearInputMask, _) => {
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot clear the input mask from a global component")
+                };
+                window_ref(component).unwrap().set_input_mask(None);
+                Value::Void
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to ShowPopupWindow")